@@ -102,6 +102,11 @@ fn generate_binding(out_file: &Path) {
                 } else {
                     None
                 },
+                if cfg!(feature = "clhash") {
+                    Some("-DCL_HASH=1")
+                } else {
+                    None
+                },
                 if cfg!(feature = "farm") {
                     Some("-DFARM_HASH=1")
                 } else {
@@ -137,6 +142,11 @@ fn generate_binding(out_file: &Path) {
                 } else {
                     None
                 },
+                if cfg!(feature = "nmhash") {
+                    Some("-DNM_HASH=1")
+                } else {
+                    None
+                },
                 if cfg!(feature = "spooky") {
                     Some("-DSPOOKY_HASH=1")
                 } else {
@@ -147,6 +157,11 @@ fn generate_binding(out_file: &Path) {
                 } else {
                     None
                 },
+                if cfg!(feature = "umash") {
+                    Some("-DUMASH=1")
+                } else {
+                    None
+                },
                 if cfg!(feature = "wy") {
                     Some("-DWY_HASH=1")
                 } else {
@@ -162,21 +177,28 @@ fn generate_binding(out_file: &Path) {
             .flatten(),
         )
         .header("src/fasthash.hpp")
+        .use_core()
+        .ctypes_prefix("::core::ffi")
         .size_t_is_usize(true)
         .generate_inline_functions(true)
         .disable_name_namespacing()
         .allowlist_function("^CityHash.*")
+        .allowlist_var("^RANDOM_.*_FOR_CLHASH$")
+        .allowlist_function("^clhash$")
         .allowlist_function("^farmhash.*")
         .allowlist_function("^lookup3.*")
         .allowlist_function("^metrohash.*")
         .allowlist_function("^mum_hash.*")
         .allowlist_function("^MurmurHash.*")
+        .allowlist_function("^nmhash32.*")
         .allowlist_function("^SpookyHasher.*")
         .allowlist_function("^t1ha.*")
         .blocklist_function("^t1ha_selfcheck__.*")
         .allowlist_function("^XXH.*")
         .allowlist_function("^HighwayHash.*")
         .allowlist_function("^wyhash.*")
+        .allowlist_type("^umash_.*")
+        .allowlist_function("^umash_.*")
         .allowlist_var("^Meow.*")
         .allowlist_function("^Meow.*")
         .generate()
@@ -252,6 +274,10 @@ fn build_fasthash() {
             .file("src/smhasher/MurmurHash3.cpp");
     }
 
+    if cfg!(feature = "nmhash") {
+        build.flag("-DNM_HASH=1");
+    }
+
     if cfg!(feature = "spooky") {
         build
             .flag("-DSPOOKY_HASH=1")
@@ -322,6 +348,37 @@ fn build_t1() {
     build.static_flag(true).compile("t1ha");
 }
 
+fn build_umash() {
+    let mut build = cc::Build::new();
+
+    build.file("src/smhasher/umash.c");
+
+    if cfg!(target_arch = "x86_64") {
+        build.flag("-mpclmul");
+    }
+
+    if cfg!(feature = "native") {
+        build.flag("-march=native");
+    }
+
+    build.static_flag(true).compile("umash");
+}
+
+fn build_clhash() {
+    let mut build = cc::Build::new();
+
+    build
+        .file("src/smhasher/clhash.c")
+        .flag("-msse4.2")
+        .flag("-mpclmul");
+
+    if cfg!(feature = "native") {
+        build.flag("-march=native");
+    }
+
+    build.static_flag(true).compile("clhash");
+}
+
 fn build_highway() {
     let mut build = cc::Build::new();
 
@@ -380,6 +437,12 @@ fn main() {
     if cfg!(feature = "highway") {
         build_highway();
     }
+    if cfg!(feature = "umash") {
+        build_umash();
+    }
+    if cfg!(all(feature = "clhash", target_arch = "x86_64")) {
+        build_clhash();
+    }
 
     let out_dir = env::var("OUT_DIR").unwrap();
     let out_file = Path::new(&out_dir).join("fasthash.rs");
@@ -21,6 +21,11 @@ uint64_t mum_hash_(const void *key, size_t len, uint64_t seed)
     return mum_hash(key, len, seed);
 }
 
+uint64_t mum_hash_aligned_(const void *key, size_t len, uint64_t seed)
+{
+    return _mum_final(_mum_hash_aligned(seed + len, key, len));
+}
+
 #endif
 
 #ifdef SPOOKY_HASH
@@ -36,6 +41,8 @@ void SpookyHasherHash(
 
 void *SpookyHasherNew() { return new SpookyHash(); }
 
+void *SpookyHasherClone(const void *h) { return new SpookyHash(*(const SpookyHash *)h); }
+
 void SpookyHasherFree(void *h) { delete ((SpookyHash *)h); }
 
 void SpookyHasherInit(
@@ -101,6 +108,20 @@ uint64_t wyhash64(const void *key, uint64_t len, uint64_t seed)
 
 #endif
 
+#ifdef NM_HASH
+
+uint32_t nmhash32(const void *key, size_t len, uint32_t seed)
+{
+    return NMHASH32(key, len, seed);
+}
+
+uint32_t nmhash32x(const void *key, size_t len, uint32_t seed)
+{
+    return NMHASH32X(key, len, seed);
+}
+
+#endif
+
 #ifdef MEOW_HASH
 
 void MeowHash128(const void *key, int len, void *seed, void *out)
@@ -5,6 +5,10 @@
 #include "smhasher/CityCrc.h"
 #endif
 
+#ifdef CL_HASH
+#include "smhasher/clhash.h"
+#endif
+
 #ifdef METRO_HASH
 #include "smhasher/metrohash/metrohash.h"
 #endif
@@ -59,6 +63,9 @@ void MeowHashExpandSeed(meow_umm InputLen, void *Input, meow_u8 *SeedResult);
 
 uint64_t mum_hash_(const void *key, size_t len, uint64_t seed);
 
+// requires the key aligned to 8 bytes
+uint64_t mum_hash_aligned_(const void *key, size_t len, uint64_t seed);
+
 #endif
 
 #ifdef SPOOKY_HASH
@@ -73,6 +80,8 @@ void SpookyHasherHash(
 
 void *SpookyHasherNew();
 
+void *SpookyHasherClone(const void *h);
+
 void SpookyHasherFree(void *h);
 
 void SpookyHasherInit(
@@ -110,6 +119,12 @@ void HighwayHash256(const HHKey key, const char *bytes, const uint64_t size, HHR
 
 #endif
 
+#ifdef UMASH
+
+#include "smhasher/umash.h"
+
+#endif
+
 #ifdef WY_HASH
 
 #include "wyhash/wyhash.h"
@@ -117,3 +132,13 @@ void HighwayHash256(const HHKey key, const char *bytes, const uint64_t size, HHR
 uint64_t wyhash64(const void *key, uint64_t len, uint64_t seed);
 
 #endif
+
+#ifdef NM_HASH
+
+#include "smhasher/nmhash.h"
+
+uint32_t nmhash32(const void *key, size_t len, uint32_t seed);
+
+uint32_t nmhash32x(const void *key, size_t len, uint32_t seed);
+
+#endif
@@ -6,8 +6,8 @@ pub type conditional_type<_If> = _If;
 pub struct pair<_T1, _T2> {
     pub first: _T1,
     pub second: _T2,
-    pub _phantom_0: ::std::marker::PhantomData<::std::cell::UnsafeCell<_T1>>,
-    pub _phantom_1: ::std::marker::PhantomData<::std::cell::UnsafeCell<_T2>>,
+    pub _phantom_0: ::core::marker::PhantomData<::core::cell::UnsafeCell<_T1>>,
+    pub _phantom_1: ::core::marker::PhantomData<::core::cell::UnsafeCell<_T2>>,
 }
 pub type pair_first_type<_T1> = _T1;
 pub type pair_second_type<_T2> = _T2;
@@ -28,33 +28,33 @@ pub type HHKey = [u64; 4usize];
 pub type HHResult128 = [u64; 2usize];
 pub type HHResult256 = [u64; 4usize];
 extern "C" {
-    pub fn HighwayHash64(key: *mut u64, bytes: *const ::std::os::raw::c_char, size: u64) -> u64;
+    pub fn HighwayHash64(key: *mut u64, bytes: *const ::core::ffi::c_char, size: u64) -> u64;
 }
 extern "C" {
     pub fn HighwayHash64_TargetPortable(
         key: *mut u64,
-        bytes: *const ::std::os::raw::c_char,
+        bytes: *const ::core::ffi::c_char,
         size: u64,
     ) -> u64;
 }
 extern "C" {
     pub fn HighwayHash64_TargetSSE41(
         key: *mut u64,
-        bytes: *const ::std::os::raw::c_char,
+        bytes: *const ::core::ffi::c_char,
         size: u64,
     ) -> u64;
 }
 extern "C" {
     pub fn HighwayHash64_TargetAVX2(
         key: *mut u64,
-        bytes: *const ::std::os::raw::c_char,
+        bytes: *const ::core::ffi::c_char,
         size: u64,
     ) -> u64;
 }
 extern "C" {
     pub fn HighwayHash64_TargetVSX(
         key: *mut u64,
-        bytes: *const ::std::os::raw::c_char,
+        bytes: *const ::core::ffi::c_char,
         size: u64,
     ) -> u64;
 }
@@ -64,19 +64,19 @@ pub type uint128 = pair<uint64, uint64>;
 extern "C" {
     #[link_name = "\u{1}__Z18CityHash32WithSeedPKcmj"]
     pub fn CityHash32WithSeed(
-        buf: *const ::std::os::raw::c_char,
+        buf: *const ::core::ffi::c_char,
         len: usize,
         seed: uint32,
     ) -> uint32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z10CityHash64PKcm"]
-    pub fn CityHash64(buf: *const ::std::os::raw::c_char, len: usize) -> uint64;
+    pub fn CityHash64(buf: *const ::core::ffi::c_char, len: usize) -> uint64;
 }
 extern "C" {
     #[link_name = "\u{1}__Z18CityHash64WithSeedPKcmy"]
     pub fn CityHash64WithSeed(
-        buf: *const ::std::os::raw::c_char,
+        buf: *const ::core::ffi::c_char,
         len: usize,
         seed: uint64,
     ) -> uint64;
@@ -84,7 +84,7 @@ extern "C" {
 extern "C" {
     #[link_name = "\u{1}__Z19CityHash64WithSeedsPKcmyy"]
     pub fn CityHash64WithSeeds(
-        buf: *const ::std::os::raw::c_char,
+        buf: *const ::core::ffi::c_char,
         len: usize,
         seed0: uint64,
         seed1: uint64,
@@ -92,31 +92,31 @@ extern "C" {
 }
 extern "C" {
     #[link_name = "\u{1}__Z11CityHash128PKcm"]
-    pub fn CityHash128(s: *const ::std::os::raw::c_char, len: usize) -> uint128;
+    pub fn CityHash128(s: *const ::core::ffi::c_char, len: usize) -> uint128;
 }
 extern "C" {
     #[link_name = "\u{1}__Z19CityHash128WithSeedPKcmNSt3__14pairIyyEE"]
     pub fn CityHash128WithSeed(
-        s: *const ::std::os::raw::c_char,
+        s: *const ::core::ffi::c_char,
         len: usize,
         seed: uint128,
     ) -> uint128;
 }
 extern "C" {
     #[link_name = "\u{1}__Z14CityHashCrc128PKcm"]
-    pub fn CityHashCrc128(s: *const ::std::os::raw::c_char, len: usize) -> uint128;
+    pub fn CityHashCrc128(s: *const ::core::ffi::c_char, len: usize) -> uint128;
 }
 extern "C" {
     #[link_name = "\u{1}__Z22CityHashCrc128WithSeedPKcmNSt3__14pairIyyEE"]
     pub fn CityHashCrc128WithSeed(
-        s: *const ::std::os::raw::c_char,
+        s: *const ::core::ffi::c_char,
         len: usize,
         seed: uint128,
     ) -> uint128;
 }
 extern "C" {
     #[link_name = "\u{1}__Z14CityHashCrc256PKcmPy"]
-    pub fn CityHashCrc256(s: *const ::std::os::raw::c_char, len: usize, result: *mut uint64);
+    pub fn CityHashCrc256(s: *const ::core::ffi::c_char, len: usize, result: *mut uint64);
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -127,17 +127,17 @@ pub struct uint128_c_t {
 #[test]
 fn bindgen_test_layout_uint128_c_t() {
     assert_eq!(
-        ::std::mem::size_of::<uint128_c_t>(),
+        ::core::mem::size_of::<uint128_c_t>(),
         16usize,
         concat!("Size of: ", stringify!(uint128_c_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<uint128_c_t>(),
+        ::core::mem::align_of::<uint128_c_t>(),
         8usize,
         concat!("Alignment of ", stringify!(uint128_c_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<uint128_c_t>())).a as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<uint128_c_t>())).a as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -147,7 +147,7 @@ fn bindgen_test_layout_uint128_c_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<uint128_c_t>())).b as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<uint128_c_t>())).b as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -158,59 +158,59 @@ fn bindgen_test_layout_uint128_c_t() {
     );
 }
 extern "C" {
-    pub fn farmhash(s: *const ::std::os::raw::c_char, len: usize) -> usize;
+    pub fn farmhash(s: *const ::core::ffi::c_char, len: usize) -> usize;
 }
 extern "C" {
-    pub fn farmhash32(s: *const ::std::os::raw::c_char, len: usize) -> u32;
+    pub fn farmhash32(s: *const ::core::ffi::c_char, len: usize) -> u32;
 }
 extern "C" {
-    pub fn farmhash32_with_seed(s: *const ::std::os::raw::c_char, len: usize, seed: u32) -> u32;
+    pub fn farmhash32_with_seed(s: *const ::core::ffi::c_char, len: usize, seed: u32) -> u32;
 }
 extern "C" {
-    pub fn farmhash64(s: *const ::std::os::raw::c_char, len: usize) -> u64;
+    pub fn farmhash64(s: *const ::core::ffi::c_char, len: usize) -> u64;
 }
 extern "C" {
-    pub fn farmhash64_with_seed(s: *const ::std::os::raw::c_char, len: usize, seed: u64) -> u64;
+    pub fn farmhash64_with_seed(s: *const ::core::ffi::c_char, len: usize, seed: u64) -> u64;
 }
 extern "C" {
     pub fn farmhash64_with_seeds(
-        s: *const ::std::os::raw::c_char,
+        s: *const ::core::ffi::c_char,
         len: usize,
         seed0: u64,
         seed1: u64,
     ) -> u64;
 }
 extern "C" {
-    pub fn farmhash128(s: *const ::std::os::raw::c_char, len: usize) -> uint128_c_t;
+    pub fn farmhash128(s: *const ::core::ffi::c_char, len: usize) -> uint128_c_t;
 }
 extern "C" {
     pub fn farmhash128_with_seed(
-        s: *const ::std::os::raw::c_char,
+        s: *const ::core::ffi::c_char,
         len: usize,
         seed: uint128_c_t,
     ) -> uint128_c_t;
 }
 extern "C" {
-    pub fn farmhash_fingerprint32(s: *const ::std::os::raw::c_char, len: usize) -> u32;
+    pub fn farmhash_fingerprint32(s: *const ::core::ffi::c_char, len: usize) -> u32;
 }
 extern "C" {
-    pub fn farmhash_fingerprint64(s: *const ::std::os::raw::c_char, len: usize) -> u64;
+    pub fn farmhash_fingerprint64(s: *const ::core::ffi::c_char, len: usize) -> u64;
 }
 extern "C" {
-    pub fn farmhash_fingerprint128(s: *const ::std::os::raw::c_char, len: usize) -> uint128_c_t;
+    pub fn farmhash_fingerprint128(s: *const ::core::ffi::c_char, len: usize) -> uint128_c_t;
 }
-pub type __m128i = [::std::os::raw::c_longlong; 2usize];
+pub type __m128i = [::core::ffi::c_longlong; 2usize];
 extern "C" {
     #[link_name = "\u{1}__ZL15MeowShiftAdjust"]
-    pub static mut MeowShiftAdjust: [::std::os::raw::c_uchar; 32usize];
+    pub static mut MeowShiftAdjust: [::core::ffi::c_uchar; 32usize];
 }
 extern "C" {
     #[link_name = "\u{1}__ZL11MeowMaskLen"]
-    pub static mut MeowMaskLen: [::std::os::raw::c_uchar; 32usize];
+    pub static mut MeowMaskLen: [::core::ffi::c_uchar; 32usize];
 }
 extern "C" {
     #[link_name = "\u{1}__ZL15MeowDefaultSeed"]
-    pub static mut MeowDefaultSeed: [::std::os::raw::c_uchar; 128usize];
+    pub static mut MeowDefaultSeed: [::core::ffi::c_uchar; 128usize];
 }
 #[repr(C)]
 #[repr(align(16))]
@@ -224,25 +224,25 @@ pub struct meow_state {
     pub xmm5: __m128i,
     pub xmm6: __m128i,
     pub xmm7: __m128i,
-    pub TotalLengthInBytes: ::std::os::raw::c_ulonglong,
-    pub BufferLen: ::std::os::raw::c_uint,
-    pub Buffer: [::std::os::raw::c_uchar; 256usize],
+    pub TotalLengthInBytes: ::core::ffi::c_ulonglong,
+    pub BufferLen: ::core::ffi::c_uint,
+    pub Buffer: [::core::ffi::c_uchar; 256usize],
     pub Pad: [__m128i; 2usize],
 }
 #[test]
 fn bindgen_test_layout_meow_state() {
     assert_eq!(
-        ::std::mem::size_of::<meow_state>(),
+        ::core::mem::size_of::<meow_state>(),
         432usize,
         concat!("Size of: ", stringify!(meow_state))
     );
     assert_eq!(
-        ::std::mem::align_of::<meow_state>(),
+        ::core::mem::align_of::<meow_state>(),
         16usize,
         concat!("Alignment of ", stringify!(meow_state))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm0 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm0 as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -252,7 +252,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm1 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm1 as *const _ as usize },
         16usize,
         concat!(
             "Offset of field: ",
@@ -262,7 +262,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm2 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm2 as *const _ as usize },
         32usize,
         concat!(
             "Offset of field: ",
@@ -272,7 +272,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm3 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm3 as *const _ as usize },
         48usize,
         concat!(
             "Offset of field: ",
@@ -282,7 +282,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm4 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm4 as *const _ as usize },
         64usize,
         concat!(
             "Offset of field: ",
@@ -292,7 +292,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm5 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm5 as *const _ as usize },
         80usize,
         concat!(
             "Offset of field: ",
@@ -302,7 +302,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm6 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm6 as *const _ as usize },
         96usize,
         concat!(
             "Offset of field: ",
@@ -312,7 +312,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).xmm7 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).xmm7 as *const _ as usize },
         112usize,
         concat!(
             "Offset of field: ",
@@ -322,7 +322,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).TotalLengthInBytes as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).TotalLengthInBytes as *const _ as usize },
         128usize,
         concat!(
             "Offset of field: ",
@@ -332,7 +332,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).BufferLen as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).BufferLen as *const _ as usize },
         136usize,
         concat!(
             "Offset of field: ",
@@ -342,7 +342,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).Buffer as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).Buffer as *const _ as usize },
         140usize,
         concat!(
             "Offset of field: ",
@@ -352,7 +352,7 @@ fn bindgen_test_layout_meow_state() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<meow_state>())).Pad as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<meow_state>())).Pad as *const _ as usize },
         400usize,
         concat!(
             "Offset of field: ",
@@ -397,92 +397,92 @@ extern "C" {
 extern "C" {
     #[link_name = "\u{1}__Z11MurmurHash1PKvij"]
     pub fn MurmurHash1(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
     ) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z18MurmurHash1AlignedPKvij"]
     pub fn MurmurHash1Aligned(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
     ) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z11MurmurHash2PKvij"]
     pub fn MurmurHash2(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
     ) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z13MurmurHash64APKviy"]
     pub fn MurmurHash64A(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u64,
     ) -> u64;
 }
 extern "C" {
     #[link_name = "\u{1}__Z13MurmurHash64BPKviy"]
     pub fn MurmurHash64B(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u64,
     ) -> u64;
 }
 extern "C" {
     #[link_name = "\u{1}__Z12MurmurHash2APKvij"]
     pub fn MurmurHash2A(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
     ) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z18MurmurHashNeutral2PKvij"]
     pub fn MurmurHashNeutral2(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
     ) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z18MurmurHashAligned2PKvij"]
     pub fn MurmurHashAligned2(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
     ) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z18MurmurHash3_x86_32PKvijPv"]
     pub fn MurmurHash3_x86_32(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
-        out: *mut ::std::os::raw::c_void,
+        out: *mut ::core::ffi::c_void,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z19MurmurHash3_x86_128PKvijPv"]
     pub fn MurmurHash3_x86_128(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
-        out: *mut ::std::os::raw::c_void,
+        out: *mut ::core::ffi::c_void,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z19MurmurHash3_x64_128PKvijPv"]
     pub fn MurmurHash3_x64_128(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
         seed: u32,
-        out: *mut ::std::os::raw::c_void,
+        out: *mut ::core::ffi::c_void,
     );
 }
 #[repr(C)]
@@ -504,17 +504,17 @@ pub struct t1ha_state256__bindgen_ty_1 {
 #[test]
 fn bindgen_test_layout_t1ha_state256__bindgen_ty_1() {
     assert_eq!(
-        ::std::mem::size_of::<t1ha_state256__bindgen_ty_1>(),
+        ::core::mem::size_of::<t1ha_state256__bindgen_ty_1>(),
         32usize,
         concat!("Size of: ", stringify!(t1ha_state256__bindgen_ty_1))
     );
     assert_eq!(
-        ::std::mem::align_of::<t1ha_state256__bindgen_ty_1>(),
+        ::core::mem::align_of::<t1ha_state256__bindgen_ty_1>(),
         8usize,
         concat!("Alignment of ", stringify!(t1ha_state256__bindgen_ty_1))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256__bindgen_ty_1>())).a as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256__bindgen_ty_1>())).a as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -524,7 +524,7 @@ fn bindgen_test_layout_t1ha_state256__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256__bindgen_ty_1>())).b as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256__bindgen_ty_1>())).b as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -534,7 +534,7 @@ fn bindgen_test_layout_t1ha_state256__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256__bindgen_ty_1>())).c as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256__bindgen_ty_1>())).c as *const _ as usize },
         16usize,
         concat!(
             "Offset of field: ",
@@ -544,7 +544,7 @@ fn bindgen_test_layout_t1ha_state256__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256__bindgen_ty_1>())).d as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256__bindgen_ty_1>())).d as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -557,17 +557,17 @@ fn bindgen_test_layout_t1ha_state256__bindgen_ty_1() {
 #[test]
 fn bindgen_test_layout_t1ha_state256() {
     assert_eq!(
-        ::std::mem::size_of::<t1ha_state256>(),
+        ::core::mem::size_of::<t1ha_state256>(),
         32usize,
         concat!("Size of: ", stringify!(t1ha_state256))
     );
     assert_eq!(
-        ::std::mem::align_of::<t1ha_state256>(),
+        ::core::mem::align_of::<t1ha_state256>(),
         8usize,
         concat!("Alignment of ", stringify!(t1ha_state256))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256>())).bytes as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256>())).bytes as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -577,7 +577,7 @@ fn bindgen_test_layout_t1ha_state256() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256>())).u32_ as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256>())).u32_ as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -587,7 +587,7 @@ fn bindgen_test_layout_t1ha_state256() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256>())).u64_ as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256>())).u64_ as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -597,7 +597,7 @@ fn bindgen_test_layout_t1ha_state256() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_state256>())).n as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_state256>())).n as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -620,17 +620,17 @@ pub struct t1ha_context {
 #[test]
 fn bindgen_test_layout_t1ha_context() {
     assert_eq!(
-        ::std::mem::size_of::<t1ha_context>(),
+        ::core::mem::size_of::<t1ha_context>(),
         96usize,
         concat!("Size of: ", stringify!(t1ha_context))
     );
     assert_eq!(
-        ::std::mem::align_of::<t1ha_context>(),
+        ::core::mem::align_of::<t1ha_context>(),
         32usize,
         concat!("Alignment of ", stringify!(t1ha_context))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_context>())).state as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_context>())).state as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -640,7 +640,7 @@ fn bindgen_test_layout_t1ha_context() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_context>())).buffer as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_context>())).buffer as *const _ as usize },
         32usize,
         concat!(
             "Offset of field: ",
@@ -650,7 +650,7 @@ fn bindgen_test_layout_t1ha_context() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_context>())).partial as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_context>())).partial as *const _ as usize },
         64usize,
         concat!(
             "Offset of field: ",
@@ -660,7 +660,7 @@ fn bindgen_test_layout_t1ha_context() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<t1ha_context>())).total as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<t1ha_context>())).total as *const _ as usize },
         72usize,
         concat!(
             "Offset of field: ",
@@ -672,12 +672,12 @@ fn bindgen_test_layout_t1ha_context() {
 }
 pub type t1ha_context_t = t1ha_context;
 extern "C" {
-    pub fn t1ha2_atonce(data: *const ::std::os::raw::c_void, length: usize, seed: u64) -> u64;
+    pub fn t1ha2_atonce(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
 }
 extern "C" {
     pub fn t1ha2_atonce128(
         extra_result: *mut u64,
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         length: usize,
         seed: u64,
     ) -> u64;
@@ -688,7 +688,7 @@ extern "C" {
 extern "C" {
     pub fn t1ha2_update(
         ctx: *mut t1ha_context_t,
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         length: usize,
     );
 }
@@ -696,17 +696,35 @@ extern "C" {
     pub fn t1ha2_final(ctx: *mut t1ha_context_t, extra_result: *mut u64) -> u64;
 }
 extern "C" {
-    pub fn t1ha1_le(data: *const ::std::os::raw::c_void, length: usize, seed: u64) -> u64;
+    pub fn t1ha1_le(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
 }
 extern "C" {
-    pub fn t1ha1_be(data: *const ::std::os::raw::c_void, length: usize, seed: u64) -> u64;
+    pub fn t1ha1_be(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
 }
-pub type t1ha0_function_t = ::std::option::Option<
-    unsafe extern "C" fn(arg1: *const ::std::os::raw::c_void, arg2: usize, arg3: u64) -> u64,
+extern "C" {
+    pub fn t1ha0_32le(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
+}
+extern "C" {
+    pub fn t1ha0_32be(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
+}
+pub type t1ha0_function_t = ::core::option::Option<
+    unsafe extern "C" fn(arg1: *const ::core::ffi::c_void, arg2: usize, arg3: u64) -> u64,
 >;
 extern "C" {
     pub fn t1ha0_resolve() -> t1ha0_function_t;
 }
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "aes"))]
+extern "C" {
+    pub fn t1ha0_ia32aes_noavx(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
+}
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "aes"))]
+extern "C" {
+    pub fn t1ha0_ia32aes_avx(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
+}
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "aes"))]
+extern "C" {
+    pub fn t1ha0_ia32aes_avx2(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
+}
 pub type wyhashmap_t = u64;
 extern "C" {
     #[doc = " @brief Obtains the xxHash version."]
@@ -715,11 +733,11 @@ extern "C" {
     #[doc = " independent of the version defined in the header."]
     #[doc = ""]
     #[doc = " @return `XXH_VERSION_NUMBER` as of when the libray was compiled."]
-    pub fn XXH_versionNumber() -> ::std::os::raw::c_uint;
+    pub fn XXH_versionNumber() -> ::core::ffi::c_uint;
 }
 pub const XXH_errorcode_XXH_OK: XXH_errorcode = 0;
 pub const XXH_errorcode_XXH_ERROR: XXH_errorcode = 1;
-pub type XXH_errorcode = ::std::os::raw::c_uint;
+pub type XXH_errorcode = ::core::ffi::c_uint;
 pub type XXH32_hash_t = u32;
 extern "C" {
     #[doc = " @brief Calculates the 32-bit hash of @p input using xxHash32."]
@@ -743,7 +761,7 @@ extern "C" {
     #[doc = " @see"]
     #[doc = "    XXH32_createState(), XXH32_update(), XXH32_digest(): Streaming version."]
     pub fn XXH32(
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         length: usize,
         seed: XXH32_hash_t,
     ) -> XXH32_hash_t;
@@ -810,7 +828,7 @@ extern "C" {
     #[doc = " @return @ref XXH_OK on success, @ref XXH_ERROR on failure."]
     pub fn XXH32_update(
         statePtr: *mut XXH32_state_t,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         length: usize,
     ) -> XXH_errorcode;
 }
@@ -834,22 +852,22 @@ extern "C" {
 #[derive(Debug, Copy, Clone)]
 pub struct XXH32_canonical_t {
     #[doc = "< Hash bytes, big endian"]
-    pub digest: [::std::os::raw::c_uchar; 4usize],
+    pub digest: [::core::ffi::c_uchar; 4usize],
 }
 #[test]
 fn bindgen_test_layout_XXH32_canonical_t() {
     assert_eq!(
-        ::std::mem::size_of::<XXH32_canonical_t>(),
+        ::core::mem::size_of::<XXH32_canonical_t>(),
         4usize,
         concat!("Size of: ", stringify!(XXH32_canonical_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH32_canonical_t>(),
+        ::core::mem::align_of::<XXH32_canonical_t>(),
         1usize,
         concat!("Alignment of ", stringify!(XXH32_canonical_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_canonical_t>())).digest as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_canonical_t>())).digest as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -904,7 +922,7 @@ extern "C" {
     #[doc = " @see"]
     #[doc = "    XXH64_createState(), XXH64_update(), XXH64_digest(): Streaming version."]
     pub fn XXH64(
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         length: usize,
         seed: XXH64_hash_t,
     ) -> XXH64_hash_t;
@@ -929,7 +947,7 @@ extern "C" {
 extern "C" {
     pub fn XXH64_update(
         statePtr: *mut XXH64_state_t,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         length: usize,
     ) -> XXH_errorcode;
 }
@@ -940,22 +958,22 @@ extern "C" {
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct XXH64_canonical_t {
-    pub digest: [::std::os::raw::c_uchar; 8usize],
+    pub digest: [::core::ffi::c_uchar; 8usize],
 }
 #[test]
 fn bindgen_test_layout_XXH64_canonical_t() {
     assert_eq!(
-        ::std::mem::size_of::<XXH64_canonical_t>(),
+        ::core::mem::size_of::<XXH64_canonical_t>(),
         8usize,
         concat!("Size of: ", stringify!(XXH64_canonical_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH64_canonical_t>(),
+        ::core::mem::align_of::<XXH64_canonical_t>(),
         1usize,
         concat!("Alignment of ", stringify!(XXH64_canonical_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_canonical_t>())).digest as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_canonical_t>())).digest as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1014,20 +1032,20 @@ extern "C" {
     #[doc = " It's also generally simpler to manipulate a scalar return type than a struct."]
     #[doc = ""]
     #[doc = " The API supports one-shot hashing, streaming mode, and custom secrets."]
-    pub fn XXH3_64bits(data: *const ::std::os::raw::c_void, len: usize) -> XXH64_hash_t;
+    pub fn XXH3_64bits(data: *const ::core::ffi::c_void, len: usize) -> XXH64_hash_t;
 }
 extern "C" {
     pub fn XXH3_64bits_withSeed(
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         len: usize,
         seed: XXH64_hash_t,
     ) -> XXH64_hash_t;
 }
 extern "C" {
     pub fn XXH3_64bits_withSecret(
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         len: usize,
-        secret: *const ::std::os::raw::c_void,
+        secret: *const ::core::ffi::c_void,
         secretSize: usize,
     ) -> XXH64_hash_t;
 }
@@ -1056,14 +1074,14 @@ extern "C" {
 extern "C" {
     pub fn XXH3_64bits_reset_withSecret(
         statePtr: *mut XXH3_state_t,
-        secret: *const ::std::os::raw::c_void,
+        secret: *const ::core::ffi::c_void,
         secretSize: usize,
     ) -> XXH_errorcode;
 }
 extern "C" {
     pub fn XXH3_64bits_update(
         statePtr: *mut XXH3_state_t,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         length: usize,
     ) -> XXH_errorcode;
 }
@@ -1085,17 +1103,17 @@ pub struct XXH128_hash_t {
 #[test]
 fn bindgen_test_layout_XXH128_hash_t() {
     assert_eq!(
-        ::std::mem::size_of::<XXH128_hash_t>(),
+        ::core::mem::size_of::<XXH128_hash_t>(),
         16usize,
         concat!("Size of: ", stringify!(XXH128_hash_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH128_hash_t>(),
+        ::core::mem::align_of::<XXH128_hash_t>(),
         8usize,
         concat!("Alignment of ", stringify!(XXH128_hash_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH128_hash_t>())).low64 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH128_hash_t>())).low64 as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1105,7 +1123,7 @@ fn bindgen_test_layout_XXH128_hash_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH128_hash_t>())).high64 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH128_hash_t>())).high64 as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -1116,20 +1134,20 @@ fn bindgen_test_layout_XXH128_hash_t() {
     );
 }
 extern "C" {
-    pub fn XXH3_128bits(data: *const ::std::os::raw::c_void, len: usize) -> XXH128_hash_t;
+    pub fn XXH3_128bits(data: *const ::core::ffi::c_void, len: usize) -> XXH128_hash_t;
 }
 extern "C" {
     pub fn XXH3_128bits_withSeed(
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         len: usize,
         seed: XXH64_hash_t,
     ) -> XXH128_hash_t;
 }
 extern "C" {
     pub fn XXH3_128bits_withSecret(
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         len: usize,
-        secret: *const ::std::os::raw::c_void,
+        secret: *const ::core::ffi::c_void,
         secretSize: usize,
     ) -> XXH128_hash_t;
 }
@@ -1146,14 +1164,14 @@ extern "C" {
 extern "C" {
     pub fn XXH3_128bits_reset_withSecret(
         statePtr: *mut XXH3_state_t,
-        secret: *const ::std::os::raw::c_void,
+        secret: *const ::core::ffi::c_void,
         secretSize: usize,
     ) -> XXH_errorcode;
 }
 extern "C" {
     pub fn XXH3_128bits_update(
         statePtr: *mut XXH3_state_t,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         length: usize,
     ) -> XXH_errorcode;
 }
@@ -1163,7 +1181,7 @@ extern "C" {
 extern "C" {
     #[doc = " XXH128_isEqual():"]
     #[doc = " Return: 1 if `h1` and `h2` are equal, 0 if they are not."]
-    pub fn XXH128_isEqual(h1: XXH128_hash_t, h2: XXH128_hash_t) -> ::std::os::raw::c_int;
+    pub fn XXH128_isEqual(h1: XXH128_hash_t, h2: XXH128_hash_t) -> ::core::ffi::c_int;
 }
 extern "C" {
     #[doc = " XXH128_cmp():"]
@@ -1174,30 +1192,30 @@ extern "C" {
     #[doc = "         =0 if *h128_1 == *h128_2"]
     #[doc = "         <0 if *h128_1  < *h128_2"]
     pub fn XXH128_cmp(
-        h128_1: *const ::std::os::raw::c_void,
-        h128_2: *const ::std::os::raw::c_void,
-    ) -> ::std::os::raw::c_int;
+        h128_1: *const ::core::ffi::c_void,
+        h128_2: *const ::core::ffi::c_void,
+    ) -> ::core::ffi::c_int;
 }
 #[doc = "   Canonical representation"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct XXH128_canonical_t {
-    pub digest: [::std::os::raw::c_uchar; 16usize],
+    pub digest: [::core::ffi::c_uchar; 16usize],
 }
 #[test]
 fn bindgen_test_layout_XXH128_canonical_t() {
     assert_eq!(
-        ::std::mem::size_of::<XXH128_canonical_t>(),
+        ::core::mem::size_of::<XXH128_canonical_t>(),
         16usize,
         concat!("Size of: ", stringify!(XXH128_canonical_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH128_canonical_t>(),
+        ::core::mem::align_of::<XXH128_canonical_t>(),
         1usize,
         concat!("Alignment of ", stringify!(XXH128_canonical_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH128_canonical_t>())).digest as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH128_canonical_t>())).digest as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1248,17 +1266,17 @@ pub struct XXH32_state_s {
 #[test]
 fn bindgen_test_layout_XXH32_state_s() {
     assert_eq!(
-        ::std::mem::size_of::<XXH32_state_s>(),
+        ::core::mem::size_of::<XXH32_state_s>(),
         48usize,
         concat!("Size of: ", stringify!(XXH32_state_s))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH32_state_s>(),
+        ::core::mem::align_of::<XXH32_state_s>(),
         4usize,
         concat!("Alignment of ", stringify!(XXH32_state_s))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).total_len_32 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).total_len_32 as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1268,7 +1286,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).large_len as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).large_len as *const _ as usize },
         4usize,
         concat!(
             "Offset of field: ",
@@ -1278,7 +1296,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).v1 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).v1 as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -1288,7 +1306,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).v2 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).v2 as *const _ as usize },
         12usize,
         concat!(
             "Offset of field: ",
@@ -1298,7 +1316,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).v3 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).v3 as *const _ as usize },
         16usize,
         concat!(
             "Offset of field: ",
@@ -1308,7 +1326,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).v4 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).v4 as *const _ as usize },
         20usize,
         concat!(
             "Offset of field: ",
@@ -1318,7 +1336,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).mem32 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).mem32 as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -1328,7 +1346,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).memsize as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).memsize as *const _ as usize },
         40usize,
         concat!(
             "Offset of field: ",
@@ -1338,7 +1356,7 @@ fn bindgen_test_layout_XXH32_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH32_state_s>())).reserved as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH32_state_s>())).reserved as *const _ as usize },
         44usize,
         concat!(
             "Offset of field: ",
@@ -1383,17 +1401,17 @@ pub struct XXH64_state_s {
 #[test]
 fn bindgen_test_layout_XXH64_state_s() {
     assert_eq!(
-        ::std::mem::size_of::<XXH64_state_s>(),
+        ::core::mem::size_of::<XXH64_state_s>(),
         88usize,
         concat!("Size of: ", stringify!(XXH64_state_s))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH64_state_s>(),
+        ::core::mem::align_of::<XXH64_state_s>(),
         8usize,
         concat!("Alignment of ", stringify!(XXH64_state_s))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).total_len as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).total_len as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1403,7 +1421,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).v1 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).v1 as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -1413,7 +1431,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).v2 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).v2 as *const _ as usize },
         16usize,
         concat!(
             "Offset of field: ",
@@ -1423,7 +1441,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).v3 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).v3 as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -1433,7 +1451,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).v4 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).v4 as *const _ as usize },
         32usize,
         concat!(
             "Offset of field: ",
@@ -1443,7 +1461,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).mem64 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).mem64 as *const _ as usize },
         40usize,
         concat!(
             "Offset of field: ",
@@ -1453,7 +1471,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).memsize as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).memsize as *const _ as usize },
         72usize,
         concat!(
             "Offset of field: ",
@@ -1463,7 +1481,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).reserved32 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).reserved32 as *const _ as usize },
         76usize,
         concat!(
             "Offset of field: ",
@@ -1473,7 +1491,7 @@ fn bindgen_test_layout_XXH64_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH64_state_s>())).reserved64 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH64_state_s>())).reserved64 as *const _ as usize },
         80usize,
         concat!(
             "Offset of field: ",
@@ -1508,8 +1526,8 @@ fn bindgen_test_layout_XXH64_state_s() {
 #[derive(Debug, Copy, Clone)]
 pub struct XXH3_state_s {
     pub acc: [XXH64_hash_t; 8usize],
-    pub customSecret: [::std::os::raw::c_uchar; 192usize],
-    pub buffer: [::std::os::raw::c_uchar; 256usize],
+    pub customSecret: [::core::ffi::c_uchar; 192usize],
+    pub buffer: [::core::ffi::c_uchar; 256usize],
     pub bufferedSize: XXH32_hash_t,
     pub reserved32: XXH32_hash_t,
     pub nbStripesSoFar: usize,
@@ -1518,22 +1536,22 @@ pub struct XXH3_state_s {
     pub secretLimit: usize,
     pub seed: XXH64_hash_t,
     pub reserved64: XXH64_hash_t,
-    pub extSecret: *const ::std::os::raw::c_uchar,
+    pub extSecret: *const ::core::ffi::c_uchar,
 }
 #[test]
 fn bindgen_test_layout_XXH3_state_s() {
     assert_eq!(
-        ::std::mem::size_of::<XXH3_state_s>(),
+        ::core::mem::size_of::<XXH3_state_s>(),
         576usize,
         concat!("Size of: ", stringify!(XXH3_state_s))
     );
     assert_eq!(
-        ::std::mem::align_of::<XXH3_state_s>(),
+        ::core::mem::align_of::<XXH3_state_s>(),
         64usize,
         concat!("Alignment of ", stringify!(XXH3_state_s))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).acc as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).acc as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1543,7 +1561,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).customSecret as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).customSecret as *const _ as usize },
         64usize,
         concat!(
             "Offset of field: ",
@@ -1553,7 +1571,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).buffer as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).buffer as *const _ as usize },
         256usize,
         concat!(
             "Offset of field: ",
@@ -1563,7 +1581,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).bufferedSize as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).bufferedSize as *const _ as usize },
         512usize,
         concat!(
             "Offset of field: ",
@@ -1573,7 +1591,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).reserved32 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).reserved32 as *const _ as usize },
         516usize,
         concat!(
             "Offset of field: ",
@@ -1583,7 +1601,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).nbStripesSoFar as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).nbStripesSoFar as *const _ as usize },
         520usize,
         concat!(
             "Offset of field: ",
@@ -1593,7 +1611,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).totalLen as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).totalLen as *const _ as usize },
         528usize,
         concat!(
             "Offset of field: ",
@@ -1603,7 +1621,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).nbStripesPerBlock as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).nbStripesPerBlock as *const _ as usize },
         536usize,
         concat!(
             "Offset of field: ",
@@ -1613,7 +1631,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).secretLimit as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).secretLimit as *const _ as usize },
         544usize,
         concat!(
             "Offset of field: ",
@@ -1623,7 +1641,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).seed as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).seed as *const _ as usize },
         552usize,
         concat!(
             "Offset of field: ",
@@ -1633,7 +1651,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).reserved64 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).reserved64 as *const _ as usize },
         560usize,
         concat!(
             "Offset of field: ",
@@ -1643,7 +1661,7 @@ fn bindgen_test_layout_XXH3_state_s() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<XXH3_state_s>())).extSecret as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<XXH3_state_s>())).extSecret as *const _ as usize },
         568usize,
         concat!(
             "Offset of field: ",
@@ -1655,14 +1673,14 @@ fn bindgen_test_layout_XXH3_state_s() {
 }
 extern "C" {
     pub fn XXH3_generateSecret(
-        secretBuffer: *mut ::std::os::raw::c_void,
-        customSeed: *const ::std::os::raw::c_void,
+        secretBuffer: *mut ::core::ffi::c_void,
+        customSeed: *const ::core::ffi::c_void,
         customSeedSize: usize,
     );
 }
 extern "C" {
     pub fn XXH128(
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
         len: usize,
         seed: XXH64_hash_t,
     ) -> XXH128_hash_t;
@@ -1670,8 +1688,8 @@ extern "C" {
 extern "C" {
     #[link_name = "\u{1}__Z7lookup3PKcij"]
     pub fn lookup3(
-        key: *const ::std::os::raw::c_char,
-        length: ::std::os::raw::c_int,
+        key: *const ::core::ffi::c_char,
+        length: ::core::ffi::c_int,
         initval: u32,
     ) -> u32;
 }
@@ -1685,12 +1703,16 @@ extern "C" {
 }
 extern "C" {
     #[link_name = "\u{1}__Z9mum_hash_PKvmy"]
-    pub fn mum_hash_(key: *const ::std::os::raw::c_void, len: usize, seed: u64) -> u64;
+    pub fn mum_hash_(key: *const ::core::ffi::c_void, len: usize, seed: u64) -> u64;
+}
+extern "C" {
+    #[link_name = "\u{1}__Z17mum_hash_aligned_PKvmy"]
+    pub fn mum_hash_aligned_(key: *const ::core::ffi::c_void, len: usize, seed: u64) -> u64;
 }
 extern "C" {
     #[link_name = "\u{1}__Z16SpookyHasherHashPKvmPyS1_"]
     pub fn SpookyHasherHash(
-        message: *const ::std::os::raw::c_void,
+        message: *const ::core::ffi::c_void,
         length: usize,
         hash1: *mut uint64,
         hash2: *mut uint64,
@@ -1698,41 +1720,45 @@ extern "C" {
 }
 extern "C" {
     #[link_name = "\u{1}__Z15SpookyHasherNewv"]
-    pub fn SpookyHasherNew() -> *mut ::std::os::raw::c_void;
+    pub fn SpookyHasherNew() -> *mut ::core::ffi::c_void;
+}
+extern "C" {
+    #[link_name = "\u{1}__Z17SpookyHasherClonePKv"]
+    pub fn SpookyHasherClone(h: *const ::core::ffi::c_void) -> *mut ::core::ffi::c_void;
 }
 extern "C" {
     #[link_name = "\u{1}__Z16SpookyHasherFreePv"]
-    pub fn SpookyHasherFree(h: *mut ::std::os::raw::c_void);
+    pub fn SpookyHasherFree(h: *mut ::core::ffi::c_void);
 }
 extern "C" {
     #[link_name = "\u{1}__Z16SpookyHasherInitPvyy"]
-    pub fn SpookyHasherInit(h: *mut ::std::os::raw::c_void, seed1: uint64, seed2: uint64);
+    pub fn SpookyHasherInit(h: *mut ::core::ffi::c_void, seed1: uint64, seed2: uint64);
 }
 extern "C" {
     #[link_name = "\u{1}__Z18SpookyHasherUpdatePvPKvm"]
     pub fn SpookyHasherUpdate(
-        h: *mut ::std::os::raw::c_void,
-        message: *const ::std::os::raw::c_void,
+        h: *mut ::core::ffi::c_void,
+        message: *const ::core::ffi::c_void,
         length: usize,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z17SpookyHasherFinalPvPyS0_"]
     pub fn SpookyHasherFinal(
-        h: *mut ::std::os::raw::c_void,
+        h: *mut ::core::ffi::c_void,
         hash1: *mut uint64,
         hash2: *mut uint64,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z8t1ha0_64PKvmy"]
-    pub fn t1ha0_64(data: *const ::std::os::raw::c_void, length: usize, seed: u64) -> u64;
+    pub fn t1ha0_64(data: *const ::core::ffi::c_void, length: usize, seed: u64) -> u64;
 }
 extern "C" {
     #[link_name = "\u{1}__Z14HighwayHash128PKyPKcyRA2_y"]
     pub fn HighwayHash128(
         key: *mut u64,
-        bytes: *const ::std::os::raw::c_char,
+        bytes: *const ::core::ffi::c_char,
         size: u64,
         hash: *mut HHResult128,
     );
@@ -1741,52 +1767,60 @@ extern "C" {
     #[link_name = "\u{1}__Z14HighwayHash256PKyPKcyRA4_y"]
     pub fn HighwayHash256(
         key: *mut u64,
-        bytes: *const ::std::os::raw::c_char,
+        bytes: *const ::core::ffi::c_char,
         size: u64,
         hash: *mut HHResult256,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z8wyhash64PKvyy"]
-    pub fn wyhash64(key: *const ::std::os::raw::c_void, len: u64, seed: u64) -> u64;
+    pub fn wyhash64(key: *const ::core::ffi::c_void, len: u64, seed: u64) -> u64;
+}
+extern "C" {
+    #[link_name = "\u{1}__Z8nmhash32PKvmj"]
+    pub fn nmhash32(key: *const ::core::ffi::c_void, len: usize, seed: u32) -> u32;
+}
+extern "C" {
+    #[link_name = "\u{1}__Z9nmhash32xPKvmj"]
+    pub fn nmhash32x(key: *const ::core::ffi::c_void, len: usize, seed: u32) -> u32;
 }
 extern "C" {
     #[link_name = "\u{1}__Z11MeowHash128PKviPvS1_"]
     pub fn MeowHash128(
-        key: *const ::std::os::raw::c_void,
-        len: ::std::os::raw::c_int,
-        seed: *mut ::std::os::raw::c_void,
-        out: *mut ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
+        len: ::core::ffi::c_int,
+        seed: *mut ::core::ffi::c_void,
+        out: *mut ::core::ffi::c_void,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z13MeowHashBeginP10meow_statePv"]
-    pub fn MeowHashBegin(State: *mut meow_state, Seed128: *mut ::std::os::raw::c_void);
+    pub fn MeowHashBegin(State: *mut meow_state, Seed128: *mut ::core::ffi::c_void);
 }
 extern "C" {
     #[link_name = "\u{1}__Z14MeowHashUpdateP10meow_statemPv"]
     pub fn MeowHashUpdate(
         State: *mut meow_state,
         Len: usize,
-        SourceInit: *mut ::std::os::raw::c_void,
+        SourceInit: *mut ::core::ffi::c_void,
     );
 }
 extern "C" {
     #[link_name = "\u{1}__Z11MeowHashEndP10meow_statePv"]
-    pub fn MeowHashEnd(State: *mut meow_state, out: *mut ::std::os::raw::c_void);
+    pub fn MeowHashEnd(State: *mut meow_state, out: *mut ::core::ffi::c_void);
 }
 extern "C" {
     #[link_name = "\u{1}__Z18MeowHashExpandSeedyPvPh"]
     pub fn MeowHashExpandSeed(
-        InputLen: ::std::os::raw::c_ulonglong,
-        Input: *mut ::std::os::raw::c_void,
-        SeedResult: *mut ::std::os::raw::c_uchar,
+        InputLen: ::core::ffi::c_ulonglong,
+        Input: *mut ::core::ffi::c_void,
+        SeedResult: *mut ::core::ffi::c_uchar,
     );
 }
 #[test]
 fn __bindgen_test_layout_pair_open0_uint64_uint64_close0_instantiation() {
     assert_eq!(
-        ::std::mem::size_of::<pair<uint64, uint64>>(),
+        ::core::mem::size_of::<pair<uint64, uint64>>(),
         16usize,
         concat!(
             "Size of template specialization: ",
@@ -1794,7 +1828,7 @@ fn __bindgen_test_layout_pair_open0_uint64_uint64_close0_instantiation() {
         )
     );
     assert_eq!(
-        ::std::mem::align_of::<pair<uint64, uint64>>(),
+        ::core::mem::align_of::<pair<uint64, uint64>>(),
         8usize,
         concat!(
             "Alignment of template specialization: ",
@@ -1802,3 +1836,122 @@ fn __bindgen_test_layout_pair_open0_uint64_uint64_close0_instantiation() {
         )
     );
 }
+pub const UMASH_OH_PARAM_COUNT: ::core::ffi::c_uint = 32;
+pub const UMASH_OH_TWISTING_COUNT: ::core::ffi::c_uint = 2;
+#[doc = " A single UMASH params struct stores the parameters for a pair of"]
+#[doc = " independent `UMASH` functions."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct umash_params {
+    pub poly: [[u64; 2usize]; 2usize],
+    pub oh: [u64; 34usize],
+}
+#[test]
+fn bindgen_test_layout_umash_params() {
+    assert_eq!(
+        ::core::mem::size_of::<umash_params>(),
+        304usize,
+        concat!("Size of: ", stringify!(umash_params))
+    );
+    assert_eq!(
+        ::core::mem::align_of::<umash_params>(),
+        8usize,
+        concat!("Alignment of ", stringify!(umash_params))
+    );
+    assert_eq!(
+        unsafe { &(*(::core::ptr::null::<umash_params>())).poly as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(umash_params),
+            "::",
+            stringify!(poly)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::core::ptr::null::<umash_params>())).oh as *const _ as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(umash_params),
+            "::",
+            stringify!(oh)
+        )
+    );
+}
+#[doc = " A fingerprint consists of two independent `UMASH` hash values."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct umash_fp {
+    pub hash: [u64; 2usize],
+}
+#[test]
+fn bindgen_test_layout_umash_fp() {
+    assert_eq!(
+        ::core::mem::size_of::<umash_fp>(),
+        16usize,
+        concat!("Size of: ", stringify!(umash_fp))
+    );
+    assert_eq!(
+        ::core::mem::align_of::<umash_fp>(),
+        8usize,
+        concat!("Alignment of ", stringify!(umash_fp))
+    );
+    assert_eq!(
+        unsafe { &(*(::core::ptr::null::<umash_fp>())).hash as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(umash_fp),
+            "::",
+            stringify!(hash)
+        )
+    );
+}
+extern "C" {
+    #[doc = " Converts a `umash_params` struct filled with random values into"]
+    #[doc = " something usable by the UMASH functions below."]
+    pub fn umash_params_prepare(params: *mut umash_params) -> bool;
+}
+extern "C" {
+    #[doc = " Deterministically derives a `umash_params` struct from `bits` and"]
+    #[doc = " `key`.  The `bits` values do not have to be particularly well"]
+    #[doc = " distributed, and can be generated sequentially."]
+    pub fn umash_params_derive(
+        params: *mut umash_params,
+        bits: u64,
+        key: *const ::core::ffi::c_void,
+    );
+}
+extern "C" {
+    #[doc = " Computes the UMASH hash of `data[0 ... n_bytes)`."]
+    pub fn umash_full(
+        params: *const umash_params,
+        seed: u64,
+        which: ::core::ffi::c_int,
+        data: *const ::core::ffi::c_void,
+        n_bytes: usize,
+    ) -> u64;
+}
+extern "C" {
+    #[doc = " Computes the UMASH fingerprint of `data[0 ... n_bytes)`."]
+    pub fn umash_fprint(
+        params: *const umash_params,
+        seed: u64,
+        data: *const ::core::ffi::c_void,
+        n_bytes: usize,
+    ) -> umash_fp;
+}
+pub const RANDOM_64BITWORDS_NEEDED_FOR_CLHASH: ::core::ffi::c_uint = 133;
+pub const RANDOM_BYTES_NEEDED_FOR_CLHASH: ::core::ffi::c_uint = 1064;
+extern "C" {
+    #[doc = " random : the random data source (should contain at least"]
+    #[doc = " RANDOM_BYTES_NEEDED_FOR_CLHASH random bytes), it should"]
+    #[doc = " also be aligned on 16-byte boundaries so that (__m128i *) random"]
+    #[doc = " makes sense."]
+    pub fn clhash(
+        random: *const ::core::ffi::c_void,
+        stringbyte: *const ::core::ffi::c_char,
+        lengthbyte: usize,
+    ) -> u64;
+}
@@ -1,3 +1,4 @@
+#![no_std]
 #![allow(
     non_camel_case_types,
     non_snake_case,
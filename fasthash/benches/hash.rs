@@ -41,52 +41,64 @@ fn bench_memory(c: &mut Criterion) {
 }
 
 fn bench_hash32(c: &mut Criterion) {
+    let mut bench = ParameterizedBenchmark::new(
+        "city::hash32",
+        move |b, &&size| {
+            b.iter(|| city::hash32_with_seed(&DATA.as_slice()[..size], SEED as u32));
+        },
+        &PARAMS,
+    )
+    .with_function("farm::hash32", move |b, &&size| {
+        b.iter(|| farm::hash32_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("farm::finterprint32", move |b, &&size| {
+        b.iter(|| farm::fingerprint32(&DATA[..size]));
+    })
+    .with_function("lookup3::hash32", move |b, &&size| {
+        b.iter(|| lookup3::hash32_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur::hash32", move |b, &&size| {
+        b.iter(|| murmur::hash32_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur::hash32_aligned", move |b, &&size| {
+        b.iter(|| murmur::hash32_aligned_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur2::hash32", move |b, &&size| {
+        b.iter(|| murmur2::Hash32::hash_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur2::hash32_a", move |b, &&size| {
+        b.iter(|| murmur2::Hash32A::hash_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur2::hash32_neutral", move |b, &&size| {
+        b.iter(|| murmur2::Hash32Neutral::hash_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur2::hash32_aligned", move |b, &&size| {
+        b.iter(|| murmur2::Hash32Aligned::hash_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("murmur3::hash32", move |b, &&size| {
+        b.iter(|| murmur3::hash32_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("spooky::hash32", move |b, &&size| {
+        b.iter(|| spooky::hash32_with_seed(&DATA[..size], SEED as u32));
+    })
+    .with_function("xx::hash32", move |b, &&size| {
+        b.iter(|| xx::hash32_with_seed(&DATA[..size], SEED as u32));
+    });
+
+    #[cfg(feature = "t1ha")]
+    {
+        bench = bench
+            .with_function("t1ha0::hash32", move |b, &&size| {
+                b.iter(|| t1ha0::Hash32::hash_with_seed(&DATA[..size], SEED));
+            })
+            .with_function("t1ha0::hash64_32le", move |b, &&size| {
+                b.iter(|| t1ha0::Hash64_32Le::hash_with_seed(&DATA[..size], SEED) as u32);
+            });
+    }
+
     c.bench(
         "hash32",
-        ParameterizedBenchmark::new(
-            "city::hash32",
-            move |b, &&size| {
-                b.iter(|| city::hash32_with_seed(&DATA.as_slice()[..size], SEED as u32));
-            },
-            &PARAMS,
-        )
-        .with_function("farm::hash32", move |b, &&size| {
-            b.iter(|| farm::hash32_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("farm::finterprint32", move |b, &&size| {
-            b.iter(|| farm::fingerprint32(&DATA[..size]));
-        })
-        .with_function("lookup3::hash32", move |b, &&size| {
-            b.iter(|| lookup3::hash32_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur::hash32", move |b, &&size| {
-            b.iter(|| murmur::hash32_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur::hash32_aligned", move |b, &&size| {
-            b.iter(|| murmur::hash32_aligned_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur2::hash32", move |b, &&size| {
-            b.iter(|| murmur2::Hash32::hash_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur2::hash32_a", move |b, &&size| {
-            b.iter(|| murmur2::Hash32A::hash_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur2::hash32_neutral", move |b, &&size| {
-            b.iter(|| murmur2::Hash32Neutral::hash_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur2::hash32_aligned", move |b, &&size| {
-            b.iter(|| murmur2::Hash32Aligned::hash_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("murmur3::hash32", move |b, &&size| {
-            b.iter(|| murmur3::hash32_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("spooky::hash32", move |b, &&size| {
-            b.iter(|| spooky::hash32_with_seed(&DATA[..size], SEED as u32));
-        })
-        .with_function("xx::hash32", move |b, &&size| {
-            b.iter(|| xx::hash32_with_seed(&DATA[..size], SEED as u32));
-        })
-        .throughput(|&&size| Throughput::Bytes(size as u64)),
+        bench.throughput(|&&size| Throughput::Bytes(size as u64)),
     );
 }
 
@@ -214,11 +226,12 @@ fn bench_hash128(c: &mut Criterion) {
         });
     }
 
+    bench = bench.with_function("city::crc::hash128", move |b, &&size| {
+        b.iter(|| city::crc::Hash128::hash_with_seed(&DATA[..size], SEED as u128));
+    });
+
     if cfg!(any(feature = "sse4.2", target_feature = "sse4.2")) {
         bench = bench
-            .with_function("city::crc::hash128", move |b, &&size| {
-                b.iter(|| city::crc::Hash128::hash_with_seed(&DATA[..size], SEED as u128));
-            })
             .with_function("metro::crc::hash128_1", move |b, &&size| {
                 b.iter(|| metro::crc::Hash128_1::hash_with_seed(&DATA[..size], SEED as u32));
             })
@@ -239,11 +252,93 @@ fn bench_hash128(c: &mut Criterion) {
     );
 }
 
+/// The keys of 4 to 16 bytes, the lengths of the symbols of a symbol table.
+fn short_keys(n: usize) -> Vec<&'static [u8]> {
+    (0..n)
+        .map(|i| &DATA[i * 16..i * 16 + 4 + i % 13])
+        .collect::<Vec<_>>()
+}
+
+fn bench_many(c: &mut Criterion) {
+    c.bench(
+        "hash32 of 4 to 16 bytes keys",
+        ParameterizedBenchmark::new(
+            "city::hash32 loop",
+            move |b, &&n| {
+                let keys = short_keys(n);
+
+                b.iter(|| {
+                    keys.iter()
+                        .map(|key| city::hash32_with_seed(key, SEED as u32))
+                        .collect::<Vec<_>>()
+                });
+            },
+            &[16, 256],
+        )
+        .with_function("city::hash32_many", move |b, &&n| {
+            let keys = short_keys(n);
+
+            b.iter(|| city::hash32_many(&keys, SEED as u32));
+        })
+        .throughput(|&&n| Throughput::Elements(n as u64)),
+    );
+
+    if cfg!(any(feature = "sse42", target_feature = "sse4.2")) {
+        c.bench(
+            "hash64 of 4 to 16 bytes keys",
+            ParameterizedBenchmark::new(
+                "metro::crc::hash64_1 loop",
+                move |b, &&n| {
+                    let keys = short_keys(n);
+
+                    b.iter(|| {
+                        keys.iter()
+                            .map(|key| metro::crc::Hash64_1::hash_with_seed(key, SEED as u32))
+                            .collect::<Vec<_>>()
+                    });
+                },
+                &[16, 256],
+            )
+            .with_function("metro::hash64_crc_many", move |b, &&n| {
+                let keys = short_keys(n);
+
+                b.iter(|| metro::hash64_crc_many(&keys, SEED as u32));
+            })
+            .throughput(|&&n| Throughput::Elements(n as u64)),
+        );
+    }
+}
+
+/// The key lengths around `smart::CITY_MIN_LEN`, where `smart::hash64` switches to `CityHash`.
+const CROSSOVER_PARAMS: [usize; 8] = [16, 32, 48, 56, 64, 72, 96, 128];
+
+fn bench_crossover(c: &mut Criterion) {
+    c.bench(
+        "smart::hash64 crossover",
+        ParameterizedBenchmark::new(
+            "city::hash64",
+            move |b, &&size| {
+                b.iter(|| city::hash64(&DATA[..size]));
+            },
+            &CROSSOVER_PARAMS,
+        )
+        .with_function("farm::hash64", move |b, &&size| {
+            b.iter(|| farm::hash64(&DATA[..size]));
+        })
+        .with_function("smart::hash64", move |b, &&size| {
+            b.iter(|| smart::hash64(&DATA[..size]));
+        })
+        .throughput(|&&size| Throughput::Bytes(size as u64)),
+    );
+}
+
 criterion_group!(
     benches,
     bench_memory,
     bench_hash32,
     bench_hash64,
     bench_hash128,
+    bench_many,
+    bench_crossover,
 );
 criterion_main!(benches);
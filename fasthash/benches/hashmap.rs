@@ -0,0 +1,127 @@
+//! Compares the `HashMap` throughput of the hash functions with the default SipHash.
+//!
+//! The default `std::collections::hash_map::RandomState` resists `HashDoS` attacks,
+//! which the fast hash functions don't, so only switch when the speedup matters.
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use criterion::measurement::WallTime;
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput,
+};
+
+use fasthash::*;
+
+const ITEMS: usize = 1000;
+
+fn bench_map<K, S>(group: &mut BenchmarkGroup<WallTime>, name: &str, keys: &[K], s: S)
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Clone,
+{
+    group.bench_function(format!("{}/insert", name), |b| {
+        b.iter(|| {
+            let mut map = HashMap::with_capacity_and_hasher(keys.len(), s.clone());
+
+            for (i, key) in keys.iter().enumerate() {
+                map.insert(key.clone(), i as u64);
+            }
+
+            black_box(map)
+        })
+    });
+
+    let mut map = HashMap::with_capacity_and_hasher(keys.len(), s);
+
+    map.extend(keys.iter().cloned().zip(0u64..));
+
+    group.bench_function(format!("{}/lookup", name), |b| {
+        b.iter(|| keys.iter().map(|key| map[key]).sum::<u64>())
+    });
+}
+
+macro_rules! bench_hashers {
+    ($group:expr, $keys:expr) => {
+        bench_map($group, "std::SipHash", $keys, hash_map::RandomState::new());
+
+        bench_map(
+            $group,
+            "ahash",
+            $keys,
+            RandomState::<fasthash::ahash::Hash64>::new(),
+        );
+        bench_map($group, "city64", $keys, RandomState::<city::Hash64>::new());
+        bench_map($group, "farm64", $keys, RandomState::<farm::Hash64>::new());
+        bench_map(
+            $group,
+            "metro64",
+            $keys,
+            RandomState::<metro::Hash64_1>::new(),
+        );
+        bench_map($group, "mum64", $keys, RandomState::<mum::Hash64>::new());
+        bench_map(
+            $group,
+            "murmur3_32",
+            $keys,
+            RandomState::<murmur3::Hash32>::new(),
+        );
+        bench_map(
+            $group,
+            "pengy64",
+            $keys,
+            RandomState::<pengy::Hash64>::new(),
+        );
+        bench_map($group, "sea64", $keys, RandomState::<sea::Hash64>::new());
+        bench_map(
+            $group,
+            "spooky64",
+            $keys,
+            RandomState::<spooky::Hash64>::new(),
+        );
+        bench_map(
+            $group,
+            "t1ha0_64",
+            $keys,
+            RandomState::<t1ha0::Hash64>::new(),
+        );
+        bench_map(
+            $group,
+            "umash64",
+            $keys,
+            CachedState::<umash::Hash64>::new(),
+        );
+        bench_map($group, "wy64", $keys, RandomState::<wy::Hash64>::new());
+        bench_map($group, "xxh64", $keys, RandomState::<xx::Hash64>::new());
+        bench_map($group, "xxh3_64", $keys, RandomState::<xxh3::Hash64>::new());
+    };
+}
+
+fn bench_u64_keys(c: &mut Criterion) {
+    let keys = (0..ITEMS as u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect::<Vec<_>>();
+    let mut group = c.benchmark_group("hashmap_u64");
+
+    group.throughput(Throughput::Elements(ITEMS as u64));
+
+    bench_hashers!(&mut group, &keys);
+
+    group.finish();
+}
+
+fn bench_string_keys(c: &mut Criterion) {
+    let keys = (0..ITEMS)
+        .map(|i| format!("key-{:08}", i))
+        .collect::<Vec<_>>();
+    let mut group = c.benchmark_group("hashmap_string");
+
+    group.throughput(Throughput::Elements(ITEMS as u64));
+
+    bench_hashers!(&mut group, &keys);
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_u64_keys, bench_string_keys);
+criterion_main!(benches);
@@ -0,0 +1,160 @@
+//! Measures the throughput of a hash algorithm selected by name.
+//!
+//! ```text
+//! cargo run --release --example bench -- <algorithm> [size] [--stream]
+//! ```
+//!
+//! Hashes `size` bytes (1 MiB by default) with the algorithm, in one `write`,
+//! or in 4 KiB chunks with `--stream`, and prints the throughput.
+use std::env;
+use std::hash::{BuildHasher, Hasher};
+use std::process;
+use std::time::{Duration, Instant};
+
+use fasthash::any::{AnyAlgorithm, AnyState};
+use fasthash::Seed;
+
+const DEFAULT_SIZE: usize = 1 << 20;
+const CHUNK_SIZE: usize = 4096;
+const MIN_DURATION: Duration = Duration::from_millis(500);
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    algorithm: AnyAlgorithm,
+    size: usize,
+    stream: bool,
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut algorithm = None;
+    let mut size = None;
+    let mut stream = false;
+
+    for arg in args {
+        if arg == "--stream" {
+            stream = true;
+        } else if algorithm.is_none() {
+            algorithm = Some(arg.parse::<AnyAlgorithm>().map_err(|err| err.to_string())?);
+        } else if size.is_none() {
+            size = Some(
+                arg.parse::<usize>()
+                    .map_err(|err| format!("invalid size `{}`: {}", arg, err))?,
+            );
+        } else {
+            return Err(format!("unexpected argument `{}`", arg));
+        }
+    }
+
+    Ok(Options {
+        algorithm: algorithm.ok_or("missing the algorithm name")?,
+        size: size.unwrap_or(DEFAULT_SIZE),
+        stream,
+    })
+}
+
+/// Hashes the data once, returning the hash to keep it from being optimized out.
+fn hash_once(s: &AnyState, data: &[u8], stream: bool) -> u64 {
+    let mut h = s.build_hasher();
+
+    if stream {
+        for chunk in data.chunks(CHUNK_SIZE) {
+            h.write(chunk);
+        }
+    } else {
+        h.write(data);
+    }
+
+    h.finish()
+}
+
+/// Repeats hashing for at least `min_duration`, returning the throughput in MiB/s.
+fn run(opts: &Options, min_duration: Duration) -> f64 {
+    let data = (0..opts.size).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+    let s = AnyState::with_seed(opts.algorithm, Seed::gen());
+    let start = Instant::now();
+    let mut iters = 0_u64;
+    let mut acc = 0_u64;
+
+    while iters == 0 || start.elapsed() < min_duration {
+        acc ^= hash_once(&s, &data, opts.stream);
+        iters += 1;
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+
+    if acc == 0 {
+        // unlikely, only printed to use the hashes
+        eprintln!("the hashes xored into zero");
+    }
+
+    (opts.size as u64 * iters) as f64 / elapsed / (1 << 20) as f64
+}
+
+fn main() {
+    let opts = match parse_args(env::args().skip(1)) {
+        Ok(opts) => opts,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            eprintln!("usage: bench <algorithm> [size] [--stream]");
+            process::exit(2);
+        }
+    };
+
+    println!(
+        "{} {} bytes{}: {:.2} MiB/s",
+        opts.algorithm.name(),
+        opts.size,
+        if opts.stream { " (stream)" } else { "" },
+        run(&opts, MIN_DURATION)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let opts = parse_args(args(&["xxh64", "1024", "--stream"])).unwrap();
+
+        assert_eq!(opts.algorithm, AnyAlgorithm::XXHash64);
+        assert_eq!(opts.size, 1024);
+        assert!(opts.stream);
+
+        let opts = parse_args(args(&["xxhash64"])).unwrap();
+
+        assert_eq!(opts.size, DEFAULT_SIZE);
+        assert!(!opts.stream);
+
+        let err = parse_args(args(&["no-such-hash"])).unwrap_err();
+        assert!(
+            err.starts_with("unknown hash algorithm `no-such-hash`"),
+            "{}",
+            err
+        );
+
+        assert!(parse_args(args(&[])).is_err());
+        assert!(parse_args(args(&["xxh64", "big"])).is_err());
+        assert!(parse_args(args(&["xxh64", "1", "2"])).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let data = (0..10_000usize).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        let s = AnyState::with_seed(AnyAlgorithm::XXHash64, Seed::gen());
+
+        assert_eq!(hash_once(&s, &data, true), hash_once(&s, &data, false));
+
+        let opts = Options {
+            algorithm: AnyAlgorithm::XXHash64,
+            size: 10_000,
+            stream: true,
+        };
+
+        assert!(run(&opts, Duration::from_millis(1)) > 0.0);
+    }
+}
@@ -1,13 +1,17 @@
 //! `aHash`
 //!
-use std::hash::Hasher;
+use core::hash::Hasher;
 
-use crate::hasher::{FastHash, FastHasher, StreamHasher};
+#[cfg(feature = "std")]
+use crate::hasher::StreamHasher;
+use crate::hasher::{FastHash, FastHasher};
 
 pub use ahash::AHasher;
 
 /// `aHash` 64-bit hash functions
 ///
+/// The seed `(key1, key2)` is passed in order to `AHasher::new_with_keys`.
+///
 /// # Example
 ///
 /// ```
@@ -56,6 +60,7 @@ impl FastHasher for AHasher {
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for AHasher {}
 
 /// `aHash` 64-bit hash function using supplied seed..
@@ -71,3 +76,22 @@ impl StreamHasher for AHasher {}
 pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seeds: (u128, u128)) -> u64 {
     Hash64::hash_with_seed(v, seeds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_slots() {
+        let mut h = AHasher::new_with_keys(123, 456);
+        h.write(b"hello");
+        let h = h.finish();
+
+        assert_eq!(Hash64::hash_with_seed(b"hello", (123, 456)), h);
+        assert_ne!(Hash64::hash_with_seed(b"hello", (456, 123)), h);
+
+        let mut hasher = <AHasher as FastHasher>::with_seed((123, 456));
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish(), h);
+    }
+}
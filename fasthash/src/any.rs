@@ -0,0 +1,352 @@
+//! Runtime selection of the hash algorithm
+//!
+//! `AnyState` picks one of the hash algorithms of the crate by name,
+//! and builds `AnyHasher`, which dispatches to the selected hasher.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use fasthash::any::AnyState;
+//!
+//! let s = AnyState::from_name("xxhash64").unwrap();
+//! let mut map = HashMap::with_hasher(s);
+//!
+//! assert_eq!(map.insert(37, "a"), None);
+//! assert_eq!(map.insert(37, "b"), Some("a"));
+//! assert_eq!(map[&37], "b");
+//!
+//! assert!(AnyState::from_name("unknown").is_err());
+//! ```
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+
+use crate::hasher::{BuildHasherExt, FastHasher, Seed};
+
+macro_rules! any_hashers {
+    // keeps the registered hash functions with a variant
+    (@select [$($selected:tt)*]) => {
+        any_hashers! { @impl $($selected)* }
+    };
+    (@select [$($selected:tt)*]
+        #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path as $variant:ident,
+        $($rest:tt)*
+    ) => {
+        any_hashers! {
+            @select [$($selected)* #[cfg($cfg)] $name $(| $alias)* => $hash as $variant,]
+            $($rest)*
+        }
+    };
+    (@select [$($selected:tt)*]
+        #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path,
+        $($rest:tt)*
+    ) => {
+        any_hashers! { @select [$($selected)*] $($rest)* }
+    };
+    (@impl $( #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path as $variant:ident, )*) => {
+        /// The hash algorithms which could be selected at runtime.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum AnyAlgorithm {
+            $(
+                #[doc = $name]
+                #[cfg($cfg)]
+                $variant,
+            )*
+        }
+
+        impl AnyAlgorithm {
+            /// All the enabled algorithms.
+            pub const ALL: &'static [AnyAlgorithm] = &[
+                $(
+                    #[cfg($cfg)]
+                    AnyAlgorithm::$variant,
+                )*
+            ];
+
+            /// Returns the algorithm with the given name or alias, ignoring ASCII case.
+            pub fn from_name(name: &str) -> Option<AnyAlgorithm> {
+                $(
+                    #[cfg($cfg)]
+                    {
+                        if name.eq_ignore_ascii_case($name)
+                            $(|| name.eq_ignore_ascii_case($alias))* {
+                            return Some(AnyAlgorithm::$variant);
+                        }
+                    }
+                )*
+
+                None
+            }
+
+            /// Returns the aliases of the algorithm.
+            pub fn aliases(&self) -> &'static [&'static str] {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyAlgorithm::$variant => &[$($alias),*],
+                    )*
+                }
+            }
+
+            /// Returns the name of the algorithm.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyAlgorithm::$variant => $name,
+                    )*
+                }
+            }
+
+            /// Constructs a hasher of the algorithm with the seed.
+            pub fn hasher_with_seed(&self, seed: Seed) -> AnyHasher {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyAlgorithm::$variant => AnyHasher::$variant(
+                            <$hash as BuildHasherExt>::FastHasher::with_seed(seed.into()),
+                        ),
+                    )*
+                }
+            }
+        }
+
+        /// A `Hasher` which dispatches to the hasher of the selected algorithm.
+        #[derive(Clone)]
+        pub enum AnyHasher {
+            $(
+                #[doc = $name]
+                #[cfg($cfg)]
+                $variant(<$hash as BuildHasherExt>::FastHasher),
+            )*
+        }
+
+        impl AnyHasher {
+            /// Returns the algorithm of the hasher.
+            pub fn algorithm(&self) -> AnyAlgorithm {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyHasher::$variant(_) => AnyAlgorithm::$variant,
+                    )*
+                }
+            }
+        }
+
+        impl Hasher for AnyHasher {
+            #[inline(always)]
+            fn finish(&self) -> u64 {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyHasher::$variant(ref h) => h.finish(),
+                    )*
+                }
+            }
+
+            #[inline(always)]
+            fn write(&mut self, bytes: &[u8]) {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyHasher::$variant(ref mut h) => h.write(bytes),
+                    )*
+                }
+            }
+        }
+    };
+    ($($registered:tt)*) => {
+        any_hashers! { @select [] $($registered)* }
+    };
+}
+
+fast_hashes!(any_hashers);
+
+/// The error returned when the name of an algorithm is unknown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownAlgorithm(String);
+
+impl UnknownAlgorithm {
+    /// The unknown name.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for UnknownAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown hash algorithm `{}`, expected one of: ", self.0)?;
+
+        for (i, algorithm) in AnyAlgorithm::ALL.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(algorithm.name())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the name or alias of an algorithm, ignoring ASCII case.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::any::{AnyAlgorithm, AnyState};
+///
+/// let algorithm: AnyAlgorithm = "xxh64".parse().unwrap();
+///
+/// assert_eq!(algorithm, AnyAlgorithm::XXHash64);
+/// assert_eq!(algorithm.name(), "xxhash64");
+///
+/// let s = AnyState::new(algorithm);
+/// ```
+impl FromStr for AnyAlgorithm {
+    type Err = UnknownAlgorithm;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AnyAlgorithm::from_name(s).ok_or_else(|| UnknownAlgorithm(s.to_owned()))
+    }
+}
+
+impl Error for UnknownAlgorithm {}
+
+/// `AnyState` provides the state for `HashMap` or `HashSet` types
+/// with an algorithm selected at runtime.
+///
+/// Like `RandomState`, a particular instance `AnyState` will create the same instances of `Hasher`.
+#[derive(Clone, Copy, Debug)]
+pub struct AnyState {
+    algorithm: AnyAlgorithm,
+    seed: Seed,
+}
+
+impl AnyState {
+    /// Constructs a new `AnyState` of the algorithm with random keys.
+    #[inline(always)]
+    pub fn new(algorithm: AnyAlgorithm) -> Self {
+        AnyState::with_seed(algorithm, Seed::gen())
+    }
+
+    /// Constructs a new `AnyState` of the algorithm with the seed.
+    #[inline(always)]
+    pub fn with_seed(algorithm: AnyAlgorithm, seed: Seed) -> Self {
+        AnyState { algorithm, seed }
+    }
+
+    /// Constructs a new `AnyState` of the named algorithm with random keys.
+    pub fn from_name(name: &str) -> Result<Self, UnknownAlgorithm> {
+        name.parse().map(AnyState::new)
+    }
+
+    /// Returns the selected algorithm.
+    #[inline(always)]
+    pub fn algorithm(&self) -> AnyAlgorithm {
+        self.algorithm
+    }
+}
+
+impl BuildHasher for AnyState {
+    type Hasher = AnyHasher;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> AnyHasher {
+        self.algorithm.hasher_with_seed(self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            AnyState::from_name("no-such-hash").unwrap_err().name(),
+            "no-such-hash"
+        );
+
+        #[cfg(feature = "xx")]
+        {
+            let s = AnyState::from_name("XXHash64").unwrap();
+
+            assert_eq!(s.algorithm(), AnyAlgorithm::XXHash64);
+            assert_eq!(s.algorithm().name(), "xxhash64");
+            assert_eq!(s.build_hasher().algorithm(), AnyAlgorithm::XXHash64);
+        }
+    }
+
+    #[test]
+    fn test_parse_names() {
+        for &algorithm in AnyAlgorithm::ALL {
+            assert_eq!(algorithm.name().parse(), Ok(algorithm));
+            assert_eq!(algorithm.name().to_uppercase().parse(), Ok(algorithm));
+
+            for alias in algorithm.aliases() {
+                assert_eq!(alias.parse(), Ok(algorithm));
+            }
+        }
+
+        #[cfg(feature = "city")]
+        assert_eq!("city64".parse(), Ok(AnyAlgorithm::CityHash64));
+        #[cfg(feature = "murmur")]
+        assert_eq!("murmur3_x64_128".parse(), Ok(AnyAlgorithm::MurmurHash3_128));
+        #[cfg(feature = "xx")]
+        assert_eq!("xxhash64".parse(), Ok(AnyAlgorithm::XXHash64));
+
+        let err = "no-such-hash".parse::<AnyAlgorithm>().unwrap_err();
+
+        assert_eq!(err.name(), "no-such-hash");
+        assert!(err
+            .to_string()
+            .starts_with("unknown hash algorithm `no-such-hash`"));
+
+        for algorithm in AnyAlgorithm::ALL {
+            assert!(err.to_string().contains(algorithm.name()));
+        }
+    }
+
+    #[test]
+    fn test_hashmap_with_any_state() {
+        for name in &["cityhash64", "murmurhash3_32", "spookyhash128", "xxhash64"] {
+            let s = match AnyState::from_name(name) {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            let mut map = HashMap::with_hasher(s);
+
+            for i in 0..100 {
+                assert_eq!(map.insert(i, i * 2), None);
+            }
+
+            for i in 0..100 {
+                assert_eq!(map[&i], i * 2);
+            }
+
+            assert_eq!(map.insert(37, 0), Some(74));
+        }
+    }
+
+    #[test]
+    fn test_same_state_same_hash() {
+        let s = AnyState::with_seed(
+            AnyAlgorithm::from_name("cityhash64")
+                .or_else(|| AnyAlgorithm::from_name("xxhash64"))
+                .unwrap(),
+            Seed::gen(),
+        );
+        let mut h0 = s.build_hasher();
+        let mut h1 = s.build_hasher();
+
+        h0.write(b"hello");
+        h1.write(b"hello");
+
+        assert_eq!(h0.finish(), h1.finish());
+    }
+}
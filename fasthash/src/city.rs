@@ -120,14 +120,23 @@
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
-use std::mem;
+use alloc::vec::Vec;
+use core::hash::Hasher;
+use core::mem;
 
 use crate::ffi;
 
-use crate::hasher::FastHash;
+#[cfg(feature = "std")]
+use crate::hasher::StreamHasher;
+use crate::hasher::{FastHash, FastHasher, ResetHasher};
 
 /// `CityHash` 32-bit hash functions
 ///
+/// The upstream `CityHash32` has no seeded form, the seed is mixed in by
+/// `CityHash32WithSeed` from the `SMHasher` port of `CityHash`, which is specific
+/// to `fasthash` and not comparable with the seeded functions of other libraries.
+/// The default seed is `0`.
+///
 /// # Example
 ///
 /// ```
@@ -322,14 +331,124 @@ trivial_hasher! {
     Hasher128(Hash128) -> u128
 }
 
-/// `CityHash` hash functions using HW CRC instruction.
-#[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+/// The size of the chunks chained by `ChunkedHasher`.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A `CityHash` 64-bit hasher for huge streams, which chains the hashes of fixed-size chunks.
+///
+/// `CityHash` has no incremental API, so `Hasher64` buffers the whole input.
+/// `ChunkedHasher` buffers at most `CHUNK_SIZE` bytes instead: every full chunk is hashed
+/// with `hash64_with_seed`, seeded with the hash of the previous chunk (or the seed of the hasher
+/// for the first one), and `finish` hashes the remaining partial chunk the same way.
+///
+/// This is a custom mode, NOT `CityHash` itself. The result differs from the one-shot `hash64`
+/// of the same bytes, and equals `hash64_with_seed` only for inputs shorter than a chunk.
+/// It doesn't depend on how the input is split across the writes.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{city::{self, ChunkedHasher, CHUNK_SIZE}, FastHasher};
+///
+/// let data = vec![0x5a_u8; 3 * CHUNK_SIZE];
+/// let mut h = ChunkedHasher::new();
+///
+/// for chunk in data.chunks(1000) {
+///     h.write(chunk);
+/// }
+///
+/// let mut seed = 0;
+/// for chunk in data.chunks(CHUNK_SIZE) {
+///     seed = city::hash64_with_seed(chunk, seed);
+/// }
+///
+/// assert_eq!(h.finish(), city::hash64_with_seed(b"", seed));
+/// assert_ne!(h.finish(), city::hash64(&data));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChunkedHasher {
+    seed: u64,
+    chained: u64,
+    buf: Vec<u8>,
+}
+
+impl Default for ChunkedHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for ChunkedHasher {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        Hash64::hash_with_seed(&self.buf, self.chained)
+    }
+
+    fn write(&mut self, mut bytes: &[u8]) {
+        if !self.buf.is_empty() {
+            let n = bytes.len().min(CHUNK_SIZE - self.buf.len());
+
+            self.buf.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+
+            if self.buf.len() < CHUNK_SIZE {
+                return;
+            }
+
+            self.chained = Hash64::hash_with_seed(&self.buf, self.chained);
+            self.buf.clear();
+        }
+
+        // hash the full chunks in place, and only buffer the tail
+        while bytes.len() >= CHUNK_SIZE {
+            self.chained = Hash64::hash_with_seed(&bytes[..CHUNK_SIZE], self.chained);
+            bytes = &bytes[CHUNK_SIZE..];
+        }
+
+        self.buf.extend_from_slice(bytes);
+    }
+}
+
+impl FastHasher for ChunkedHasher {
+    type Seed = u64;
+    type Output = u64;
+
+    #[inline(always)]
+    fn with_seed(seed: u64) -> Self {
+        ChunkedHasher {
+            seed,
+            chained: seed,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl ResetHasher for ChunkedHasher {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.chained = self.seed;
+        self.buf.clear();
+    }
+}
+
+#[cfg(feature = "std")]
+impl StreamHasher for ChunkedHasher {}
+
+impl_write!(ChunkedHasher);
+
+/// `CityHash` hash functions using the CRC32C checksum.
+///
+/// With SSE4.2 they call the bundled `CityHashCrc128`, otherwise a port of it which
+/// computes the CRC with `crate::crc`, so the hash values are the same on every target.
 pub mod crc {
-    use std::mem;
+    #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+    use core::mem;
 
     use crate::FastHash;
 
-    /// `CityHash` 128-bit hash functions using HW CRC instruction.
+    /// `CityHash` 128-bit hash functions using the CRC32C checksum.
     ///
     /// # Example
     ///
@@ -357,6 +476,7 @@ pub mod crc {
         type Hash = u128;
         type Seed = u128;
 
+        #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
         #[inline(always)]
         fn hash<T: AsRef<[u8]>>(bytes: T) -> u128 {
             unsafe {
@@ -367,6 +487,13 @@ pub mod crc {
             }
         }
 
+        #[cfg(not(any(feature = "sse42", target_feature = "sse4.2")))]
+        #[inline(always)]
+        fn hash<T: AsRef<[u8]>>(bytes: T) -> u128 {
+            scalar::hash128(bytes.as_ref())
+        }
+
+        #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
         #[inline(always)]
         fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
             unsafe {
@@ -377,6 +504,12 @@ pub mod crc {
                 ))
             }
         }
+
+        #[cfg(not(any(feature = "sse42", target_feature = "sse4.2")))]
+        #[inline(always)]
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
+            scalar::hash128_with_seed(bytes.as_ref(), seed)
+        }
     }
 
     trivial_hasher! {
@@ -397,6 +530,208 @@ pub mod crc {
         /// ```
         Hasher128(Hash128) -> u128
     }
+
+    /// A port of `CityHashCrc128` from the bundled `CityHash` 1.0.3.
+    ///
+    /// `_mm_crc32_u64(crc, v)` is the raw CRC32C of the low 32 bits of `crc` over
+    /// the 8 little endian bytes of `v`, without the pre and post inversion.
+    #[cfg_attr(any(feature = "sse42", target_feature = "sse4.2"), allow(dead_code))]
+    mod scalar {
+        use core::convert::TryInto;
+
+        use crate::crc;
+        use crate::FastHash;
+
+        const K0: u64 = 0xc3a5_c85c_97cb_3127;
+        const K_MUL: u64 = 0x9ddf_ea08_eb38_2d69;
+
+        #[inline(always)]
+        fn fetch64(s: &[u8], off: usize) -> u64 {
+            u64::from_le_bytes(s[off..off + 8].try_into().unwrap())
+        }
+
+        #[inline(always)]
+        fn crc32_u64(crc: u64, v: u64) -> u64 {
+            u64::from(crc::raw_update(crc as u32, &v.to_le_bytes()))
+        }
+
+        #[inline(always)]
+        fn shift_mix(v: u64) -> u64 {
+            v ^ (v >> 47)
+        }
+
+        #[inline(always)]
+        fn hash_len16(u: u64, v: u64) -> u64 {
+            let mut a = (u ^ v).wrapping_mul(K_MUL);
+            a ^= a >> 47;
+            let mut b = (v ^ a).wrapping_mul(K_MUL);
+            b ^= b >> 47;
+            b.wrapping_mul(K_MUL)
+        }
+
+        /// `CityHashCrc256Long`, for at least 240 bytes.
+        fn crc256_long(s: &[u8], seed: u32) -> [u64; 4] {
+            let len = s.len() as u64;
+            let seed = u64::from(seed);
+            let mut r = [0; 4];
+
+            let mut a = fetch64(s, 56).wrapping_add(K0);
+            let mut b = fetch64(s, 96).wrapping_add(K0);
+            let mut c = hash_len16(b, len);
+            let mut d = fetch64(s, 120).wrapping_mul(K0).wrapping_add(len);
+            let mut e = fetch64(s, 184).wrapping_add(seed);
+            let mut f = seed;
+            let mut g = 0;
+            let mut h = 0;
+            let mut i = 0;
+            let mut j = 0;
+            let mut t = c.wrapping_add(d);
+
+            r[0] = c;
+            r[1] = d;
+
+            let mut chunk = |s: &[u8], multiplier: u64, z: u32| {
+                let old_a = a;
+
+                a = b
+                    .rotate_right(41 ^ z)
+                    .wrapping_mul(multiplier)
+                    .wrapping_add(fetch64(s, 0));
+                b = c
+                    .rotate_right(27 ^ z)
+                    .wrapping_mul(multiplier)
+                    .wrapping_add(fetch64(s, 8));
+                c = d
+                    .rotate_right(41 ^ z)
+                    .wrapping_mul(multiplier)
+                    .wrapping_add(fetch64(s, 16));
+                d = e
+                    .rotate_right(33 ^ z)
+                    .wrapping_mul(multiplier)
+                    .wrapping_add(fetch64(s, 24));
+                e = t
+                    .rotate_right(25 ^ z)
+                    .wrapping_mul(multiplier)
+                    .wrapping_add(fetch64(s, 32));
+                t = old_a;
+
+                f = crc32_u64(f, a);
+                g = crc32_u64(g, b);
+                h = crc32_u64(h, c);
+                i = crc32_u64(i, d);
+                j = crc32_u64(j, e);
+            };
+
+            // 240 bytes of input per iteration
+            let iters = s.len() / 240;
+            let mut off = 0;
+
+            for _ in 0..iters {
+                for _ in 0..3 {
+                    chunk(&s[off..], 1, 1);
+                    chunk(&s[off + 40..], K0, 0);
+                    off += 80;
+                }
+            }
+
+            while s.len() - off >= 40 {
+                chunk(&s[off..], K0, 0);
+                off += 40;
+            }
+
+            if s.len() > off {
+                chunk(&s[s.len() - 40..], K0, 0);
+            }
+
+            j = j.wrapping_add(i << 32);
+            a = hash_len16(a, j);
+            h = h.wrapping_add(g << 32);
+            b = b.wrapping_add(h);
+            c = hash_len16(c, f).wrapping_add(i);
+            d = hash_len16(d, e.wrapping_add(r[0]));
+            j = j.wrapping_add(e);
+            i = i.wrapping_add(hash_len16(h, t));
+            e = hash_len16(a, d).wrapping_add(j);
+            f = hash_len16(b, c).wrapping_add(a);
+            g = hash_len16(j, i).wrapping_add(c);
+            r[0] = e.wrapping_add(f).wrapping_add(g).wrapping_add(h);
+            a = shift_mix(a.wrapping_add(g).wrapping_mul(K0))
+                .wrapping_mul(K0)
+                .wrapping_add(b);
+            r[1] = r[1].wrapping_add(a.wrapping_add(r[0]));
+            a = shift_mix(a.wrapping_mul(K0))
+                .wrapping_mul(K0)
+                .wrapping_add(c);
+            r[2] = a.wrapping_add(r[1]);
+            a = shift_mix(a.wrapping_add(e).wrapping_mul(K0)).wrapping_mul(K0);
+            r[3] = a.wrapping_add(r[2]);
+
+            r
+        }
+
+        /// `CityHashCrc128`, the plain `CityHash128` up to 900 bytes.
+        pub fn hash128(s: &[u8]) -> u128 {
+            if s.len() <= 900 {
+                return super::super::Hash128::hash(s);
+            }
+
+            let r = crc256_long(s, 0);
+
+            u128::from(r[3]) << 64 | u128::from(r[2])
+        }
+
+        /// `CityHashCrc128WithSeed`, the plain `CityHash128WithSeed` up to 900 bytes.
+        pub fn hash128_with_seed(s: &[u8], seed: u128) -> u128 {
+            if s.len() <= 900 {
+                return super::super::Hash128::hash_with_seed(s, seed);
+            }
+
+            let r = crc256_long(s, 0);
+            let u = ((seed >> 64) as u64).wrapping_add(r[0]);
+            let v = (seed as u64).wrapping_add(r[1]);
+            let lo = hash_len16(u, v.wrapping_add(r[2]));
+            let hi = hash_len16(v.rotate_right(32), u.wrapping_mul(K0).wrapping_add(r[3]));
+
+            u128::from(hi) << 64 | u128::from(lo)
+        }
+    }
+
+    #[cfg(all(test, any(feature = "sse42", target_feature = "sse4.2")))]
+    mod tests {
+        use alloc::vec::Vec;
+
+        use super::*;
+
+        #[test]
+        fn test_scalar_matches_hardware() {
+            let data = (0..5000u32)
+                .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+                .collect::<Vec<_>>();
+
+            for &len in &[
+                0, 1, 16, 240, 900, 901, 959, 960, 1000, 1024, 1039, 1234, 4096, 5000,
+            ] {
+                let bytes = &data[..len];
+
+                assert_eq!(scalar::hash128(bytes), Hash128::hash(bytes), "len {}", len);
+
+                for &seed in &[
+                    0,
+                    123,
+                    u128::max_value(),
+                    0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+                ] {
+                    assert_eq!(
+                        scalar::hash128_with_seed(bytes, seed),
+                        Hash128::hash_with_seed(bytes, seed),
+                        "len {} seed {:#x}",
+                        len,
+                        seed
+                    );
+                }
+            }
+        }
+    }
 }
 
 /// `CityHash` 32-bit hash functions for a byte array.
@@ -407,12 +742,176 @@ pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
 
 /// `CityHash` 32-bit hash function for a byte array.
 ///
-/// For convenience, a 32-bit seed is also hashed into the result.
+/// For convenience, a 32-bit seed is also hashed into the result,
+/// see `Hash32` for the seeding.
 #[inline(always)]
 pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Hash32::hash_with_seed(v, seed)
 }
 
+/// `CityHash` 32-bit hash function for every key, hashed independently with the seed,
+/// the same as `Hash32::hash_with_seed`.
+///
+/// The keys of symbol tables are mostly short, and on the x86_64 CPUs supporting `SSE4.1`
+/// the keys of 5 to 12 bytes are hashed four at a time, their words mixed in parallel
+/// in the lanes of the SIMD registers, the other keys are hashed one by one.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{city, city::Hash32, FastHash};
+///
+/// let keys = ["hello", "world", "hello"];
+/// let hashes = city::hash32_many(&keys, 123);
+///
+/// assert_eq!(hashes[0], Hash32::hash_with_seed("hello", 123));
+/// assert_eq!(hashes[0], hashes[2]);
+/// ```
+pub fn hash32_many<I>(keys: I, seed: u32) -> Vec<u32>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    #[cfg(target_arch = "x86_64")]
+    {
+        if has_x86_feature!("sse4.1") {
+            return unsafe { hash32_many_sse41(keys, seed) };
+        }
+    }
+
+    hash32_many_scalar(keys, seed)
+}
+
+#[inline(always)]
+fn hash32_many_scalar<I>(keys: I, seed: u32) -> Vec<u32>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    keys.into_iter()
+        .map(|key| Hash32::hash_with_seed(key, seed))
+        .collect()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+unsafe fn hash32_many_sse41<I>(keys: I, seed: u32) -> Vec<u32>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let fetch32 = |key: &[u8], i: usize| {
+        let mut word = [0; 4];
+        word.copy_from_slice(&key[i..i + 4]);
+        u32::from_le_bytes(word)
+    };
+
+    let keys = keys.into_iter();
+    let mut hashes = Vec::with_capacity(keys.size_hint().0);
+    // the positions of the hashes and the words of the keys in the lanes of the batch
+    let mut pos = [0; 4];
+    let mut lanes = [[0; 4]; 4];
+    let mut n = 0;
+
+    for key in keys {
+        let key = key.as_ref();
+        let len = key.len();
+
+        if !(5..=12).contains(&len) {
+            hashes.push(Hash32::hash_with_seed(key, seed));
+            continue;
+        }
+
+        pos[n] = hashes.len();
+        lanes[0][n] = len as u32;
+        lanes[1][n] = fetch32(key, 0);
+        lanes[2][n] = fetch32(key, len - 4);
+        lanes[3][n] = fetch32(key, (len >> 1) & 4);
+        hashes.push(0);
+        n += 1;
+
+        if n == 4 {
+            let batch = hash32_len5to12_x4(&lanes, seed);
+
+            for (&i, &hash) in pos.iter().zip(&batch) {
+                hashes[i] = hash;
+            }
+            n = 0;
+        }
+    }
+
+    // the lanes after `n` still hold the keys of the previous batch, their hashes are dropped
+    if n > 0 {
+        let batch = hash32_len5to12_x4(&lanes, seed);
+
+        for (&i, &hash) in pos[..n].iter().zip(&batch) {
+            hashes[i] = hash;
+        }
+    }
+
+    hashes
+}
+
+/// `Hash32Len5to12` of `CityHash32WithSeed` for four keys, from the lengths
+/// and the first, last and middle words of the keys.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+unsafe fn hash32_len5to12_x4(lanes: &[[u32; 4]; 4], seed: u32) -> [u32; 4] {
+    use core::arch::x86_64::*;
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn load(v: &[u32; 4]) -> __m128i {
+        _mm_loadu_si128(v.as_ptr() as *const __m128i)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn mul(v: __m128i, m: u32) -> __m128i {
+        _mm_mullo_epi32(v, _mm_set1_epi32(m as i32))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn rotr<const S: i32, const L: i32>(v: __m128i) -> __m128i {
+        _mm_or_si128(_mm_srli_epi32::<S>(v), _mm_slli_epi32::<L>(v))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn mur(a: __m128i, h: __m128i) -> __m128i {
+        let a = mul(rotr::<17, 15>(mul(a, 0xcc9e_2d51)), 0x1b87_3593);
+        let h = rotr::<19, 13>(_mm_xor_si128(h, a));
+
+        _mm_add_epi32(mul(h, 5), _mm_set1_epi32(0xe654_6b64_u32 as i32))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn fmix(h: __m128i) -> __m128i {
+        let h = mul(_mm_xor_si128(h, _mm_srli_epi32::<16>(h)), 0x85eb_ca6b);
+        let h = mul(_mm_xor_si128(h, _mm_srli_epi32::<13>(h)), 0xc2b2_ae35);
+
+        _mm_xor_si128(h, _mm_srli_epi32::<16>(h))
+    }
+
+    let len = load(&lanes[0]);
+    let d = mul(len, 5);
+    let a = _mm_add_epi32(
+        _mm_add_epi32(len, _mm_set1_epi32(seed as i32)),
+        load(&lanes[1]),
+    );
+    let b = _mm_add_epi32(d, load(&lanes[2]));
+    let c = _mm_add_epi32(_mm_set1_epi32(9), load(&lanes[3]));
+
+    let h = fmix(mur(c, mur(b, mur(a, d))));
+    let mut hashes = [0; 4];
+
+    _mm_storeu_si128(hashes.as_mut_ptr() as *mut __m128i, h);
+
+    hashes
+}
+
 /// `CityHash` 64-bit hash functions for a byte array.
 #[inline(always)]
 pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
@@ -427,6 +926,27 @@ pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     Hash64::hash_with_seed(v, seed)
 }
 
+/// `CityHash` 64-bit hash functions for a C string.
+///
+/// The bytes are hashed without the NUL terminator, the same as `hash64(s.to_bytes())`.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::CStr;
+///
+/// use fasthash::city;
+///
+/// let s = CStr::from_bytes_with_nul(b"hello\0").unwrap();
+///
+/// assert_eq!(city::hash64_cstr(s), city::hash64(b"hello"));
+/// ```
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn hash64_cstr(s: &std::ffi::CStr) -> u64 {
+    Hash64::hash(s.to_bytes())
+}
+
 /// `CityHash` 64-bit hash function for a byte array.
 ///
 /// For convenience, two seeds are also hashed into the result.
@@ -435,37 +955,151 @@ pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
     Hash64::hash_with_seeds(v, seed0, seed1)
 }
 
-cfg_if! {
-    if #[cfg(any(feature = "sse42", target_feature = "sse4.2"))] {
-        /// `CityHash` 128-bit hash function for a byte array using HW CRC instruction.
-        ///
-        /// That require SSE4.2 instructions to be available.
-        #[inline(always)]
-        pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
-            crc::Hash128::hash(v)
+/// `CityHash` 128-bit hash function for a byte array using the CRC32C checksum.
+///
+/// It's `city::crc::Hash128`, with the HW CRC instruction if SSE4.2 is available.
+#[inline(always)]
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    crc::Hash128::hash(v)
+}
+
+/// `CityHash` 128-bit hash function for a byte array using the CRC32C checksum.
+///
+/// For convenience, a 128-bit seed is also hashed into the result.
+/// It's `city::crc::Hash128`, with the HW CRC instruction if SSE4.2 is available.
+#[inline(always)]
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
+    crc::Hash128::hash_with_seed(v, seed)
+}
+
+/// `CityHash` 128-bit hash function for a byte array, split into the `(low, high)` halves.
+///
+/// It's the same as splitting `hash128_with_seed`, e.g. to shard on one half.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::city;
+///
+/// let h = city::hash128_with_seed(b"hello", 123);
+///
+/// assert_eq!(city::hash128_halves(b"hello", 123), (h as u64, (h >> 64) as u64));
+/// ```
+#[inline(always)]
+pub fn hash128_halves<T: AsRef<[u8]>>(v: T, seed: u128) -> (u64, u64) {
+    let h = hash128_with_seed(v, seed);
+
+    (h as u64, (h >> 64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HasherWriteExt;
+
+    #[test]
+    fn test_hash32_many() {
+        let data = (0..64u32)
+            .map(|i| (i * 37 + (i >> 3)) as u8)
+            .collect::<Vec<_>>();
+        // all the lengths of the short keys, interleaved with the longer ones
+        let keys = (0..200)
+            .map(|i| &data[i % 7..i % 7 + [4, 5, 8, 12, 9, 13, 0, 16, 6, 33, 11][i % 11]])
+            .collect::<Vec<_>>();
+
+        for &seed in &[0, 123, 0xdead_beef] {
+            for n in 0..keys.len() {
+                let hashes = hash32_many(&keys[..n], seed);
+                let expected = keys[..n]
+                    .iter()
+                    .map(|key| Hash32::hash_with_seed(key, seed))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(hashes, expected, "seed {}, {} keys", seed, n);
+                assert_eq!(hash32_many_scalar(&keys[..n], seed), expected);
+            }
         }
 
-        /// `CityHash` 128-bit hash function for a byte array using HW CRC instruction.
-        ///
-        /// For convenience, a 128-bit seed is also hashed into the result.
-        /// That require SSE4.2 instructions to be available.
-        #[inline(always)]
-        pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
-            crc::Hash128::hash_with_seed(v, seed)
+        assert_eq!(hash32_many(&["hello"], 123), [3366460263]);
+        assert!(hash32_many(Vec::<Vec<u8>>::new(), 123).is_empty());
+    }
+
+    #[test]
+    fn test_write_str_raw() {
+        let mut h = Hasher64::new();
+
+        h.write_str_raw("abc");
+        assert_eq!(h.finish(), hash64(b"abc"));
+
+        h.write_str_raw("déf");
+        assert_eq!(h.finish(), hash64("abcdéf".as_bytes()));
+    }
+
+    #[test]
+    fn test_hash32_seed() {
+        assert_eq!(hash32(b"hello"), hash32_with_seed(b"hello", 0));
+
+        let hashes = (0..16)
+            .map(|seed| hash32_with_seed(b"hello", seed))
+            .collect::<Vec<_>>();
+
+        for (i, h) in hashes.iter().enumerate() {
+            assert!(!hashes[i + 1..].contains(h));
         }
-    } else {
-        /// `CityHash` 128-bit hash function for a byte array.
-        #[inline(always)]
-        pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
-            Hash128::hash(v)
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash64_cstr() {
+        use std::ffi::{CStr, CString};
+
+        let s = CStr::from_bytes_with_nul(b"abc\0").unwrap();
+
+        assert_eq!(hash64_cstr(s), hash64(b"abc"));
+        assert_ne!(hash64_cstr(s), hash64(b"abc\0"));
+        assert_eq!(hash64_cstr(&CString::new("").unwrap()), hash64(b""));
+    }
+
+    #[test]
+    fn test_hash128_halves() {
+        for &seed in &[0, 123, u128::MAX] {
+            let (low, high) = hash128_halves(b"hello world", seed);
+            let h = hash128_with_seed(b"hello world", seed);
+
+            assert_eq!(u128::from(high) << 64 | u128::from(low), h);
         }
+    }
 
-        /// `CityHash` 128-bit hash function for a byte array.
-        ///
-        /// For convenience, a 128-bit seed is also hashed into the result.
-        #[inline(always)]
-        pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
-            Hash128::hash_with_seed(v, seed)
+    #[test]
+    fn test_chunked_hasher() {
+        let data = (0..3 * CHUNK_SIZE + 123).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        let mut h = ChunkedHasher::with_seed(123);
+        h.write(&data);
+        let hash = h.finish();
+
+        for &step in &[1, 7, 4096, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1] {
+            let mut h = ChunkedHasher::with_seed(123);
+
+            for chunk in data.chunks(step) {
+                h.write(chunk);
+            }
+
+            assert_eq!(h.finish(), hash, "step {}", step);
+
+            h.reset();
+            h.write(&data);
+            assert_eq!(h.finish(), hash, "step {}", step);
         }
+
+        assert_ne!(hash, hash64_with_seed(&data, 123));
+
+        let mut h = ChunkedHasher::new();
+        h.write(&data);
+        assert_ne!(h.finish(), hash);
+
+        let mut h = ChunkedHasher::with_seed(123);
+        h.write(b"hello");
+        assert_eq!(h.finish(), hash64_with_seed(b"hello", 123));
     }
 }
@@ -0,0 +1,376 @@
+//! `CLHash`: a carry-less multiplication based hash
+//!
+//! by Daniel Lemire, Owen Kaser
+//!
+//! https://github.com/lemire/clhash
+//!
+//! `CLHash` is a fast, almost universal 64-bit string hash function built on the
+//! carry-less multiplication (`PCLMULQDQ`) instruction, with a provable bound on the
+//! collision probability of two distinct inputs, as long as the random `Key` stays secret.
+//!
+//! # Key generation
+//!
+//! The hash function is keyed by 133 random 64-bit words, the `Key`. Converting a
+//! `fasthash::Seed` into a `Key` draws all the words from the seed generator, and
+//! `Key::from_seeds` reproduces the `get_random_key_for_clhash` routine of the
+//! reference implementation. The default key is the one of the reference tests.
+//!
+//! The key is large, so `RandomState` generates it once and shares it with every
+//! hasher it builds.
+//!
+//! # Verification
+//!
+//! `Key::from_seeds` is tested against `get_random_key_for_clhash` of the bundled
+//! reference implementation, with the seeds of its unit test, and the hash value is the
+//! reference `clhash` of the key. The reference unit test only checks that equal inputs
+//! collide and different ones don't, it pins no hash value.
+//!
+//! # CPU support
+//!
+//! `CLHash` requires the `PCLMULQDQ` and `SSE4.2` instructions, the hash functions
+//! panic if `is_supported()` returns `false`.
+//!
+//! # Example
+//!
+//! ```
+//! use std::hash::{Hash, Hasher};
+//!
+//! use fasthash::clhash;
+//!
+//! if clhash::is_supported() {
+//!     fn hash<T: Hash>(t: &T) -> u64 {
+//!         let mut s: clhash::Hasher64 = Default::default();
+//!         t.hash(&mut s);
+//!         s.finish()
+//!     }
+//!
+//!     let h = clhash::hash64(b"hello world\xff");
+//!
+//!     assert_eq!(h, hash(&"hello world"));
+//! }
+//! ```
+//!
+use core::ffi::{c_char, c_void};
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+
+use crate::ffi;
+use crate::hasher::{self, FastHash, FastHasher};
+
+const KEY_WORDS: usize = ffi::RANDOM_64BITWORDS_NEEDED_FOR_CLHASH as usize;
+
+lazy_static::lazy_static! {
+    static ref DEFAULT_KEY: Key = Key::from_seeds(0x23a2_3cf5_033c_3c81, 0xb381_6f6a_2c68_e530);
+}
+
+/// Returns `true` if the CPU supports the instructions required by `CLHash`.
+#[inline(always)]
+pub fn is_supported() -> bool {
+    has_x86_feature!("pclmulqdq", "sse4.2")
+}
+
+/// The random key of `CLHash`, 133 words aligned on 16-byte boundaries.
+#[repr(C, align(16))]
+#[derive(Clone, Copy)]
+pub struct Key([u64; KEY_WORDS]);
+
+impl Key {
+    /// Generates the key from two 64-bit seeds,
+    /// like `get_random_key_for_clhash` of the reference implementation.
+    pub fn from_seeds(seed1: u64, seed2: u64) -> Key {
+        let mut rng = XorShift128Plus(seed1, seed2);
+        rng.jump();
+
+        let mut words = [0; KEY_WORDS];
+        for word in words.iter_mut() {
+            *word = rng.next();
+        }
+        while words[128] == 0 && words[129] == 1 {
+            words[128] = rng.next();
+            words[129] = rng.next();
+        }
+
+        Key(words)
+    }
+
+    /// Constructs a key from 133 random words.
+    ///
+    /// Returns `None` if the words don't satisfy the `CLHash` constraints.
+    pub fn from_words(words: [u64; KEY_WORDS]) -> Option<Key> {
+        if words[128] == 0 && words[129] == 1 {
+            None
+        } else {
+            Some(Key(words))
+        }
+    }
+
+    /// Computes the 64-bit `CLHash` value of a byte array.
+    #[inline(always)]
+    pub fn hash<T: AsRef<[u8]>>(&self, bytes: T) -> u64 {
+        assert!(is_supported(), "CLHash requires PCLMULQDQ and SSE4.2");
+
+        unsafe {
+            ffi::clhash(
+                self.0.as_ptr() as *const c_void,
+                bytes.as_ref().as_ptr() as *const c_char,
+                bytes.as_ref().len(),
+            )
+        }
+    }
+}
+
+impl Default for Key {
+    #[inline(always)]
+    fn default() -> Self {
+        *DEFAULT_KEY
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Key { .. }")
+    }
+}
+
+impl From<(u64, u64)> for Key {
+    #[inline(always)]
+    fn from(seeds: (u64, u64)) -> Key {
+        Key::from_seeds(seeds.0, seeds.1)
+    }
+}
+
+impl From<hasher::Seed> for Key {
+    #[inline(always)]
+    fn from(mut seed: hasher::Seed) -> Key {
+        let mut words = [0; KEY_WORDS];
+
+        loop {
+            for word in words.iter_mut() {
+                *word = seed.draw();
+            }
+
+            if let Some(key) = Key::from_words(words) {
+                return key;
+            }
+        }
+    }
+}
+
+/// The xorshift128+ generator used by the reference key generation.
+struct XorShift128Plus(u64, u64);
+
+impl XorShift128Plus {
+    fn next(&mut self) -> u64 {
+        let mut s1 = self.0;
+        let s0 = self.1;
+
+        self.0 = s0;
+        s1 ^= s1 << 23;
+        self.1 = s1 ^ s0 ^ (s1 >> 18) ^ (s0 >> 5);
+        self.1.wrapping_add(s0)
+    }
+
+    fn jump(&mut self) {
+        const JUMP: [u64; 2] = [0x8a5c_d789_635d_2dff, 0x121f_d215_5c47_2f96];
+
+        let (mut s0, mut s1) = (0, 0);
+
+        for jump in &JUMP {
+            for b in 0..64 {
+                if jump & (1 << b) != 0 {
+                    s0 ^= self.0;
+                    s1 ^= self.1;
+                }
+                self.next();
+            }
+        }
+
+        self.0 = s0;
+        self.1 = s1;
+    }
+}
+
+/// `CLHash` 64-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{clhash::{Hash64, Key}, FastHash};
+///
+/// if fasthash::clhash::is_supported() {
+///     let h = Hash64::hash(b"hello");
+///
+///     assert_eq!(h, Hash64::hash_with_seed(b"hello", Key::default()));
+///     assert_ne!(h, Hash64::hash_with_seed(b"hello", Key::from_seeds(1, 2)));
+///     assert_ne!(h, Hash64::hash(b"helloworld"));
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Hash64;
+
+impl FastHash for Hash64 {
+    type Hash = u64;
+    type Seed = Key;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: Key) -> u64 {
+        seed.hash(bytes)
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{clhash::{self, Hasher64}, FastHasher};
+    ///
+    /// if clhash::is_supported() {
+    ///     let mut h = Hasher64::new();
+    ///
+    ///     h.write(b"hello");
+    ///     assert_eq!(h.finish(), clhash::hash64(b"hello"));
+    ///
+    ///     h.write(b"world");
+    ///     assert_eq!(h.finish(), clhash::hash64(b"helloworld"));
+    /// }
+    /// ```
+    Hasher64(Hash64) -> u64
+}
+
+/// `RandomState` for `CLHash`, which generates the key once.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fasthash::clhash;
+///
+/// if clhash::is_supported() {
+///     let mut map = HashMap::with_hasher(clhash::RandomState::new());
+///
+///     assert_eq!(map.insert(37, "a"), None);
+///     assert_eq!(map.insert(37, "b"), Some("a"));
+///     assert_eq!(map[&37], "b");
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct RandomState(Key);
+
+#[cfg(feature = "std")]
+impl RandomState {
+    /// Constructs a new `RandomState` with a random key.
+    #[inline(always)]
+    pub fn new() -> Self {
+        RandomState(hasher::Seed::gen().into())
+    }
+
+    /// Constructs a new `RandomState` with the given key.
+    #[inline(always)]
+    pub fn with_key(key: Key) -> Self {
+        RandomState(key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for RandomState {
+    #[inline(always)]
+    fn default() -> Self {
+        RandomState::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl BuildHasher for RandomState {
+    type Hasher = Hasher64;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Hasher64 {
+        Hasher64::with_seed(self.0)
+    }
+}
+
+/// `CLHash` 64-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    Hash64::hash(v)
+}
+
+/// `CLHash` 64-bit hash function for a byte array with the key.
+#[inline(always)]
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, key: &Key) -> u64 {
+    key.hash(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice;
+
+    use super::*;
+
+    #[test]
+    fn test_key_generation() {
+        let k0 = Key::from_seeds(1, 2);
+        let k1 = Key::from_seeds(1, 2);
+
+        assert_eq!(&k0.0[..], &k1.0[..]);
+        assert_ne!(&k0.0[..], &Key::from_seeds(1, 3).0[..]);
+        assert_eq!(k0.0.as_ptr() as usize % 16, 0);
+
+        assert!(Key::from_words({
+            let mut words = [0; KEY_WORDS];
+            words[129] = 1;
+            words
+        })
+        .is_none());
+    }
+
+    #[test]
+    fn test_reference_key() {
+        extern "C" {
+            fn get_random_key_for_clhash(seed1: u64, seed2: u64) -> *mut c_void;
+
+            fn free(ptr: *mut c_void);
+        }
+
+        // the key of `tests/unit.c` of the reference implementation
+        let key = Key::from_seeds(0x23a2_3cf5_033c_3c81, 0xb381_6f6a_2c68_e530);
+        let mut words = [0; KEY_WORDS];
+
+        unsafe {
+            let random = get_random_key_for_clhash(0x23a2_3cf5_033c_3c81, 0xb381_6f6a_2c68_e530);
+
+            words.copy_from_slice(slice::from_raw_parts(random as *const u64, KEY_WORDS));
+            free(random);
+        }
+
+        assert_eq!(&key.0[..], &words[..]);
+        assert_eq!(&Key::default().0[..], &words[..]);
+
+        if is_supported() {
+            let h1 = key.hash("my dog");
+            let h2 = key.hash("my cat");
+            let h3 = key.hash("my dog");
+
+            assert_eq!(h1, h3);
+            assert_ne!(h1, h2);
+            assert_eq!(hash64("my dog"), h1);
+        }
+    }
+
+    #[test]
+    fn test_random_key() {
+        let k0: Key = hasher::Seed::gen().into();
+        let k1: Key = hasher::Seed::gen().into();
+
+        assert_ne!(&k0.0[..], &k1.0[..]);
+
+        if is_supported() {
+            assert_ne!(k0.hash(b"hello"), k1.hash(b"hello"));
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! `HashMap` and `HashSet` aliases with the default state of the crate
+//!
+//! The default state is `RandomState<xxh3::Hash64>`, a fast hash function with random keys.
+//! With the `secure` feature, the default state is `umash::RandomState` instead,
+//! a keyed hash function with a provable collision bound, and every `Seed::gen`
+//! is salted with a random salt of the process, to resist `HashDoS` attacks.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::collections::{HashMap, HashSet};
+//!
+//! let mut map = HashMap::default();
+//!
+//! assert_eq!(map.insert(37, "a"), None);
+//! assert_eq!(map.insert(37, "b"), Some("a"));
+//!
+//! let set = map.keys().copied().collect::<HashSet<_>>();
+//!
+//! assert!(set.contains(&37));
+//! ```
+//!
+//! There are also `HashSet` aliases for the popular hash functions with random seeds,
+//! e.g. `XXHashSet` or `CityHashSet`.
+//!
+//! ```
+//! use fasthash::collections::XXHashSet;
+//!
+//! let mut set = XXHashSet::default();
+//!
+//! assert!(set.insert("hello"));
+//! assert!(!set.insert("hello"));
+//! assert!(set.contains("hello"));
+//! assert!(!set.contains("world"));
+//! ```
+
+#[allow(unused_imports)]
+use std::collections::HashSet as StdHashSet;
+
+#[allow(unused_imports)]
+use crate::hasher::RandomState;
+
+cfg_if! {
+    if #[cfg(feature = "secure")] {
+        /// The default state of the map aliases.
+        pub type DefaultState = crate::umash::RandomState;
+    } else if #[cfg(feature = "xx")] {
+        /// The default state of the map aliases.
+        pub type DefaultState = RandomState<crate::xxh3::Hash64>;
+    }
+}
+
+/// A `HashMap` using the default state of the crate.
+#[cfg(any(feature = "secure", feature = "xx"))]
+pub type HashMap<K, V> = std::collections::HashMap<K, V, DefaultState>;
+
+/// A `HashSet` using the default state of the crate.
+#[cfg(any(feature = "secure", feature = "xx"))]
+pub type HashSet<K> = StdHashSet<K, DefaultState>;
+
+/// A `HashSet` using `CityHash64` with a random seed.
+#[cfg(feature = "city")]
+pub type CityHashSet<T> = StdHashSet<T, RandomState<crate::city::Hash64>>;
+
+/// A `HashSet` using `FarmHash64` with a random seed.
+#[cfg(feature = "farm")]
+pub type FarmHashSet<T> = StdHashSet<T, RandomState<crate::farm::Hash64>>;
+
+/// A `HashSet` using `MetroHash64` with a random seed.
+#[cfg(feature = "metro")]
+pub type MetroHashSet<T> = StdHashSet<T, RandomState<crate::metro::Hash64_2>>;
+
+/// A `HashSet` using `wyhash` with a random seed.
+#[cfg(feature = "wy")]
+pub type WYHashSet<T> = StdHashSet<T, RandomState<crate::wy::Hash64>>;
+
+/// A `HashSet` using `xxHash64` with a random seed.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::collections::XXHashSet;
+///
+/// let set = [1, 2, 3, 2, 1].iter().copied().collect::<XXHashSet<_>>();
+///
+/// assert_eq!(set.len(), 3);
+/// assert!(set.contains(&2));
+/// ```
+#[cfg(feature = "xx")]
+pub type XXHashSet<T> = StdHashSet<T, RandomState<crate::xx::Hash64>>;
+
+/// A `HashSet` using `XXH3` 64-bit with a random seed.
+#[cfg(feature = "xx")]
+pub type XXH3HashSet<T> = StdHashSet<T, RandomState<crate::xxh3::Hash64>>;
@@ -0,0 +1,1083 @@
+//! Hashers combining or wrapping other hashers
+use core::fmt;
+use core::hash::Hasher;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::crc;
+#[cfg(feature = "std")]
+use crate::hasher::StreamHasher;
+use crate::hasher::{FastHasher, Seed};
+
+/// The seed of the hashers combined by `DualStream`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DualSeed<A, B>(pub A, pub B);
+
+impl<A: From<Seed>, B: From<Seed>> From<Seed> for DualSeed<A, B> {
+    #[inline(always)]
+    fn from(mut seed: Seed) -> Self {
+        let a = A::from(seed);
+
+        // skip the words consumed by the first seed
+        let _: [u64; 4] = seed.draw();
+
+        DualSeed(a, B::from(seed))
+    }
+}
+
+/// A hasher which feeds the same stream to two hashers at once.
+///
+/// It computes two different hashes of a stream without reading the input twice,
+/// e.g. a fast index hash and a stable fingerprint.
+///
+/// `finish` returns the hash of the first hasher.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use fasthash::{city, xx, DualStream, FastHasher, StreamHasher};
+///
+/// let mut h = DualStream::<xx::Hasher64, city::Hasher64>::new();
+///
+/// h.write_stream(&mut Cursor::new(&b"hello world"[..])).unwrap();
+///
+/// assert_eq!(h.finish_a(), xx::hash64(b"hello world"));
+/// assert_eq!(h.finish_b(), city::hash64(b"hello world"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DualStream<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Hasher, B: Hasher> DualStream<A, B> {
+    /// Constructs a `DualStream` from two hashers.
+    #[inline(always)]
+    pub fn from_hashers(a: A, b: B) -> Self {
+        DualStream { a, b }
+    }
+
+    /// Returns the hash of the first hasher.
+    #[inline(always)]
+    pub fn finish_a(&self) -> u64 {
+        self.a.finish()
+    }
+
+    /// Returns the hash of the second hasher.
+    #[inline(always)]
+    pub fn finish_b(&self) -> u64 {
+        self.b.finish()
+    }
+
+    /// Returns the first hasher.
+    #[inline(always)]
+    pub fn a(&self) -> &A {
+        &self.a
+    }
+
+    /// Returns the second hasher.
+    #[inline(always)]
+    pub fn b(&self) -> &B {
+        &self.b
+    }
+
+    /// Unwraps the two hashers.
+    #[inline(always)]
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: Hasher, B: Hasher> Hasher for DualStream<A, B> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.a.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.a.write(bytes);
+        self.b.write(bytes);
+    }
+}
+
+impl<A: FastHasher, B: FastHasher> FastHasher for DualStream<A, B> {
+    type Seed = DualSeed<A::Seed, B::Seed>;
+    type Output = (A::Output, B::Output);
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        DualStream {
+            a: A::with_seed(seed.0),
+            b: B::with_seed(seed.1),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: FastHasher, B: FastHasher> StreamHasher for DualStream<A, B> {}
+
+/// A hasher which computes a 32-bit and a 64-bit hash of the same input in one pass.
+///
+/// It suits users who need a small bucket index and a wider fingerprint at once,
+/// with hash functions which don't produce both widths from one accumulation.
+///
+/// `finish` returns the 64-bit hash.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{city, murmur3, FastHasher, MultiWidth};
+///
+/// let mut h = MultiWidth::<murmur3::Hasher32, city::Hasher64>::new();
+///
+/// h.write(b"hello world");
+///
+/// assert_eq!(h.finish32(), murmur3::hash32(b"hello world"));
+/// assert_eq!(h.finish64(), city::hash64(b"hello world"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MultiWidth<H32, H64> {
+    h32: H32,
+    h64: H64,
+}
+
+impl<H32, H64> MultiWidth<H32, H64>
+where
+    H32: FastHasher<Output = u32>,
+    H64: FastHasher<Output = u64>,
+{
+    /// Constructs a `MultiWidth` from a 32-bit and a 64-bit hasher.
+    #[inline(always)]
+    pub fn from_hashers(h32: H32, h64: H64) -> Self {
+        MultiWidth { h32, h64 }
+    }
+
+    /// Returns the 32-bit hash.
+    #[inline(always)]
+    pub fn finish32(&self) -> u32 {
+        self.h32.finish() as u32
+    }
+
+    /// Returns the 64-bit hash.
+    #[inline(always)]
+    pub fn finish64(&self) -> u64 {
+        self.h64.finish()
+    }
+
+    /// Unwraps the two hashers.
+    #[inline(always)]
+    pub fn into_inner(self) -> (H32, H64) {
+        (self.h32, self.h64)
+    }
+}
+
+impl<H32: Hasher, H64: Hasher> Hasher for MultiWidth<H32, H64> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.h64.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.h32.write(bytes);
+        self.h64.write(bytes);
+    }
+}
+
+impl<H32, H64> FastHasher for MultiWidth<H32, H64>
+where
+    H32: FastHasher<Output = u32>,
+    H64: FastHasher<Output = u64>,
+{
+    type Seed = DualSeed<H32::Seed, H64::Seed>;
+    type Output = u64;
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        MultiWidth::from_hashers(H32::with_seed(seed.0), H64::with_seed(seed.1))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H32, H64> StreamHasher for MultiWidth<H32, H64>
+where
+    H32: FastHasher<Output = u32>,
+    H64: FastHasher<Output = u64>,
+{
+}
+
+/// A hasher which computes a `CRC32C` checksum alongside the hash in a single pass.
+///
+/// It suits storage systems which need both a distribution hash and an error-detection
+/// checksum of large data.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{crc, xx, ChecksumHasher, FastHasher};
+///
+/// let mut h = ChecksumHasher::<xx::Hasher64>::new();
+///
+/// h.write(b"hello");
+/// h.write(b"world");
+///
+/// assert_eq!(h.finish(), xx::hash64(b"helloworld"));
+/// assert_eq!(h.checksum(), crc::crc32c(b"helloworld"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ChecksumHasher<H> {
+    hasher: H,
+    crc: u32,
+}
+
+impl<H: Hasher> ChecksumHasher<H> {
+    /// Constructs a `ChecksumHasher` from a hasher.
+    #[inline(always)]
+    pub fn from_hasher(hasher: H) -> Self {
+        ChecksumHasher { hasher, crc: 0 }
+    }
+
+    /// Returns the `CRC32C` checksum of the written bytes.
+    #[inline(always)]
+    pub fn checksum(&self) -> u32 {
+        self.crc
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher and the checksum.
+    #[inline(always)]
+    pub fn into_inner(self) -> (H, u32) {
+        (self.hasher, self.crc)
+    }
+}
+
+impl<H: Hasher> Hasher for ChecksumHasher<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+        self.crc = crc::crc32c_update(self.crc, bytes);
+    }
+}
+
+impl<H: FastHasher> FastHasher for ChecksumHasher<H> {
+    type Seed = H::Seed;
+    type Output = H::Output;
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        ChecksumHasher::from_hasher(H::with_seed(seed))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: FastHasher> StreamHasher for ChecksumHasher<H> {}
+
+/// A hasher which counts the bytes written to the inner hasher.
+///
+/// It suits framing protocols which send the length of a payload alongside its hash,
+/// without a separate counter of the written or streamed bytes.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+/// use std::io::{self, Cursor};
+///
+/// use fasthash::{xx, CountingHasher, FastHasher, StreamHasher};
+///
+/// let mut h = CountingHasher::<xx::Hasher64>::new();
+///
+/// h.write(b"hello ");
+/// h.write_stream(&mut Cursor::new(&b"world"[..])).unwrap();
+/// io::copy(&mut Cursor::new(&b"!"[..]), &mut h).unwrap();
+///
+/// assert_eq!(h.len(), 12);
+/// assert_eq!(h.finish(), xx::hash64(b"hello world!"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CountingHasher<H> {
+    hasher: H,
+    len: u64,
+}
+
+impl<H: Hasher> CountingHasher<H> {
+    /// Constructs a `CountingHasher` from a hasher.
+    #[inline(always)]
+    pub fn from_hasher(hasher: H) -> Self {
+        CountingHasher { hasher, len: 0 }
+    }
+
+    /// Returns the number of the written bytes.
+    #[inline(always)]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were written.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher and the number of the written bytes.
+    #[inline(always)]
+    pub fn into_inner(self) -> (H, u64) {
+        (self.hasher, self.len)
+    }
+}
+
+impl<H: Hasher> Hasher for CountingHasher<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+        self.len += bytes.len() as u64;
+    }
+}
+
+impl<H: FastHasher> FastHasher for CountingHasher<H> {
+    type Seed = H::Seed;
+    type Output = H::Output;
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        CountingHasher::from_hasher(H::with_seed(seed))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: FastHasher> StreamHasher for CountingHasher<H> {}
+
+#[cfg(feature = "std")]
+impl<H: Hasher> io::Write for CountingHasher<H> {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        Hasher::write(self, bytes);
+
+        Ok(bytes.len())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A hasher which xors a fixed pepper into the hash of the inner hasher.
+///
+/// It diversifies the output of one hasher without rehashing the input,
+/// e.g. to derive the hash functions of several hash tables from one hash.
+/// Unlike a seed, the pepper doesn't change how the input is mixed,
+/// so the hashes of all the peppers collide on the same inputs.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{xx, FastHasher, PepperedHasher};
+///
+/// let mut h = PepperedHasher::new(xx::Hasher64::new(), 0x5555);
+///
+/// h.write(b"hello");
+///
+/// assert_eq!(h.finish(), xx::hash64(b"hello") ^ 0x5555);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PepperedHasher<H> {
+    hasher: H,
+    pepper: u64,
+}
+
+impl<H: Hasher> PepperedHasher<H> {
+    /// Constructs a `PepperedHasher` xoring `pepper` into the hash of `hasher`.
+    #[inline(always)]
+    pub fn new(hasher: H, pepper: u64) -> Self {
+        PepperedHasher { hasher, pepper }
+    }
+
+    /// Returns the pepper.
+    #[inline(always)]
+    pub fn pepper(&self) -> u64 {
+        self.pepper
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher and the pepper.
+    #[inline(always)]
+    pub fn into_inner(self) -> (H, u64) {
+        (self.hasher, self.pepper)
+    }
+}
+
+impl<H: Hasher> Hasher for PepperedHasher<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hasher.finish() ^ self.pepper
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+}
+
+/// The 64-bit finalizer of `MurmurHash3`, every input bit affects every output bit.
+#[inline(always)]
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^ (k >> 33)
+}
+
+/// A hasher which whitens the hash of the inner hasher with the `MurmurHash3` finalizer.
+///
+/// The hash functions with weaker low bits, which hash tables indexing with a mask
+/// are sensitive to, get a full avalanche of the output at the cost of a few multiplications.
+/// The mix is a bijection, so it doesn't add collisions, but it changes the hash values.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{xx, FastHasher, FinalMix};
+///
+/// let mut h = FinalMix::<xx::Hasher64>::new();
+///
+/// h.write(b"hello");
+///
+/// assert_ne!(h.finish(), xx::hash64(b"hello"));
+/// assert_eq!(h.finish(), FinalMix::from_hasher(h.clone().into_inner()).finish());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FinalMix<H> {
+    hasher: H,
+}
+
+impl<H: Hasher> FinalMix<H> {
+    /// Constructs a `FinalMix` whitening the hash of `hasher`.
+    #[inline(always)]
+    pub fn from_hasher(hasher: H) -> Self {
+        FinalMix { hasher }
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher.
+    #[inline(always)]
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+}
+
+impl<H: Hasher> Hasher for FinalMix<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        fmix64(self.hasher.finish())
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+}
+
+impl<H: FastHasher> FastHasher for FinalMix<H> {
+    type Seed = H::Seed;
+    type Output = u64;
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        FinalMix::from_hasher(H::with_seed(seed))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: FastHasher> StreamHasher for FinalMix<H> {}
+
+/// A hasher which hashes the formatted output written with `write!`.
+///
+/// It implements `fmt::Write`, so the `Display` or `Debug` output of a value
+/// is hashed without formatting it into a `String` first. The hash is the hash of
+/// the formatted UTF-8 bytes, as if they were written at once.
+///
+/// # Example
+///
+/// ```
+/// use std::fmt::Write;
+/// use std::hash::Hasher;
+///
+/// use fasthash::{city, FastHasher, FmtHasher};
+///
+/// let mut h = FmtHasher::<city::Hasher64>::new();
+///
+/// write!(h, "{}-{:?}", 42, "a").unwrap();
+/// assert_eq!(h.finish(), city::hash64(b"42-\"a\""));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FmtHasher<H> {
+    hasher: H,
+}
+
+impl<H: Hasher> FmtHasher<H> {
+    /// Constructs a `FmtHasher` from a hasher.
+    #[inline(always)]
+    pub fn from_hasher(hasher: H) -> Self {
+        FmtHasher { hasher }
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher.
+    #[inline(always)]
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+}
+
+impl<H: Hasher> fmt::Write for FmtHasher<H> {
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.hasher.write(s.as_bytes());
+
+        Ok(())
+    }
+}
+
+impl<H: Hasher> Hasher for FmtHasher<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes)
+    }
+}
+
+impl<H: FastHasher> FastHasher for FmtHasher<H> {
+    type Seed = H::Seed;
+    type Output = H::Output;
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        FmtHasher::from_hasher(H::with_seed(seed))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: FastHasher> StreamHasher for FmtHasher<H> {}
+
+/// A hasher which hashes streams up to a limit of total bytes.
+///
+/// It protects servers hashing untrusted input with `write_stream`,
+/// where a malicious peer could otherwise send an unbounded stream.
+/// Once the written streams exceed `max_bytes` in total, `write_stream` fails
+/// with an `InvalidData` error without hashing the bytes over the limit.
+///
+/// # Example
+///
+/// ```
+/// use std::io::{Cursor, ErrorKind};
+///
+/// use fasthash::{xx, BoundedStreamHasher, FastHasher};
+///
+/// let mut h = BoundedStreamHasher::new(xx::Hasher64::new(), 11);
+///
+/// h.write_stream(&mut Cursor::new(&b"hello world"[..])).unwrap();
+/// assert_eq!(h.finish(), xx::hash64(b"hello world"));
+///
+/// let err = h.write_stream(&mut Cursor::new(&b"!"[..])).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::InvalidData);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct BoundedStreamHasher<H> {
+    hasher: H,
+    len: usize,
+    max_bytes: usize,
+}
+
+#[cfg(feature = "std")]
+impl<H: Hasher> BoundedStreamHasher<H> {
+    /// Constructs a `BoundedStreamHasher` hashing at most `max_bytes` into the hasher.
+    #[inline(always)]
+    pub fn new(hasher: H, max_bytes: usize) -> Self {
+        BoundedStreamHasher {
+            hasher,
+            len: 0,
+            max_bytes,
+        }
+    }
+
+    /// Writes the stream into the hasher.
+    ///
+    /// Returns the number of bytes read from the stream,
+    /// or an `InvalidData` error once the total exceeds the limit.
+    /// The bytes read before the error are hashed as long as they fit into the limit.
+    pub fn write_stream<R: io::Read>(&mut self, r: &mut R) -> io::Result<usize> {
+        let mut buf = [0_u8; 4096];
+        let mut len = 0;
+
+        loop {
+            let n = match r.read(&mut buf) {
+                Ok(0) => return Ok(len),
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            if n > self.max_bytes - self.len {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("stream exceeds the limit of {} bytes", self.max_bytes),
+                ));
+            }
+
+            self.hasher.write(&buf[..n]);
+            self.len += n;
+            len += n;
+        }
+    }
+
+    /// Returns the hash of the written bytes.
+    #[inline(always)]
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    /// Returns the total number of the hashed bytes.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no byte was hashed yet.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the limit of the hashed bytes.
+    #[inline(always)]
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher.
+    #[inline(always)]
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+}
+
+/// A hasher which measures the time spent in the inner hasher, to report its throughput.
+///
+/// Only the writes into the inner hasher are timed, not the reads of `write_stream`,
+/// so `throughput_bps` tells whether the hash function keeps up with the I/O of the stream.
+/// Every write reads the clock twice, so time large writes, e.g. streams, not small keys.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+/// use std::io::Cursor;
+///
+/// use fasthash::{xx, FastHasher, StreamHasher, TimedStream};
+///
+/// let data = vec![0x5a_u8; 1 << 20];
+/// let mut h = TimedStream::<xx::Hasher64>::new();
+///
+/// h.write_stream(&mut Cursor::new(&data)).unwrap();
+///
+/// assert_eq!(h.finish(), xx::hash64(&data));
+/// assert_eq!(h.len(), data.len() as u64);
+///
+/// // zero if the write took less than a tick of the clock
+/// assert!(h.throughput_bps() >= 0.0);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct TimedStream<H> {
+    hasher: H,
+    len: u64,
+    elapsed: Duration,
+}
+
+#[cfg(feature = "std")]
+impl<H: Hasher> TimedStream<H> {
+    /// Constructs a `TimedStream` from a hasher.
+    #[inline(always)]
+    pub fn from_hasher(hasher: H) -> Self {
+        TimedStream {
+            hasher,
+            len: 0,
+            elapsed: Duration::default(),
+        }
+    }
+
+    /// Returns the number of the written bytes.
+    #[inline(always)]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were written.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the total time spent writing into the inner hasher.
+    #[inline(always)]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the throughput of the inner hasher in bytes per second,
+    /// or `0.0` if no time was measured yet.
+    #[inline]
+    pub fn throughput_bps(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+
+        if secs > 0.0 {
+            self.len as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the inner hasher.
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwraps the inner hasher and the time spent writing into it.
+    #[inline(always)]
+    pub fn into_inner(self) -> (H, Duration) {
+        (self.hasher, self.elapsed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: Hasher> Hasher for TimedStream<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let start = Instant::now();
+
+        self.hasher.write(bytes);
+        self.elapsed += start.elapsed();
+        self.len += bytes.len() as u64;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: FastHasher> FastHasher for TimedStream<H> {
+    type Seed = H::Seed;
+    type Output = H::Output;
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        TimedStream::from_hasher(H::with_seed(seed))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: FastHasher> StreamHasher for TimedStream<H> {}
+
+#[cfg(feature = "std")]
+impl<H: Hasher> io::Write for TimedStream<H> {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        Hasher::write(self, bytes);
+
+        Ok(bytes.len())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "murmur", feature = "xx"))]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{murmur3, xx};
+
+    #[test]
+    fn test_dual_stream() {
+        let data = (0..100_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        let mut h = DualStream::<xx::Hasher64, murmur3::Hasher32>::new();
+
+        assert_eq!(h.write_stream(&mut Cursor::new(&data)).unwrap(), data.len());
+        assert_eq!(h.finish_a(), xx::hash64(&data));
+        assert_eq!(h.finish_b(), u64::from(murmur3::hash32(&data)));
+        assert_eq!(h.finish(), h.finish_a());
+
+        let seed = Seed::gen();
+        let mut h = DualStream::<xx::Hasher64, xx::Hasher64>::with_seed(seed.into());
+
+        h.write(b"hello");
+        assert_ne!(h.finish_a(), h.finish_b());
+    }
+
+    #[test]
+    fn test_multi_width() {
+        let data = (0..100_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        let mut h = MultiWidth::<murmur3::Hasher32, xx::Hasher64>::new();
+
+        assert_eq!(h.write_stream(&mut Cursor::new(&data)).unwrap(), data.len());
+        assert_eq!(h.finish32(), murmur3::hash32(&data));
+        assert_eq!(h.finish64(), xx::hash64(&data));
+        assert_eq!(h.finish(), h.finish64());
+
+        let mut h = MultiWidth::<xx::Hasher32, xx::Hasher64>::with_seed(DualSeed(123, 456));
+
+        for chunk in data.chunks(1000) {
+            h.write(chunk);
+        }
+
+        assert_eq!(h.finish32(), xx::hash32_with_seed(&data, 123));
+        assert_eq!(h.finish64(), xx::hash64_with_seed(&data, 456));
+    }
+
+    #[test]
+    fn test_checksum_hasher() {
+        let data = (0..100_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        let mut h = ChecksumHasher::<murmur3::Hasher32>::with_seed(123);
+
+        assert_eq!(h.write_stream(&mut Cursor::new(&data)).unwrap(), data.len());
+        assert_eq!(h.finish(), u64::from(murmur3::hash32_with_seed(&data, 123)));
+        assert_eq!(h.checksum(), crc::crc32c(&data));
+
+        let mut h = ChecksumHasher::<xx::Hasher64>::new();
+
+        for chunk in data.chunks(1000) {
+            h.write(chunk);
+        }
+
+        assert_eq!(h.finish(), xx::hash64(&data));
+        assert_eq!(h.checksum(), crc::crc32c(&data));
+    }
+
+    #[test]
+    fn test_counting_hasher() {
+        let data = (0..100_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        let mut h = CountingHasher::<xx::Hasher64>::with_seed(123);
+
+        assert!(h.is_empty());
+        assert_eq!(h.write_stream(&mut Cursor::new(&data)).unwrap(), data.len());
+        assert_eq!(h.len(), data.len() as u64);
+        assert_eq!(h.finish(), xx::hash64_with_seed(&data, 123));
+
+        let mut h = CountingHasher::<murmur3::Hasher32>::new();
+
+        for chunk in data.chunks(1000) {
+            h.write(chunk);
+        }
+        h.write(b"");
+
+        assert_eq!(h.len(), data.len() as u64);
+        assert_eq!(h.finish(), u64::from(murmur3::hash32(&data)));
+    }
+
+    #[cfg(feature = "city")]
+    #[test]
+    fn test_fmt_hasher() {
+        use core::fmt::Write;
+
+        use crate::city;
+
+        let mut h = FmtHasher::<city::Hasher64>::new();
+
+        write!(h, "{}", 42).unwrap();
+        assert_eq!(h.finish(), city::hash64(b"42"));
+
+        let mut h = FmtHasher::<xx::Hasher64>::with_seed(123);
+        let value = (1.5, "x", [1, 2]);
+
+        write!(h, "{:?}", value).unwrap();
+        h.write_char('!').unwrap();
+        assert_eq!(
+            h.into_inner().finish(),
+            xx::hash64_with_seed(format!("{:?}!", value), 123)
+        );
+    }
+
+    #[test]
+    fn test_peppered_hasher() {
+        let hash = |pepper| {
+            let mut h = PepperedHasher::new(murmur3::Hasher32::new(), pepper);
+            h.write(b"hello");
+            h.finish()
+        };
+
+        assert_eq!(hash(0), u64::from(murmur3::hash32(b"hello")));
+        assert_eq!(hash(123), hash(123));
+        assert_ne!(hash(123), hash(456));
+        assert_eq!(hash(123) ^ hash(456), 123 ^ 456);
+    }
+
+    #[test]
+    fn test_final_mix() {
+        /// A weak hasher summing the bytes, so the high input bits never reach the low bits.
+        #[derive(Default)]
+        struct ByteSum(u64);
+
+        impl Hasher for ByteSum {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for &b in bytes {
+                    self.0 = self.0.wrapping_add(u64::from(b));
+                }
+            }
+        }
+
+        // the average fraction of the low 8 output bits which change on a single input bit flip
+        fn low_bits_avalanche<H: Hasher + Default>() -> f64 {
+            let hash = |input: &[u8]| {
+                let mut h = H::default();
+                h.write(input);
+                h.finish()
+            };
+            let mut changed = 0;
+            let mut flips = 0;
+
+            for i in 0..100u64 {
+                let mut input = (i.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ i << 7).to_le_bytes();
+                let h = hash(&input);
+
+                for bit in 0..64 {
+                    input[bit / 8] ^= 1 << (bit % 8);
+                    changed += ((h ^ hash(&input)) & 0xff).count_ones();
+                    flips += 1;
+                    input[bit / 8] ^= 1 << (bit % 8);
+                }
+            }
+
+            f64::from(changed) / f64::from(flips * 8)
+        }
+
+        let raw = low_bits_avalanche::<ByteSum>();
+        let mixed = low_bits_avalanche::<FinalMix<ByteSum>>();
+
+        assert!((raw - 0.5).abs() > 0.2, "raw {}", raw);
+        assert!((mixed - 0.5).abs() < 0.05, "mixed {}", mixed);
+
+        let mut h = FinalMix::<xx::Hasher64>::with_seed(123);
+        h.write(b"hello");
+        assert_eq!(h.finish(), fmix64(xx::hash64_with_seed(b"hello", 123)));
+    }
+
+    #[test]
+    fn test_timed_stream() {
+        let data = (0..1_000_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        let mut h = TimedStream::<xx::Hasher64>::new();
+
+        assert_eq!(h.throughput_bps(), 0.0);
+
+        assert_eq!(h.write_stream(&mut Cursor::new(&data)).unwrap(), data.len());
+        io::copy(&mut Cursor::new(&data[..10]), &mut h).unwrap();
+
+        assert_eq!(h.len(), data.len() as u64 + 10);
+        assert_eq!(h.finish(), xx::hash64([&data[..], &data[..10]].concat()));
+
+        // no bounds on the timing, a coarse clock may not even tick while hashing a megabyte
+        assert!(h.throughput_bps() >= 0.0, "{}", h.throughput_bps());
+        assert_eq!(h.throughput_bps() > 0.0, h.elapsed() > Duration::default());
+    }
+
+    #[test]
+    fn test_bounded_stream_hasher() {
+        let data = (0..10_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        // exactly at the limit
+        let mut h = BoundedStreamHasher::new(xx::Hasher64::new(), data.len());
+
+        assert_eq!(h.write_stream(&mut Cursor::new(&data)).unwrap(), data.len());
+        assert_eq!(h.finish(), xx::hash64(&data));
+        assert_eq!(h.len(), data.len());
+
+        // the limit covers the total of all the streams
+        assert_eq!(h.write_stream(&mut Cursor::new(&[][..])).unwrap(), 0);
+        let err = h.write_stream(&mut Cursor::new(&b"!"[..])).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(h.finish(), xx::hash64(&data));
+
+        // over the limit
+        let mut h = BoundedStreamHasher::new(xx::Hasher64::new(), data.len() - 1);
+
+        let err = h.write_stream(&mut Cursor::new(&data)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(h.len() < data.len());
+        assert_eq!(h.finish(), xx::hash64(&data[..h.len()]));
+    }
+}
@@ -0,0 +1,135 @@
+//! `CRC32C` checksum (Castagnoli polynomial)
+//!
+//! The checksum used by iSCSI, ext4, Btrfs and many storage systems for error detection.
+//! It is computed with the `SSE4.2` CRC instruction if the CPU supports it at runtime,
+//! and with a lookup table otherwise.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::crc;
+//!
+//! assert_eq!(crc::crc32c(b"123456789"), 0xe306_9283);
+//! assert_eq!(crc::crc32c_update(crc::crc32c(b"12345"), b"6789"), 0xe306_9283);
+//! ```
+
+/// The reflected Castagnoli polynomial.
+const POLY: u32 = 0x82f6_3b78;
+
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+/// Computes the `CRC32C` checksum of a byte array.
+#[inline(always)]
+pub fn crc32c<T: AsRef<[u8]>>(bytes: T) -> u32 {
+    crc32c_update(0, bytes)
+}
+
+/// Continues the `CRC32C` checksum `crc` of the previous bytes with more bytes.
+#[inline]
+pub fn crc32c_update<T: AsRef<[u8]>>(crc: u32, bytes: T) -> u32 {
+    !raw_update(!crc, bytes.as_ref())
+}
+
+/// Updates the raw CRC state, without the pre and post inversion.
+pub(crate) fn raw_update(crc: u32, bytes: &[u8]) -> u32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if has_x86_feature!("sse4.2") {
+            return unsafe { raw_update_sse42(crc, bytes) };
+        }
+    }
+
+    raw_update_table(crc, bytes)
+}
+
+#[inline(always)]
+fn raw_update_table(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        crc = TABLE[((crc ^ u32::from(b)) & 0xff) as usize] ^ (crc >> 8);
+    }
+
+    crc
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.2")]
+unsafe fn raw_update_sse42(crc: u32, bytes: &[u8]) -> u32 {
+    use core::arch::x86_64::{_mm_crc32_u64, _mm_crc32_u8};
+
+    let mut crc = u64::from(crc);
+    let mut chunks = bytes.chunks_exact(8);
+
+    for chunk in &mut chunks {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        crc = _mm_crc32_u64(crc, u64::from_le_bytes(word));
+    }
+
+    let mut crc = crc as u32;
+
+    for &b in chunks.remainder() {
+        crc = _mm_crc32_u8(crc, b);
+    }
+
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32c() {
+        assert_eq!(crc32c(b""), 0);
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+        assert_eq!(crc32c(&[0u8; 32][..]), 0x8a91_36aa);
+        assert_eq!(crc32c(&[0xffu8; 32][..]), 0x62a8_ab43);
+    }
+
+    #[test]
+    fn test_table_matches_hardware() {
+        let data = (0..1000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        for len in 0..data.len() {
+            assert_eq!(
+                raw_update(!0, &data[..len]),
+                raw_update_table(!0, &data[..len])
+            );
+        }
+    }
+
+    #[test]
+    fn test_crc32c_update() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+
+        for i in 0..data.len() {
+            let (head, tail) = data.split_at(i);
+
+            assert_eq!(crc32c_update(crc32c(head), tail), crc32c(&data[..]));
+        }
+    }
+}
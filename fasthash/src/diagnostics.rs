@@ -0,0 +1,382 @@
+//! Diagnostics of the statistical quality of the hash functions
+//!
+//! The diagnostics help to compare the bit mixing of the hash functions,
+//! they are no replacement for a full test suite like `SMHasher`.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::{city, diagnostics};
+//!
+//! let score = diagnostics::avalanche::<city::Hash64>(100);
+//!
+//! assert!((score - 0.5).abs() < 0.05);
+//! ```
+#[cfg(feature = "std")]
+use core::marker::PhantomData;
+use core::mem;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hasher};
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use num_traits::PrimInt;
+use rand::RngCore;
+use xoroshiro128::Xoroshiro128Rng;
+
+use crate::hasher::FastHash;
+#[cfg(feature = "std")]
+use crate::hasher::{FastHasher, Seed};
+
+/// The length of the random inputs of `avalanche` in bytes.
+const AVALANCHE_INPUT_LEN: usize = 16;
+
+/// Measures the avalanche effect of a hash function with the default seed.
+///
+/// For each of the `samples` random inputs, every input bit is flipped in turn,
+/// and the fraction of the output bits which change is averaged over all flips.
+/// A hash function with a good avalanche effect changes about half of the output
+/// bits for any input bit, so the ideal score is `0.5`.
+///
+/// The inputs are generated from a fixed seed, so the score is reproducible.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero.
+pub fn avalanche<H: FastHash>(samples: usize) -> f64 {
+    assert!(samples > 0, "avalanche needs at least one sample");
+
+    let output_bits = mem::size_of::<H::Hash>() * 8;
+    let mut rng = Xoroshiro128Rng::from_seed_u64([0x6176_616c_616e_6368, 0x9e37_79b9_7f4a_7c15]);
+    let mut input = [0_u8; AVALANCHE_INPUT_LEN];
+    let mut changed = 0_u64;
+
+    for _ in 0..samples {
+        rng.fill_bytes(&mut input);
+
+        let h = H::hash(&input);
+
+        for bit in 0..AVALANCHE_INPUT_LEN * 8 {
+            input[bit / 8] ^= 1 << (bit % 8);
+            changed += u64::from((h ^ H::hash(&input)).count_ones());
+            input[bit / 8] ^= 1 << (bit % 8);
+        }
+    }
+
+    let flips = samples * AVALANCHE_INPUT_LEN * 8;
+
+    changed as f64 / (flips * output_bits) as f64
+}
+
+/// The number of buckets `DiagnosticState` counts the hashes into.
+#[cfg(feature = "std")]
+pub const DIAGNOSTIC_BUCKETS: usize = 256;
+
+/// The load of the buckets of the hashes sampled by a `DiagnosticState`.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadReport {
+    /// The number of sampled hashes.
+    pub samples: u64,
+    /// The number of the hashes in the most loaded bucket.
+    pub max_load: u64,
+    /// The load of the most loaded bucket relative to the mean load,
+    /// close to `1.0` for the well distributed hashes.
+    pub skew: f64,
+    /// The chi-squared statistic of the bucket loads against a uniform distribution,
+    /// about `DIAGNOSTIC_BUCKETS - 1` for the well distributed hashes.
+    pub chi_squared: f64,
+}
+
+#[cfg(feature = "std")]
+struct Buckets {
+    loads: Box<[AtomicU64]>,
+}
+
+#[cfg(feature = "std")]
+impl Buckets {
+    fn new() -> Self {
+        Buckets {
+            loads: (0..DIAGNOSTIC_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    #[inline(always)]
+    fn sample(&self, hash: u64) {
+        // the hash tables index their buckets with the low bits of the hash
+        self.loads[hash as usize % DIAGNOSTIC_BUCKETS].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn report(&self) -> LoadReport {
+        let loads = self
+            .loads
+            .iter()
+            .map(|load| load.load(Ordering::Relaxed))
+            .collect::<Vec<_>>();
+        let samples = loads.iter().sum::<u64>();
+        let max_load = loads.iter().copied().max().unwrap_or(0);
+
+        if samples == 0 {
+            return LoadReport {
+                samples,
+                max_load,
+                skew: 0.0,
+                chi_squared: 0.0,
+            };
+        }
+
+        let mean = samples as f64 / DIAGNOSTIC_BUCKETS as f64;
+
+        LoadReport {
+            samples,
+            max_load,
+            skew: max_load as f64 / mean,
+            chi_squared: loads
+                .iter()
+                .map(|&load| (load as f64 - mean).powi(2) / mean)
+                .sum(),
+        }
+    }
+}
+
+/// A `BuildHasher` which samples the hashes of the built hashers in debug builds.
+///
+/// It helps to diagnose a slow `HashMap`, confirming that the keys aren't pathological
+/// for the hash function. In debug builds, every `finish` of a hasher counts the low bits
+/// of the hash into `DIAGNOSTIC_BUCKETS` buckets, so the repeated lookups of a key
+/// count it again, and `report` returns the load of the buckets. In release builds,
+/// it builds the same hashers as `RandomState` and `report` returns `None`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fasthash::{diagnostics::DiagnosticState, xx};
+///
+/// let s = DiagnosticState::<xx::Hash64>::new();
+/// let mut map = HashMap::with_hasher(s.clone());
+///
+/// for i in 0..1000u32 {
+///     map.insert(i, i);
+/// }
+///
+/// if let Some(report) = s.report() {
+///     assert!(report.samples >= 1000);
+///     assert!(report.skew < 2.0, "{:?}", report);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub struct DiagnosticState<T: FastHash> {
+    seed: Seed,
+    buckets: Arc<Buckets>,
+    phantom: PhantomData<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T: FastHash> DiagnosticState<T> {
+    /// Constructs a new `DiagnosticState` that is initialized with random keys.
+    #[inline(always)]
+    pub fn new() -> Self {
+        DiagnosticState::with_seed(Seed::gen())
+    }
+
+    /// Constructs a new `DiagnosticState` that is initialized with the seed.
+    #[inline(always)]
+    pub fn with_seed(seed: Seed) -> Self {
+        DiagnosticState {
+            seed,
+            buckets: Arc::new(Buckets::new()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the load of the buckets of the hashes sampled by this state and its clones,
+    /// or `None` in release builds.
+    pub fn report(&self) -> Option<LoadReport> {
+        if cfg!(debug_assertions) {
+            Some(self.buckets.report())
+        } else {
+            None
+        }
+    }
+}
+
+/// The clones share the sampled hashes.
+#[cfg(feature = "std")]
+impl<T: FastHash> Clone for DiagnosticState<T> {
+    fn clone(&self) -> Self {
+        DiagnosticState {
+            seed: self.seed,
+            buckets: self.buckets.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FastHash> Default for DiagnosticState<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        DiagnosticState::new()
+    }
+}
+
+#[cfg(all(feature = "std", debug_assertions))]
+impl<T: FastHash> BuildHasher for DiagnosticState<T> {
+    type Hasher = DiagnosticHasher<T::FastHasher>;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Self::Hasher {
+        DiagnosticHasher {
+            hasher: T::FastHasher::with_seed(self.seed.into()),
+            buckets: self.buckets.clone(),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", not(debug_assertions)))]
+impl<T: FastHash> BuildHasher for DiagnosticState<T> {
+    type Hasher = T::FastHasher;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Self::Hasher {
+        T::FastHasher::with_seed(self.seed.into())
+    }
+}
+
+/// The hasher of a `DiagnosticState` in debug builds, sampling its hashes.
+#[cfg(all(feature = "std", debug_assertions))]
+pub struct DiagnosticHasher<H> {
+    hasher: H,
+    buckets: Arc<Buckets>,
+}
+
+#[cfg(all(feature = "std", debug_assertions))]
+impl<H: Hasher> Hasher for DiagnosticHasher<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        let hash = self.hasher.finish();
+
+        self.buckets.sample(hash);
+
+        hash
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct SumHash;
+
+    impl FastHash for SumHash {
+        type Hash = u64;
+        type Seed = u64;
+
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
+            bytes
+                .as_ref()
+                .iter()
+                .fold(seed, |h, &b| h.wrapping_add(u64::from(b)))
+        }
+    }
+
+    trivial_hasher! {
+        SumHasher(SumHash) -> u64
+    }
+
+    #[test]
+    fn test_avalanche() {
+        #[cfg(feature = "city")]
+        {
+            let score = avalanche::<crate::city::Hash64>(1000);
+
+            assert!((score - 0.5).abs() < 0.01, "city {}", score);
+        }
+
+        #[cfg(feature = "xx")]
+        {
+            let score = avalanche::<crate::xx::Hash64>(1000);
+
+            assert!((score - 0.5).abs() < 0.01, "xx {}", score);
+        }
+
+        let score = avalanche::<SumHash>(1000);
+
+        assert!(score < 0.1, "sum {}", score);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        use core::hash::Hasher;
+
+        use crate::hasher::FastHasher;
+
+        let mut h = SumHasher::new();
+        h.write(b"hello");
+        assert_eq!(h.finish(), SumHash::hash(b"hello"));
+
+        assert_eq!(avalanche::<SumHash>(10), avalanche::<SumHash>(10));
+    }
+
+    #[cfg(all(feature = "std", feature = "xx"))]
+    #[test]
+    fn test_diagnostic_state() {
+        use std::collections::HashSet;
+
+        let s = DiagnosticState::<crate::xx::Hash64>::new();
+        let mut set = HashSet::with_hasher(s.clone());
+
+        set.extend(0..10_000u64);
+
+        let report = match s.report() {
+            Some(report) => report,
+            None => return,
+        };
+
+        assert!(report.samples >= 10_000, "{:?}", report);
+        assert!(report.skew < 2.0, "{:?}", report);
+        assert!(
+            report.chi_squared < 2.0 * DIAGNOSTIC_BUCKETS as f64,
+            "{:?}",
+            report
+        );
+
+        // the bytes of the keys sum to a multiple of 256, so they all fall into one bucket
+        let s = DiagnosticState::<SumHash>::new();
+        let mut set = HashSet::with_hasher(s.clone());
+
+        set.extend((0..10_000u64).map(|i| {
+            let (a, b) = (i & 0xff, i >> 8);
+
+            a | b << 8 | ((512 - a - b) & 0xff) << 16
+        }));
+
+        let report = s.report().unwrap();
+
+        assert_eq!(report.max_load, report.samples);
+        assert!(
+            (report.skew - DIAGNOSTIC_BUCKETS as f64).abs() < 1e-9,
+            "{:?}",
+            report
+        );
+        assert!(
+            report.chi_squared > 100.0 * DIAGNOSTIC_BUCKETS as f64,
+            "{:?}",
+            report
+        );
+        assert_eq!(
+            DiagnosticState::<SumHash>::new().report().unwrap().samples,
+            0
+        );
+    }
+}
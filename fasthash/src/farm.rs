@@ -118,7 +118,7 @@
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
-use std::mem;
+use core::mem;
 
 use crate::ffi;
 
@@ -365,6 +365,18 @@ pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
 ///
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
+/// # Example
+///
+/// ```
+/// use fasthash::farm;
+///
+/// assert_eq!(farm::hash64_with_seeds(b"hello", 123, 456), 15077713332534145879);
+/// assert_ne!(
+///     farm::hash64_with_seeds(b"hello", 123, 456),
+///     farm::hash64_with_seeds(b"hello", 456, 123)
+/// );
+/// ```
+#[inline(always)]
 pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
     Hash64::hash_with_seeds(v, seed0, seed1)
 }
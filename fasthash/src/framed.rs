@@ -0,0 +1,325 @@
+//! Collision-safe hashing of composite keys
+//!
+//! Hashing the concatenation of the fields of a composite key is ambiguous,
+//! `("ab", "c")` and `("a", "bc")` produce the same bytes. `Framed` writes every
+//! element in a self-delimiting frame through the incremental `Hasher` path instead:
+//!
+//! - a byte array or string is prefixed with its length as a 64-bit little endian integer,
+//! - an integer is written as its fixed-width little endian bytes (`usize` and `isize` as 64-bit),
+//! - a tuple writes the frames of its elements in order,
+//! - a slice of byte arrays or strings is prefixed with the number of elements
+//!   as a 64-bit little endian integer, followed by the frame of each element.
+//!
+//! The framing is part of the hash value, and will not change between releases.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+use crate::hasher::FastHasher;
+
+/// A value which could be written to a `Hasher` in self-delimiting frames.
+pub trait Framed {
+    /// Writes the frames of the value into the hasher.
+    fn write_framed<H: Hasher>(&self, h: &mut H);
+}
+
+#[inline(always)]
+fn write_len<H: Hasher>(h: &mut H, len: usize) {
+    h.write(&(len as u64).to_le_bytes())
+}
+
+impl Framed for [u8] {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        write_len(h, self.len());
+        h.write(self)
+    }
+}
+
+impl Framed for str {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        self.as_bytes().write_framed(h)
+    }
+}
+
+impl Framed for Vec<u8> {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        self.as_slice().write_framed(h)
+    }
+}
+
+impl Framed for String {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        self.as_bytes().write_framed(h)
+    }
+}
+
+impl<'a, T: Framed + ?Sized> Framed for &'a T {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        (**self).write_framed(h)
+    }
+}
+
+macro_rules! impl_framed_int {
+    ($($ty:ty)*) => {$(
+        impl Framed for $ty {
+            #[inline(always)]
+            fn write_framed<H: Hasher>(&self, h: &mut H) {
+                h.write(&self.to_le_bytes())
+            }
+        }
+    )*};
+}
+
+impl_framed_int! { u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 }
+
+impl Framed for usize {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        (*self as u64).write_framed(h)
+    }
+}
+
+impl Framed for isize {
+    #[inline(always)]
+    fn write_framed<H: Hasher>(&self, h: &mut H) {
+        (*self as i64).write_framed(h)
+    }
+}
+
+macro_rules! impl_framed_slice {
+    ($($ty:ty)*) => {$(
+        impl Framed for [$ty] {
+            #[inline(always)]
+            fn write_framed<H: Hasher>(&self, h: &mut H) {
+                write_len(h, self.len());
+
+                for item in self {
+                    item.write_framed(h)
+                }
+            }
+        }
+
+        impl Framed for Vec<$ty> {
+            #[inline(always)]
+            fn write_framed<H: Hasher>(&self, h: &mut H) {
+                self.as_slice().write_framed(h)
+            }
+        }
+    )*};
+}
+
+impl_framed_slice! { &[u8] &str Vec<u8> String }
+
+macro_rules! impl_framed_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: Framed),+> Framed for ($($name,)+) {
+            #[allow(non_snake_case)]
+            #[inline(always)]
+            fn write_framed<H: Hasher>(&self, h: &mut H) {
+                let ($(ref $name,)+) = *self;
+
+                $($name.write_framed(h);)+
+            }
+        }
+    };
+}
+
+impl_framed_tuple! { A }
+impl_framed_tuple! { A B }
+impl_framed_tuple! { A B C }
+impl_framed_tuple! { A B C D }
+impl_framed_tuple! { A B C D E }
+impl_framed_tuple! { A B C D E F }
+impl_framed_tuple! { A B C D E F G }
+impl_framed_tuple! { A B C D E F G I }
+
+/// Declares a struct and implements `Framed` for it, writing the frames of its fields
+/// in the declaration order.
+///
+/// The fields are framed like the elements of a tuple, so the byte arrays and strings
+/// are prefixed with their length, and the hash of the struct is stable as long as
+/// the order and the types of its fields don't change. Reordering the fields changes
+/// the hash, even between the fields of the same type. The struct can't be generic,
+/// and every field type must implement `Framed`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{fasthash_struct, hash_tuple, murmur3::Hasher32};
+///
+/// fasthash_struct! {
+///     #[derive(Clone, Debug)]
+///     pub struct Key {
+///         pub name: String,
+///         pub id: u32,
+///     }
+/// }
+///
+/// let key = Key { name: "hello".into(), id: 42 };
+///
+/// assert_eq!(
+///     hash_tuple::<Hasher32, _>(&key),
+///     hash_tuple::<Hasher32, _>(&("hello", 42u32))
+/// );
+/// ```
+#[macro_export]
+macro_rules! fasthash_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($(#[$field_meta])* $field_vis $field : $ty),*
+        }
+
+        impl $crate::Framed for $name {
+            #[inline(always)]
+            fn write_framed<H: ::core::hash::Hasher>(&self, _h: &mut H) {
+                $($crate::Framed::write_framed(&self.$field, _h);)*
+            }
+        }
+    };
+}
+
+/// Hashes a composite value in self-delimiting frames with the hasher.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{hash_tuple, murmur3::Hasher32};
+///
+/// assert_ne!(
+///     hash_tuple::<Hasher32, _>(&("ab", "c")),
+///     hash_tuple::<Hasher32, _>(&("a", "bc"))
+/// );
+/// assert_ne!(
+///     hash_tuple::<Hasher32, _>(&[&b"ab"[..], b"c"][..]),
+///     hash_tuple::<Hasher32, _>(&[&b"a"[..], b"bc"][..])
+/// );
+/// assert_eq!(
+///     hash_tuple::<Hasher32, _>(&("hello", 42u32)),
+///     hash_tuple::<Hasher32, _>(&(String::from("hello"), 42u32))
+/// );
+/// ```
+#[inline(always)]
+pub fn hash_tuple<H: FastHasher, T: Framed + ?Sized>(value: &T) -> u64 {
+    hash_tuple_with_seed::<H, T>(value, Default::default())
+}
+
+/// Hashes a composite value in self-delimiting frames with the hasher and seed.
+#[inline(always)]
+pub fn hash_tuple_with_seed<H: FastHasher, T: Framed + ?Sized>(value: &T, seed: H::Seed) -> u64 {
+    let mut h = H::with_seed(seed);
+    value.write_framed(&mut h);
+    h.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+
+    fn framed<T: Framed + ?Sized>(value: &T) -> Vec<u8> {
+        struct Collect(Vec<u8>);
+
+        impl Hasher for Collect {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.extend_from_slice(bytes)
+            }
+        }
+
+        let mut h = Collect(vec![]);
+        value.write_framed(&mut h);
+        h.0
+    }
+
+    #[test]
+    fn test_framing() {
+        assert_eq!(framed("ab"), &b"\x02\0\0\0\0\0\0\0ab"[..]);
+        assert_eq!(framed(&1u16), &b"\x01\0"[..]);
+        assert_eq!(framed(&1usize), &b"\x01\0\0\0\0\0\0\0"[..]);
+        assert_eq!(framed(&("a", 1u8)), &b"\x01\0\0\0\0\0\0\0a\x01"[..]);
+        assert_eq!(
+            framed(&["a", "b"][..]),
+            &b"\x02\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0a\x01\0\0\0\0\0\0\0b"[..]
+        );
+    }
+
+    #[test]
+    fn test_hash_tuple() {
+        let ab_c = hash_tuple::<DefaultHasherWrapper, _>(&("ab", "c"));
+        let a_bc = hash_tuple::<DefaultHasherWrapper, _>(&("a", "bc"));
+
+        assert_ne!(ab_c, a_bc);
+        assert_eq!(
+            ab_c,
+            hash_tuple::<DefaultHasherWrapper, _>(&(&b"ab"[..], b"c".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_fasthash_struct() {
+        fasthash_struct! {
+            struct Point {
+                x: u32,
+                y: u32,
+            }
+        }
+
+        fasthash_struct! {
+            struct Swapped {
+                y: u32,
+                x: u32,
+            }
+        }
+
+        fasthash_struct! {
+            struct Empty {}
+        }
+
+        let point = Point { x: 1, y: 2 };
+        let swapped = Swapped { y: 2, x: 1 };
+
+        assert_eq!(framed(&point), framed(&(1u32, 2u32)));
+        assert_eq!(framed(&swapped), framed(&(2u32, 1u32)));
+        assert_eq!(framed(&Empty {}), b"");
+        assert_ne!(
+            hash_tuple::<DefaultHasherWrapper, _>(&point),
+            hash_tuple::<DefaultHasherWrapper, _>(&swapped)
+        );
+    }
+
+    struct DefaultHasherWrapper(DefaultHasher);
+
+    impl Hasher for DefaultHasherWrapper {
+        fn finish(&self) -> u64 {
+            self.0.finish()
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.write(bytes)
+        }
+    }
+
+    impl FastHasher for DefaultHasherWrapper {
+        type Seed = u64;
+        type Output = u64;
+
+        fn with_seed(_seed: u64) -> Self {
+            DefaultHasherWrapper(DefaultHasher::new())
+        }
+    }
+}
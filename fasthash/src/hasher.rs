@@ -1,12 +1,36 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use core::cell::RefCell;
-use core::hash::{BuildHasher, Hasher};
+use core::fmt;
+use core::hash::{BuildHasher, Hash, Hasher};
 use core::marker::PhantomData;
+#[cfg(feature = "std")]
 use std::io;
 
-use derive_more::{Deref, DerefMut};
+use derive_more::{From, Into};
 use num_traits::PrimInt;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
 use rand::Rng;
-use xoroshiro128::Xoroshiro128Rng;
+
+#[cfg(feature = "secure")]
+lazy_static::lazy_static! {
+    /// The random salt of the seeds of the process.
+    static ref PROCESS_SALT: [u64; 2] = OsRng.gen();
+}
+
+/// Salts the seed with the random salt of the process.
+#[cfg(feature = "secure")]
+#[inline(always)]
+fn process_salted(seed: Seed) -> Seed {
+    seed.salted(*PROCESS_SALT)
+}
+
+#[cfg(all(feature = "std", not(feature = "secure")))]
+#[inline(always)]
+fn process_salted(seed: Seed) -> Seed {
+    seed
+}
 
 /// Generate a good, portable, forever-fixed hash value
 pub trait Fingerprint<T: PrimInt> {
@@ -14,12 +38,128 @@ pub trait Fingerprint<T: PrimInt> {
     fn fingerprint(&self) -> T;
 }
 
+/// A 128-bit hash value, e.g. the result of `HasherExt::finish_ext`.
+///
+/// It converts to and from `[u8; 16]` in little-endian order, the natural storage form
+/// of content hashes, which is the byte order of `murmur3::hash128_x64_bytes`
+/// on little-endian targets.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::Digest128;
+///
+/// let digest = Digest128::from(0x0f0e0d0c_0b0a0908_07060504_03020100);
+/// let bytes: [u8; 16] = digest.into();
+///
+/// assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// assert_eq!(Digest128::from(bytes), digest);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, From, Into)]
+pub struct Digest128(pub u128);
+
+impl Digest128 {
+    /// Constructs the hash value from its low and high 64 bits.
+    #[inline(always)]
+    pub fn from_halves(low: u64, high: u64) -> Self {
+        Digest128(u128::from(low) | u128::from(high) << 64)
+    }
+
+    /// Returns the low 64 bits of the hash value.
+    #[inline(always)]
+    pub fn low64(self) -> u64 {
+        self.0 as u64
+    }
+
+    /// Returns the high 64 bits of the hash value.
+    #[inline(always)]
+    pub fn high64(self) -> u64 {
+        (self.0 >> 64) as u64
+    }
+}
+
+/// Compares a 128-bit hash value with its low and high 64 bits computed elsewhere,
+/// e.g. the `first` and `second` halves of the `uint128` of `CityHash`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::eq_128;
+///
+/// let hash = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+///
+/// assert!(eq_128(hash, 0xfedc_ba98_7654_3210, 0x0123_4567_89ab_cdef));
+/// assert!(!eq_128(hash, 0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210));
+/// ```
+#[inline(always)]
+pub fn eq_128(a: u128, low: u64, high: u64) -> bool {
+    Digest128(a) == Digest128::from_halves(low, high)
+}
+
+impl From<[u8; 16]> for Digest128 {
+    #[inline(always)]
+    fn from(bytes: [u8; 16]) -> Self {
+        Digest128(u128::from_le_bytes(bytes))
+    }
+}
+
+impl From<Digest128> for [u8; 16] {
+    #[inline(always)]
+    fn from(digest: Digest128) -> Self {
+        digest.0.to_le_bytes()
+    }
+}
+
 #[doc(hidden)]
 pub trait BuildHasherExt: BuildHasher {
     type FastHasher: FastHasher;
 }
 
 /// Fast non-cryptographic hash functions
+///
+/// The hash functions take any `T: AsRef<[u8]>` by value, so an owned buffer,
+/// a reference to it, or a reference to a reference in generic code all hash the same.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{city, FastHash};
+///
+/// let v: Vec<u8> = b"hello".to_vec();
+/// let h = city::Hash64::hash(b"hello");
+///
+/// assert_eq!(city::Hash64::hash(&v), h);
+/// assert_eq!(city::Hash64::hash(&v[..]), h);
+/// assert_eq!(city::Hash64::hash(String::from("hello")), h);
+/// assert_eq!(city::Hash64::hash(v), h);
+///
+/// fn hash_keys<K: AsRef<[u8]>>(keys: &[K]) -> Vec<u64> {
+///     keys.iter().map(city::hash64).collect()
+/// }
+///
+/// assert_eq!(hash_keys(&["hello"]), [h]);
+/// assert_eq!(hash_keys(&[b"hello".to_vec()]), [h]);
+/// ```
+///
+/// A mutable borrow is hashed read-only, like a shared one. Since the bytes are taken
+/// by value, a `&mut [u8]` binding is moved into the call, reborrow it with `&*buf`
+/// to keep using the buffer.
+///
+/// ```
+/// use fasthash::{city, FastHash};
+///
+/// let mut v = b"hello".to_vec();
+/// let buf: &mut [u8] = &mut v;
+///
+/// assert_eq!(city::Hash64::hash(&*buf), city::Hash64::hash(b"hello"));
+/// assert_eq!(city::hash64(&mut *buf), city::hash64(b"hello"));
+///
+/// buf[0] = b'j';
+///
+/// assert_eq!(city::hash64(&mut v), city::hash64(b"jello"));
+/// assert_eq!(v, b"jello");
+/// ```
 pub trait FastHash: BuildHasherExt {
     /// The output hash generated value.
     type Hash: PrimInt;
@@ -34,8 +174,83 @@ pub trait FastHash: BuildHasherExt {
     fn hash<T: AsRef<[u8]>>(bytes: T) -> Self::Hash {
         Self::hash_with_seed(bytes, Default::default())
     }
+
+    /// The length in bytes of the longest input the hash function hashes correctly.
+    ///
+    /// The native implementations which take the length as a C `int` can't hash
+    /// more than `i32::MAX` bytes, the longer inputs are hashed with a wrong length.
+    /// They are `lookup3`, `meow`, and the 32-bit, 64-bit and 128-bit hash functions
+    /// of `murmur`, `murmur2` and `murmur3`, the others hash inputs of any length.
+    const MAX_INPUT_LEN: usize = usize::MAX;
+
+    /// Hash functions for a byte array, or `HashError` if the input is longer
+    /// than `MAX_INPUT_LEN` bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fasthash::{murmur3, FastHash};
+    ///
+    /// assert_eq!(murmur3::Hash32::try_hash(b"hello"), Ok(murmur3::hash32(b"hello")));
+    /// assert_eq!(murmur3::Hash32::MAX_INPUT_LEN, i32::MAX as usize);
+    /// ```
+    #[inline(always)]
+    fn try_hash<T: AsRef<[u8]>>(bytes: T) -> Result<Self::Hash, HashError> {
+        Self::try_hash_with_seed(bytes, Default::default())
+    }
+
+    /// Hash functions for a byte array with the seed, or `HashError` if the input is longer
+    /// than `MAX_INPUT_LEN` bytes.
+    #[inline(always)]
+    fn try_hash_with_seed<T: AsRef<[u8]>>(
+        bytes: T,
+        seed: Self::Seed,
+    ) -> Result<Self::Hash, HashError> {
+        let len = bytes.as_ref().len();
+
+        if len > Self::MAX_INPUT_LEN {
+            Err(HashError {
+                len,
+                max_len: Self::MAX_INPUT_LEN,
+            })
+        } else {
+            Ok(Self::hash_with_seed(bytes, seed))
+        }
+    }
 }
 
+/// The error returned when the input is longer than the hash function can hash correctly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashError {
+    len: usize,
+    max_len: usize,
+}
+
+impl HashError {
+    /// The length of the input in bytes.
+    pub fn input_len(&self) -> usize {
+        self.len
+    }
+
+    /// The length of the longest input of the hash function, its `MAX_INPUT_LEN`.
+    pub fn max_input_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the input of {} bytes is longer than the {} bytes the hash function handles",
+            self.len, self.max_len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HashError {}
+
 /// Fast non-cryptographic hasher
 pub trait FastHasher: Hasher
 where
@@ -54,6 +269,7 @@ where
     }
 
     /// Constructs a new `FastHasher` with a random seed.
+    #[cfg(feature = "std")]
     fn with_random_seed() -> Self {
         Self::with_seed(Seed::gen().into())
     }
@@ -62,7 +278,22 @@ where
     fn with_seed(seed: Self::Seed) -> Self;
 }
 
+/// The default limit of the bytes buffered by the `BufHasher` hashers, 1 GiB.
+pub const DEFAULT_BUFFER_LIMIT: usize = 1 << 30;
+
 /// Hasher in the buffer mode for short key
+///
+/// The buffered hashers keep the written bytes until `finish` and hash them with
+/// the one-shot function, up to a limit (`DEFAULT_BUFFER_LIMIT` unless set with
+/// `with_buffer_limit`). Past the limit, the hashers of the functions with a native
+/// incremental state, `spooky::Hasher32`, `spooky::Hasher64`, `umash::Hasher64` and
+/// `umash::Hasher128`, switch to it and stop buffering, so they hash in bounded memory.
+/// The other buffered hashers aren't suitable for huge inputs like a file of a few GB,
+/// and panic in debug builds past the limit, use a native streaming hasher instead,
+/// e.g. `xx::Hasher64`, `xxh3::Hasher64`, `spooky::Hasher128` or `t1ha2::Hasher128`.
+///
+/// A hasher built `with_finish_guard` also panics in debug builds on a `write` after
+/// `finish` without a `reset`, for the code expecting `finish` to start a new value.
 pub trait BufHasher: FastHasher + AsRef<[u8]> {
     /// Constructs a buffered hasher with capacity and seed
     fn with_capacity_and_seed(capacity: usize, seed: Option<Self::Seed>) -> Self;
@@ -87,36 +318,90 @@ pub trait BufHasher: FastHasher + AsRef<[u8]> {
 }
 
 /// Hasher in the streaming mode without buffer
+#[cfg(feature = "std")]
 pub trait StreamHasher: FastHasher + Sized {
     /// Writes the stream into this hasher.
+    ///
+    /// Returns the number of bytes read from the stream, every one of them is hashed exactly once.
+    /// If the reader fails, the bytes read before the error are still hashed.
     fn write_stream<R: io::Read>(&mut self, r: &mut R) -> io::Result<usize> {
+        self.write_stream_with_progress(r, |_| {})
+    }
+
+    /// Writes the stream of a reader trait object into this hasher like `write_stream`.
+    ///
+    /// Every reader type monomorphizes `write_stream` again, this one is compiled once
+    /// for all the readers, at the cost of a dynamic call per read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    /// use std::io::{self, Cursor};
+    ///
+    /// use fasthash::{xx::Hasher64, FastHasher, StreamHasher};
+    ///
+    /// let mut readers: Vec<Box<dyn io::Read>> =
+    ///     vec![Box::new(Cursor::new(b"hello")), Box::new(io::empty())];
+    ///
+    /// let mut h = Hasher64::new();
+    ///
+    /// for r in &mut readers {
+    ///     h.write_stream_dyn(r.as_mut()).unwrap();
+    /// }
+    ///
+    /// assert_eq!(h.finish(), fasthash::xx::hash64(b"hello"));
+    /// ```
+    fn write_stream_dyn(&mut self, mut r: &mut dyn io::Read) -> io::Result<usize> {
+        self.write_stream(&mut r)
+    }
+
+    /// Writes the stream into this hasher like `write_stream`,
+    /// calling `cb` with the total number of bytes read so far after every chunk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    /// use std::io::Cursor;
+    ///
+    /// use fasthash::{xx::Hasher64, FastHasher, StreamHasher};
+    ///
+    /// let mut h = Hasher64::new();
+    /// let mut progress = 0;
+    ///
+    /// h.write_stream_with_progress(&mut Cursor::new(&b"hello world"[..]), |n| progress = n)
+    ///     .unwrap();
+    /// assert_eq!(progress, 11);
+    /// assert_eq!(h.finish(), fasthash::xx::hash64(b"hello world"));
+    /// ```
+    fn write_stream_with_progress<R: io::Read>(
+        &mut self,
+        r: &mut R,
+        mut cb: impl FnMut(usize),
+    ) -> io::Result<usize> {
         let mut buf = [0_u8; 4096];
         let mut len = 0;
         let mut pos = 0;
-        let ret;
-
-        loop {
-            if pos == buf.len() {
-                self.write(&buf[..]);
-                pos = 0;
-            }
-
+        let ret = loop {
             match r.read(&mut buf[pos..]) {
-                Ok(0) => {
-                    ret = Ok(len);
-                    break;
-                }
+                Ok(0) => break Ok(len),
                 Ok(n) => {
                     len += n;
                     pos += n;
+
+                    // flush the buffer once it is full, so the next read always has room
+                    if pos == buf.len() {
+                        self.write(&buf[..]);
+                        pos = 0;
+                    }
+
+                    cb(len);
                 }
                 Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
-                Err(e) => {
-                    ret = Err(e);
-                    break;
-                }
+                Err(e) => break Err(e),
             }
-        }
+        };
 
         if pos > 0 {
             self.write(&buf[..pos])
@@ -124,14 +409,225 @@ pub trait StreamHasher: FastHasher + Sized {
 
         ret
     }
+
+    /// Writes the buffered stream into this hasher,
+    /// hashing directly from the internal buffer of the reader without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    /// use std::io::{BufReader, Cursor};
+    ///
+    /// use fasthash::{xx::Hasher64, FastHasher, StreamHasher};
+    ///
+    /// let mut h = Hasher64::new();
+    ///
+    /// h.write_buf_stream(&mut BufReader::new(Cursor::new(&b"hello world"[..]))).unwrap();
+    /// assert_eq!(h.finish(), fasthash::xx::hash64(b"hello world"));
+    /// ```
+    fn write_buf_stream<R: io::BufRead>(&mut self, r: &mut R) -> io::Result<usize> {
+        let mut len = 0;
+
+        loop {
+            let n = match r.fill_buf() {
+                Ok(buf) if buf.is_empty() => return Ok(len),
+                Ok(buf) => {
+                    self.write(buf);
+                    buf.len()
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            r.consume(n);
+            len += n;
+        }
+    }
+}
+
+/// Hasher which could be reused for many items
+///
+/// The hashers also implement `io::Write`, so a pipeline could hash every item
+/// with `io::copy`, read the result with `finish`, and `reset` the hasher for the next one.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+/// use std::io::{self, Cursor};
+///
+/// use fasthash::{xxh3, FastHasher, ResetHasher};
+///
+/// let mut h = xxh3::Hasher64::with_seed(123);
+///
+/// for item in &[&b"hello"[..], &b"world"[..]] {
+///     io::copy(&mut Cursor::new(item), &mut h).unwrap();
+///     assert_eq!(h.finish(), xxh3::hash64_with_seed(item, 123));
+///     h.reset();
+/// }
+/// ```
+pub trait ResetHasher: FastHasher {
+    /// Resets the hasher to its initial state with the same seed.
+    fn reset(&mut self);
 }
 
 /// A trait which represents the ability to hash an arbitrary stream of bytes.
+///
+/// Only the 128-bit hashers implement it, so it bounds the generic code
+/// which needs a 128-bit hash, see `hash128_generic`.
 pub trait HasherExt: Hasher {
     /// Completes a round of hashing, producing the output hash generated.
     fn finish_ext(&self) -> u128;
+
+    /// Returns the 128-bit hash of the bytes written so far without changing the state,
+    /// so the hashing can go on after a checkpoint.
+    ///
+    /// The buffering hashers hash the buffered bytes, the streaming ones finalize
+    /// a copy of their state if finalizing it would change it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{xxh3, FastHasher, HasherExt};
+    ///
+    /// let mut h = xxh3::Hasher128::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.snapshot(), xxh3::hash128(b"hello"));
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.snapshot(), xxh3::hash128(b"helloworld"));
+    /// ```
+    #[inline(always)]
+    fn snapshot(&self) -> u128 {
+        self.finish_ext()
+    }
+}
+
+/// Hashes a byte array with any 128-bit hasher and the default seed.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{city, hash128_generic, spooky};
+///
+/// let key = b"hello".to_vec();
+///
+/// assert_eq!(hash128_generic::<city::Hasher128, _>(&key), city::hash128(b"hello"));
+/// assert_eq!(hash128_generic::<spooky::Hasher128, _>("hello"), spooky::hash128(b"hello"));
+/// ```
+///
+/// The 64-bit hashers don't implement `HasherExt`,
+///
+/// ```compile_fail
+/// use fasthash::{hash128_generic, xx};
+///
+/// hash128_generic::<xx::Hasher64, _>(b"hello");
+/// ```
+#[inline]
+pub fn hash128_generic<H: FastHasher + HasherExt, T: AsRef<[u8]>>(bytes: T) -> u128 {
+    let mut h = H::new();
+    h.write(bytes.as_ref());
+    h.finish_ext()
+}
+
+/// Extension methods to write raw values into any `Hasher`.
+pub trait HasherWriteExt: Hasher {
+    /// Writes exactly the UTF-8 bytes of a string into this hasher.
+    ///
+    /// Unlike the `Hash` implementation of `str`, which writes the bytes followed by
+    /// a `0xff` terminator, the result matches hashing `s.as_bytes()` with the one-shot
+    /// hash functions or other tools.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::{Hash, Hasher};
+    ///
+    /// use fasthash::{city, FastHasher, HasherWriteExt};
+    ///
+    /// let mut h = city::Hasher64::new();
+    /// h.write_str_raw("abc");
+    /// assert_eq!(h.finish(), city::hash64(b"abc"));
+    ///
+    /// let mut h = city::Hasher64::new();
+    /// "abc".hash(&mut h);
+    /// assert_eq!(h.finish(), city::hash64(b"abc\xff"));
+    /// ```
+    #[inline(always)]
+    fn write_str_raw(&mut self, s: &str) {
+        self.write(s.as_bytes())
+    }
+
+    /// Writes a `usize` as 8 little-endian bytes into this hasher, on any target.
+    ///
+    /// The default `write_usize` writes the native bytes of the `usize`, 4 bytes
+    /// on the 32-bit targets and 8 bytes on the 64-bit ones, so the hash of a value
+    /// containing a `usize`, e.g. a length, changes across targets. The portable form
+    /// hashes the same as `write_u64(v as u64)` on a little-endian 64-bit target.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{city, FastHasher, HasherWriteExt};
+    ///
+    /// let mut h = city::Hasher64::new();
+    /// h.write_usize_portable(42);
+    /// assert_eq!(h.finish(), city::hash64(42u64.to_le_bytes()));
+    /// ```
+    #[inline(always)]
+    fn write_usize_portable(&mut self, v: usize) {
+        self.write(&(v as u64).to_le_bytes())
+    }
+
+    /// Writes an `isize` as 8 little-endian bytes into this hasher, on any target,
+    /// sign extended like `write_usize_portable(v as usize)` on a 64-bit target.
+    #[inline(always)]
+    fn write_isize_portable(&mut self, v: isize) {
+        self.write(&(v as i64).to_le_bytes())
+    }
+
+    /// Consumes a `bytes::Buf` into this hasher, writing its chunks in place without copying.
+    ///
+    /// The result matches hashing the contiguous bytes of the buffer,
+    /// e.g. a chain of `Bytes` hashes the same as the concatenated bytes.
+    /// A `Bytes` or `BytesMut` could also be hashed directly, since it is `AsRef<[u8]>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use bytes::{Buf, Bytes};
+    /// use fasthash::{xx, FastHasher, HasherWriteExt};
+    ///
+    /// let mut h = xx::Hasher64::new();
+    /// h.write_buf(Bytes::from_static(b"hello ").chain(Bytes::from_static(b"world")));
+    ///
+    /// assert_eq!(h.finish(), xx::hash64(b"hello world"));
+    /// ```
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn write_buf<B: bytes::Buf>(&mut self, mut buf: B) {
+        while buf.has_remaining() {
+            let n = {
+                let chunk = buf.chunk();
+                self.write(chunk);
+                chunk.len()
+            };
+
+            buf.advance(n);
+        }
+    }
 }
 
+impl<H: Hasher + ?Sized> HasherWriteExt for H {}
+
 /// Generate hash seeds
 ///
 /// It base on the same workflow from `std::collections::RandomState`
@@ -155,25 +651,431 @@ pub trait HasherExt: Hasher {
 ///
 /// city::hash128_with_seed(b"hello world", Seed::gen().into());
 /// ```
-#[derive(Clone, Copy, Debug, Deref, DerefMut)]
-pub struct Seed(Xoroshiro128Rng);
+///
+/// # Conversions
+///
+/// A seed converts into the integer, tuple and array seeds of the hash functions
+/// by drawing the words from its generator. The tuples and arrays draw their fields
+/// in order, so `(a, b)` draws `a` first, while `u128` draws its high 64 bits first.
+/// Every hash function documents how the fields map to the seeds of its native
+/// implementation.
+///
+/// # Generators
+///
+/// The words are drawn from an internal generator, which isn't part of the API.
+/// Seed it from any source of randomness with `from_words`, the `rand` based `from_rng`
+/// is deprecated, so `rand` can be upgraded without changing the API.
+#[derive(Clone, Copy, Debug)]
+pub struct Seed(SeedRngImpl);
+
+/// The generator of the words of `Seed`.
+pub(crate) trait SeedRng: Copy {
+    /// Generates the next word.
+    fn next_word(&mut self) -> u64;
+
+    /// Clears the state, so the generator only generates zero words.
+    #[cfg(feature = "secure")]
+    fn clear(&mut self);
+}
+
+type SeedRngImpl = Xoroshiro128;
+
+/// The `xoroshiro128+` generator of `Seed`, which generates the same words as
+/// `Xoroshiro128Rng` of the `xoroshiro128` crate, with a `const` constructor.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Xoroshiro128([u64; 2]);
+
+impl Xoroshiro128 {
+    #[inline(always)]
+    const fn new(words: [u64; 2]) -> Self {
+        Xoroshiro128(words)
+    }
+}
+
+impl SeedRng for Xoroshiro128 {
+    #[inline(always)]
+    fn next_word(&mut self) -> u64 {
+        let [s0, mut s1] = self.0;
+        let word = s0.wrapping_add(s1);
+
+        s1 ^= s0;
+        self.0 = [s0.rotate_left(55) ^ s1 ^ (s1 << 14), s1.rotate_left(36)];
+
+        word
+    }
+
+    #[cfg(feature = "secure")]
+    fn clear(&mut self) {
+        use core::sync::atomic::{self, Ordering};
+
+        // SAFETY: `self` is valid for writes, and the zeroed generator is written as a whole,
+        // volatile so the compiler doesn't elide the write to a seed about to be dropped
+        unsafe { core::ptr::write_volatile(self, Xoroshiro128::new([0; 2])) };
+
+        atomic::compiler_fence(Ordering::SeqCst);
+    }
+}
+
+/// A seed of a hash function, drawn word by word from `Seed`.
+pub(crate) trait DrawSeed: Sized {
+    /// Draws the seed from the next words of the generator.
+    fn draw(seed: &mut Seed) -> Self;
+}
+
+impl DrawSeed for u32 {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> u32 {
+        seed.next_word() as u32
+    }
+}
+
+impl DrawSeed for u64 {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> u64 {
+        seed.next_word()
+    }
+}
+
+/// Draws the low 64 bits first, unlike `From<Seed> for u128`,
+/// which keeps the order of the `rand` generators the fields were drawn with.
+impl DrawSeed for u128 {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> u128 {
+        let lo = seed.next_word();
+        let hi = seed.next_word();
+
+        u128::from(hi) << 64 | u128::from(lo)
+    }
+}
+
+impl<A: DrawSeed, B: DrawSeed> DrawSeed for (A, B) {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> (A, B) {
+        let a = A::draw(seed);
+
+        (a, B::draw(seed))
+    }
+}
+
+impl<A: DrawSeed, B: DrawSeed, C: DrawSeed, D: DrawSeed> DrawSeed for (A, B, C, D) {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> (A, B, C, D) {
+        let a = A::draw(seed);
+        let b = B::draw(seed);
+        let c = C::draw(seed);
+
+        (a, b, c, D::draw(seed))
+    }
+}
+
+impl DrawSeed for [u64; 2] {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> [u64; 2] {
+        let a = seed.next_word();
+
+        [a, seed.next_word()]
+    }
+}
+
+impl DrawSeed for [u64; 4] {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> [u64; 4] {
+        let [a, b] = <[u64; 2]>::draw(seed);
+        let [c, d] = <[u64; 2]>::draw(seed);
+
+        [a, b, c, d]
+    }
+}
+
+/// The bytes of the next two words in little-endian order, like `fill_bytes`.
+impl DrawSeed for [u8; 16] {
+    #[inline(always)]
+    fn draw(seed: &mut Seed) -> [u8; 16] {
+        let mut bytes = [0; 16];
+
+        seed.fill_bytes(&mut bytes);
+
+        bytes
+    }
+}
 
 impl Seed {
+    /// The seed which only draws zero words, e.g. `u64::from(Seed::ZERO)` is `0`.
+    ///
+    /// It is the same in every process, for tests and reproducible examples, but every word
+    /// drawn from it is zero, so its `family` and `split` are degenerate, use `Seed::fixed`
+    /// for a reproducible seed which derives distinct seeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{city, Seed};
+    ///
+    /// const SEED: Seed = Seed::ZERO;
+    ///
+    /// assert_eq!(u64::from(SEED), 0);
+    /// assert_eq!(
+    ///     city::hash64_with_seed(b"hello", SEED.into()),
+    ///     city::hash64_with_seed(b"hello", 0)
+    /// );
+    /// ```
+    pub const ZERO: Seed = Seed::from_words([0; 2]);
+
+    #[cfg(feature = "std")]
     #[inline(always)]
     fn new() -> Seed {
-        Seed(Xoroshiro128Rng::new())
+        Seed::from_words(OsRng.gen())
+    }
+
+    /// Constructs a reproducible seed from a value.
+    ///
+    /// The same value always constructs the same seed, in every process and on every platform,
+    /// so the examples and tests built on it have a stable output, unlike with `Seed::gen`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{city, Seed};
+    ///
+    /// let seed = Seed::fixed(42);
+    ///
+    /// assert_eq!(seed, Seed::fixed(42));
+    /// assert_ne!(seed, Seed::fixed(43));
+    /// assert_eq!(
+    ///     city::hash64_with_seed(b"hello", seed.into()),
+    ///     city::hash64_with_seed(b"hello", Seed::fixed(42).into())
+    /// );
+    /// ```
+    #[inline(always)]
+    pub const fn fixed(value: u64) -> Seed {
+        // never the all zero state of `Seed::ZERO`
+        Seed::from_words([value, value ^ 0x9e37_79b9_7f4a_7c15])
+    }
+
+    /// Constructs a seed from the two words of the state of its generator.
+    ///
+    /// The same words always construct the same seed, so any source of randomness,
+    /// e.g. a newer version of `rand`, can seed it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::Seed;
+    ///
+    /// assert_eq!(Seed::from_words([1, 2]), Seed::from_words([1, 2]));
+    /// assert_ne!(Seed::from_words([1, 2]), Seed::from_words([2, 1]));
+    /// ```
+    #[inline(always)]
+    pub const fn from_words(words: [u64; 2]) -> Seed {
+        Seed(SeedRngImpl::new(words))
+    }
+
+    /// Generates the next word, advancing the seed.
+    #[inline(always)]
+    pub(crate) fn next_word(&mut self) -> u64 {
+        self.0.next_word()
+    }
+
+    /// Draws a seed of a hash function from the next words, advancing the seed.
+    #[inline(always)]
+    pub(crate) fn draw<T: DrawSeed>(&mut self) -> T {
+        T::draw(self)
+    }
+
+    /// Fills the bytes with the next words in little-endian order, advancing the seed.
+    pub(crate) fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(8) {
+            let word = self.next_word().to_le_bytes();
+
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
     }
 
     /// Generate a new seed
+    ///
+    /// With the `secure` feature, the seed is also salted with a random salt of the process.
+    #[cfg(feature = "std")]
     #[inline(always)]
     pub fn gen() -> Seed {
         thread_local!(static SEEDS: RefCell<Seed> = RefCell::new(Seed::new()));
 
-        SEEDS.with(|seeds| {
-            Seed(Xoroshiro128Rng::from_seed_u64({
-                seeds.borrow_mut().0.gen::<[u64; 2]>()
-            }))
-        })
+        process_salted(SEEDS.with(|seeds| Seed::from_words(seeds.borrow_mut().draw())))
+    }
+
+    /// Mixes a salt into the seed.
+    ///
+    /// The same seed with different salts generates unrelated words,
+    /// so the hashes of two processes with their own salt are independent,
+    /// even if their per-thread generators were seeded the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{city, Seed};
+    ///
+    /// let seed = Seed::gen();
+    ///
+    /// assert_eq!(seed.salted([1, 2]), seed.salted([1, 2]));
+    /// assert_ne!(
+    ///     city::hash64_with_seed(b"hello", seed.salted([1, 2]).into()),
+    ///     city::hash64_with_seed(b"hello", seed.salted([3, 4]).into())
+    /// );
+    /// ```
+    pub fn salted(self, salt: [u64; 2]) -> Seed {
+        let [a, b] = <[u64; 2]>::from(self);
+
+        Seed::from_words([a ^ salt[0], b ^ salt[1]])
+    }
+
+    /// Generate a new seed from the OS random number generator.
+    ///
+    /// Unlike `gen`, which derives the seeds from a per-thread generator seeded once,
+    /// every call reads fresh entropy from the OS. This costs a system call per seed,
+    /// which is orders of magnitude slower than `gen`, so prefer `gen` unless the seeds
+    /// must not be correlated by a shared generator state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{Seed, city};
+    ///
+    /// city::hash128_with_seed(b"hello world", Seed::fresh().into());
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn fresh() -> Seed {
+        Seed::from_words(OsRng.gen())
+    }
+
+    /// Generate a new seed from the user provided random number generator.
+    ///
+    /// The seed only uses the generator to seed its own state,
+    /// the same generator state always produces the same seed.
+    ///
+    /// It is the same as `Seed::from_words(rng.gen())`, which takes the words
+    /// of any version of `rand`, or of any other source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{rngs::StdRng, Rng, SeedableRng};
+    ///
+    /// use fasthash::Seed;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let seed = Seed::from_words(rng.gen());
+    ///
+    /// assert_eq!(u128::from(seed), Seed::from_words(StdRng::seed_from_u64(42).gen()).into());
+    /// ```
+    #[deprecated(
+        since = "0.5.0",
+        note = "ties the API to `rand` 0.7, use `Seed::from_words(rng.gen())` instead"
+    )]
+    #[inline(always)]
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Seed {
+        Seed::from_words(rng.gen::<[u64; 2]>())
+    }
+
+    /// Derives a family of `n` independent seeds from this seed.
+    ///
+    /// The same seed always derives the same family,
+    /// which selects a family of independent hash functions, e.g. for sketches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{city, Seed};
+    ///
+    /// let seed = Seed::gen();
+    /// let family = seed.family(4);
+    ///
+    /// assert_eq!(family, seed.family(4));
+    /// assert_ne!(family[0], family[1]);
+    ///
+    /// let hashes = family
+    ///     .iter()
+    ///     .map(|&seed| city::hash64_with_seed(b"hello", seed.into()))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_ne!(hashes[0], hashes[1]);
+    /// ```
+    pub fn family(&self, n: usize) -> Vec<Seed> {
+        let mut seed = *self;
+
+        (0..n).map(|_| Seed::from_words(seed.draw())).collect()
+    }
+
+    /// Splits the seed into two independent child seeds, e.g. one
+    /// for the index hash and one for the fingerprint of a table.
+    ///
+    /// The same seed always splits into the same children, which are the first two seeds
+    /// of its `family`, so they don't leak the parent seed by their conversions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{city, Seed};
+    ///
+    /// let (index, fingerprint) = Seed::gen().split();
+    ///
+    /// assert_ne!(index, fingerprint);
+    ///
+    /// city::hash64_with_seed(b"hello", index.into());
+    /// city::hash128_with_seed(b"hello", fingerprint.into());
+    /// ```
+    #[inline(always)]
+    pub fn split(&self) -> (Seed, Seed) {
+        let [a, b, c, d] = <[u64; 4]>::from(*self);
+
+        (Seed::from_words([a, b]), Seed::from_words([c, d]))
+    }
+
+    /// Generate a native `u128` seed, the same as `u128::from(seed)`.
+    ///
+    /// The high 64 bits are the first word drawn from the seed, and the low 64 bits the second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{Seed, city};
+    ///
+    /// let seed = Seed::gen();
+    ///
+    /// assert_eq!(seed.to_u128(), u128::from(seed));
+    ///
+    /// city::hash128_with_seed(b"hello world", seed.to_u128());
+    /// ```
+    #[inline(always)]
+    pub fn to_u128(&self) -> u128 {
+        (*self).into()
+    }
+
+    /// Advances the seed in place, and returns its previous value.
+    ///
+    /// The seed reseeds itself from its own generator, so one seed yields a reproducible
+    /// stream of distinct seeds, without the per-thread generator of `gen`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fasthash::{city, Seed};
+    ///
+    /// let mut seed = Seed::gen();
+    /// let first = seed;
+    ///
+    /// assert_eq!(seed.next(), first);
+    /// assert_ne!(seed, first);
+    ///
+    /// city::hash64_with_seed(b"hello", seed.next().into());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    #[inline(always)]
+    pub fn next(&mut self) -> Seed {
+        let prev = *self;
+
+        *self = Seed::from_words(self.draw());
+
+        prev
     }
 }
 
@@ -181,29 +1083,59 @@ macro_rules! impl_from_seed {
     ($target:ty) => {
         impl From<Seed> for $target {
             #[inline(always)]
-            fn from(seed: Seed) -> $target {
-                let mut rng = seed.0;
-
-                rng.gen()
+            fn from(mut seed: Seed) -> $target {
+                seed.draw()
             }
         }
     };
 }
 
+/// Two seeds are equal if they generate the same words.
+impl PartialEq for Seed {
+    #[inline(always)]
+    fn eq(&self, other: &Seed) -> bool {
+        <[u64; 2]>::from(*self) == <[u64; 2]>::from(*other)
+    }
+}
+
+impl Eq for Seed {}
+
+/// Hashes the first two words generated by the seed,
+/// which are derived from its internal state.
+impl Hash for Seed {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <[u64; 2]>::from(*self).hash(state)
+    }
+}
+
+/// Clears the internal state of the seed, so it stops generating the words of the key.
+///
+/// `Seed` is `Copy`, so it can't implement `Drop`: it is **never** zeroed on drop,
+/// and every copy passed by value keeps its own state. Zeroize explicitly every copy
+/// holding the keying material.
+#[cfg(feature = "secure")]
+impl zeroize::Zeroize for Seed {
+    #[inline(always)]
+    fn zeroize(&mut self) {
+        self.0.clear()
+    }
+}
+
 impl_from_seed!(u32);
 impl_from_seed!(u64);
 impl_from_seed!((u64, u64));
 impl_from_seed!((u64, u64, u64, u64));
 impl_from_seed!([u64; 2]);
 impl_from_seed!([u64; 4]);
+impl_from_seed!([u8; 16]);
 impl_from_seed!((u128, u128));
 
 impl From<Seed> for u128 {
     #[inline(always)]
-    fn from(seed: Seed) -> u128 {
-        let mut rng = seed.0;
-        let hi = rng.gen::<u64>();
-        let lo = rng.gen::<u64>();
+    fn from(mut seed: Seed) -> u128 {
+        let hi = seed.next_word();
+        let lo = seed.next_word();
 
         u128::from(hi).wrapping_shl(64) + u128::from(lo)
     }
@@ -231,32 +1163,62 @@ impl From<Seed> for u128 {
 /// assert_eq!(map.insert(37, "c"), Some("b"));
 /// assert_eq!(map[&37], "c");
 /// ```
-#[derive(Clone)]
+///
+/// The hash functions also construct their `RandomState` without the generic syntax.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fasthash::city::Hash64;
+///
+/// let mut map = HashMap::with_hasher(Hash64::random_state());
+///
+/// assert_eq!(map.insert(37, "a"), None);
+/// assert_eq!(map.insert(37, "b"), Some("a"));
+/// assert_eq!(map[&37], "b");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone)]
 pub struct RandomState<T: FastHash> {
     seed: Seed,
+    hasher_seed: <T::FastHasher as FastHasher>::Seed,
     phantom: PhantomData<T>,
 }
 
+#[cfg(feature = "std")]
 impl<T: FastHash> RandomState<T> {
     /// Constructs a new `RandomState` that is initialized with random keys.
+    ///
+    /// With the `secure` feature, the keys are salted with a random salt of the process.
     #[inline(always)]
     pub fn new() -> Self {
+        RandomState::with_seed(Seed::gen())
+    }
+
+    /// Constructs a new `RandomState` that is initialized with the seed.
+    ///
+    /// The seed of the hashers is derived once here, and copied into every hasher it builds.
+    #[inline(always)]
+    pub fn with_seed(seed: Seed) -> Self {
         RandomState {
-            seed: Seed::gen(),
+            seed,
+            hasher_seed: seed.into(),
             phantom: PhantomData,
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: FastHash> BuildHasher for RandomState<T> {
     type Hasher = T::FastHasher;
 
     #[inline(always)]
     fn build_hasher(&self) -> Self::Hasher {
-        T::FastHasher::with_seed(self.seed.into())
+        T::FastHasher::with_seed(self.hasher_seed)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: FastHash> Default for RandomState<T> {
     #[inline(always)]
     fn default() -> Self {
@@ -264,10 +1226,128 @@ impl<T: FastHash> Default for RandomState<T> {
     }
 }
 
+/// Two states are equal if they were constructed with equal seeds,
+/// so they build hashers producing the same hash values.
+#[cfg(feature = "std")]
+impl<T: FastHash> PartialEq for RandomState<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.seed == other.seed
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FastHash> Eq for RandomState<T> {}
+
+/// Constructs a `RandomState` from a fixed seed, for the tests of code depending on
+/// the iteration order of a `HashMap` or a `HashSet`.
+///
+/// The iteration order of a map depends only on the hashes of its keys and the history
+/// of its insertions and removals, so two maps built with the same seed, and filled
+/// with the same operations, iterate in the same order in every run of the test.
+/// The seed is used as is, without the random salt of the `secure` feature.
+///
+/// The order still changes with the hash function, the version of the standard library
+/// and the target, so a test shouldn't compare it with a hard-coded order.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// use fasthash::{city, deterministic_state};
+///
+/// let mut a = HashSet::with_hasher(deterministic_state::<city::Hash64>(42));
+/// let mut b = HashSet::with_hasher(deterministic_state::<city::Hash64>(42));
+///
+/// a.extend(0..100);
+/// b.extend(0..100);
+///
+/// assert!(a.iter().eq(b.iter()));
+/// ```
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn deterministic_state<T: FastHash>(seed: u64) -> RandomState<T> {
+    RandomState::with_seed(Seed::fixed(seed))
+}
+
+/// `CachedState` derives the seed of the hashers once, and copies it into every hasher it builds.
+///
+/// Some algorithms (e.g. `UMASH`) derive expensive parameters from the seed, so like
+/// `RandomState`, `CachedState` pays for the derivation only at construction. It is also
+/// constructed from the derived seed itself, e.g. a `umash::Seed` with known `Params`,
+/// and doesn't need the `std` feature.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::hash::{BuildHasher, Hasher};
+///
+/// use fasthash::{city, CachedState, Seed};
+///
+/// let seed = Seed::gen();
+/// let s = CachedState::<city::Hash64>::from(seed);
+///
+/// let mut h = s.build_hasher();
+/// h.write(b"hello");
+/// assert_eq!(h.finish(), city::hash64_with_seed(b"hello", seed.into()));
+///
+/// let mut map = HashMap::with_hasher(s);
+///
+/// assert_eq!(map.insert(37, "a"), None);
+/// assert_eq!(map.insert(37, "b"), Some("a"));
+/// ```
+#[derive(Clone)]
+pub struct CachedState<T: FastHash> {
+    seed: <T::FastHasher as FastHasher>::Seed,
+    phantom: PhantomData<T>,
+}
+
+impl<T: FastHash> CachedState<T> {
+    /// Constructs a new `CachedState` that is initialized with random keys.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn new() -> Self {
+        Seed::gen().into()
+    }
+
+    /// Constructs a new `CachedState` with the derived seed of the hashers.
+    #[inline(always)]
+    pub fn with_seed(seed: <T::FastHasher as FastHasher>::Seed) -> Self {
+        CachedState {
+            seed,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: FastHash> From<Seed> for CachedState<T> {
+    #[inline(always)]
+    fn from(seed: Seed) -> Self {
+        CachedState::with_seed(seed.into())
+    }
+}
+
+impl<T: FastHash> BuildHasher for CachedState<T> {
+    type Hasher = T::FastHasher;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Self::Hasher {
+        T::FastHasher::with_seed(self.seed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FastHash> Default for CachedState<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        CachedState::new()
+    }
+}
+
 #[doc(hidden)]
 macro_rules! impl_build_hasher {
     ($hasher:ident, $hash:ident) => {
-        impl ::std::hash::BuildHasher for $hash {
+        impl ::core::hash::BuildHasher for $hash {
             type Hasher = $hasher;
 
             #[inline(always)]
@@ -279,9 +1359,20 @@ macro_rules! impl_build_hasher {
         impl $crate::hasher::BuildHasherExt for $hash {
             type FastHasher = $hasher;
         }
+
+        impl $hash {
+            /// Constructs a new `RandomState` of the hash function that is initialized with random keys.
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            pub fn random_state() -> $crate::hasher::RandomState<$hash> {
+                $crate::hasher::RandomState::new()
+            }
+        }
     };
 }
 
+/// The 128-bit result of a buffering hasher shares the cache of `finish`,
+/// so repeated `finish_ext` calls don't rehash the buffer until the next `write`.
 impl<T> HasherExt for T
 where
     T: TrivialHasher + FastHasher<Output = u128>,
@@ -346,6 +1437,45 @@ macro_rules! impl_digest {
     };
 }
 
+/// Detects the x86 CPU features at runtime, or at compile time without `std`.
+#[doc(hidden)]
+#[allow(unused_macros)]
+#[cfg(feature = "std")]
+macro_rules! has_x86_feature {
+    ($( $feature:tt ),+) => {
+        $( is_x86_feature_detected!($feature) )&&+
+    };
+}
+
+#[doc(hidden)]
+#[allow(unused_macros)]
+#[cfg(not(feature = "std"))]
+macro_rules! has_x86_feature {
+    ($( $feature:tt ),+) => {
+        $( cfg!(target_feature = $feature) )&&+
+    };
+}
+
+#[doc(hidden)]
+macro_rules! impl_write {
+    ($hasher:ident) => {
+        #[cfg(feature = "std")]
+        impl ::std::io::Write for $hasher {
+            #[inline(always)]
+            fn write(&mut self, bytes: &[u8]) -> ::std::io::Result<usize> {
+                ::core::hash::Hasher::write(self, bytes);
+
+                Ok(bytes.len())
+            }
+
+            #[inline(always)]
+            fn flush(&mut self) -> ::std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
 cfg_if! {
     if #[cfg(feature = "digest")] {
         /// The `Digest` output type
@@ -369,19 +1499,121 @@ cfg_if! {
 }
 
 pub trait TrivialHasher: FastHasher {
+    /// Hashes the buffered bytes, the result is cached until the next `write`.
     fn finalize(&self) -> Self::Output;
 }
 
+/// The native incremental state of the hash function `H`, which its buffered hasher
+/// switches to past the buffer limit.
+///
+/// The state must hash the same as `H`, and `H::hash` must be `H::hash_with_seed`
+/// with the default seed.
+#[doc(hidden)]
+pub trait SpillState<H: FastHash>: Hasher + Clone + fmt::Debug {
+    /// `false` if the hash function has no native state to switch to.
+    const NATIVE: bool = true;
+
+    /// Constructs the state hashing with the seed of `H`.
+    fn with_hash_seed(seed: H::Seed) -> Self;
+
+    /// Returns the hash value of the written bytes, the same as `H` of them.
+    fn finish_hash(&self) -> H::Hash;
+}
+
+/// The state of the buffered hashers without a native incremental state.
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub enum NoSpill {}
+
+impl Hasher for NoSpill {
+    fn finish(&self) -> u64 {
+        match *self {}
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {
+        match *self {}
+    }
+}
+
+impl<H: FastHash> SpillState<H> for NoSpill {
+    const NATIVE: bool = false;
+
+    fn with_hash_seed(_seed: H::Seed) -> Self {
+        unreachable!("no native state to spill into")
+    }
+
+    fn finish_hash(&self) -> H::Hash {
+        match *self {}
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! trivial_hasher {
     ($(#[$meta:meta])* $hasher:ident ( $hash:ident ) -> $output:ident) => {
+        trivial_hasher! {
+            $(#[$meta])*
+            $hasher($hash) -> $output, spill $crate::hasher::NoSpill
+        }
+    };
+    ($(#[$meta:meta])* $hasher:ident ( $hash:ident ) -> $output:ident, spill $state:ty) => {
         /// An implementation of `std::hash::Hasher`.
+        ///
+        /// The hash value of `finish` is cached in a `Cell` until the next `write`,
+        /// so the hasher is `Send`, but not `Sync`.
+        ///
+        /// The written bytes are buffered up to the limit set with `with_buffer_limit`,
+        /// see `BufHasher` for the hashers past the limit.
         #[derive(Clone, Debug)]
         $(#[$meta])*
         pub struct $hasher {
             seed: Option<<$hash as $crate::hasher::FastHash>::Seed>,
-            bytes: Vec<u8>,
+            bytes: ::alloc::vec::Vec<u8>,
+            cache: ::core::cell::Cell<Option<$output>>,
+            limit: usize,
+            spilled: Option<$state>,
+            guard: bool,
+            finished: ::core::cell::Cell<bool>,
+        }
+
+        impl $hasher {
+            /// Sets the limit of the buffered bytes, `DEFAULT_BUFFER_LIMIT` by default.
+            ///
+            /// Past the limit, the hasher switches to the native incremental state
+            /// of the hash function if it has one, and panics in debug builds otherwise.
+            #[inline(always)]
+            pub fn with_buffer_limit(mut self, limit: usize) -> Self {
+                self.limit = limit;
+                self
+            }
+
+            /// Panics in debug builds on a `write` after `finish` without a `reset`.
+            #[inline(always)]
+            pub fn with_finish_guard(mut self) -> Self {
+                self.guard = true;
+                self
+            }
+
+            /// Consumes the hasher, returning the seed and the buffered bytes.
+            ///
+            /// An escape hatch for the custom combiners, which re-hash the bytes
+            /// with another hash function without writing them again.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the hasher switched to its native state past the buffer limit,
+            /// since it no longer keeps the bytes.
+            #[inline(always)]
+            pub fn into_parts(
+                self,
+            ) -> (Option<<$hash as $crate::hasher::FastHash>::Seed>, ::alloc::vec::Vec<u8>) {
+                assert!(
+                    self.spilled.is_none(),
+                    "the hasher switched to its native state past the buffer limit"
+                );
+
+                (self.seed, self.bytes)
+            }
         }
 
         impl Default for $hasher {
@@ -393,15 +1625,32 @@ macro_rules! trivial_hasher {
         impl $crate::hasher::TrivialHasher for $hasher {
             #[inline(always)]
             fn finalize(&self) -> $output {
-                self.seed
-                    .map_or_else(
-                        || $hash::hash(&self.bytes),
-                        |seed| $hash::hash_with_seed(&self.bytes, seed),
-                    )
+                if cfg!(debug_assertions) && self.guard {
+                    self.finished.set(true);
+                }
+
+                if let Some(hash) = self.cache.get() {
+                    return hash;
+                }
+
+                let hash = match self.spilled {
+                    Some(ref state) => {
+                        <$state as $crate::hasher::SpillState<$hash>>::finish_hash(state)
+                    }
+                    None => self.seed
+                        .map_or_else(
+                            || $hash::hash(&self.bytes),
+                            |seed| $hash::hash_with_seed(&self.bytes, seed),
+                        ),
+                };
+
+                self.cache.set(Some(hash));
+
+                hash
             }
         }
 
-        impl ::std::hash::Hasher for $hasher {
+        impl ::core::hash::Hasher for $hasher {
             #[inline(always)]
             fn finish(&self) -> u64 {
                 use crate::hasher::TrivialHasher;
@@ -411,7 +1660,37 @@ macro_rules! trivial_hasher {
 
             #[inline(always)]
             fn write(&mut self, bytes: &[u8]) {
-                self.bytes.extend_from_slice(bytes)
+                debug_assert!(
+                    !self.finished.get(),
+                    "wrote to the hasher after finish, reset it to hash a new value"
+                );
+
+                self.cache.set(None);
+
+                if let Some(ref mut state) = self.spilled {
+                    ::core::hash::Hasher::write(state, bytes);
+                } else if self.bytes.len().saturating_add(bytes.len()) <= self.limit {
+                    self.bytes.extend_from_slice(bytes)
+                } else if <$state as $crate::hasher::SpillState<$hash>>::NATIVE {
+                    let mut state = <$state as $crate::hasher::SpillState<$hash>>::with_hash_seed(
+                        self.seed.unwrap_or_default(),
+                    );
+
+                    ::core::hash::Hasher::write(&mut state, &self.bytes);
+                    ::core::hash::Hasher::write(&mut state, bytes);
+
+                    self.bytes = ::alloc::vec::Vec::new();
+                    self.spilled = Some(state);
+                } else {
+                    if cfg!(debug_assertions) {
+                        panic!(
+                            "buffered more than {} bytes, use a streaming hasher for huge inputs",
+                            self.limit
+                        );
+                    }
+
+                    self.bytes.extend_from_slice(bytes)
+                }
             }
         }
 
@@ -430,7 +1709,7 @@ macro_rules! trivial_hasher {
             }
         }
 
-        impl ::std::convert::AsRef<[u8]> for $hasher {
+        impl ::core::convert::AsRef<[u8]> for $hasher {
             #[inline(always)]
             fn as_ref(&self) -> &[u8] {
                 &self.bytes
@@ -442,20 +1721,40 @@ macro_rules! trivial_hasher {
             fn with_capacity_and_seed(capacity: usize, seed: Option<Self::Seed>) -> Self {
                 $hasher {
                     seed,
-                    bytes: Vec::with_capacity(capacity),
+                    bytes: ::alloc::vec::Vec::with_capacity(capacity),
+                    cache: ::core::cell::Cell::new(None),
+                    limit: $crate::hasher::DEFAULT_BUFFER_LIMIT,
+                    spilled: None,
+                    guard: false,
+                    finished: ::core::cell::Cell::new(false),
                 }
             }
         }
 
+        impl $crate::hasher::ResetHasher for $hasher {
+            #[inline(always)]
+            fn reset(&mut self) {
+                self.cache.set(None);
+                self.finished.set(false);
+                self.spilled = None;
+                self.bytes.clear();
+            }
+        }
+
         impl_build_hasher!($hasher, $hash);
         impl_digest!($hasher, $output);
+        impl_write!($hasher);
     };
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
     use std::collections::HashMap;
     use std::convert::Into;
+    use std::hash::{BuildHasher, Hasher};
+    use std::io::{self, BufRead, Read};
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     use crate::*;
 
@@ -499,6 +1798,799 @@ mod tests {
         assert!(u1 != (u2 >> 64) as u64);
     }
 
+    /// A reader which returns at most `step` bytes at a time, with interruptions.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+        interrupted: bool,
+    }
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.fill_buf()?.len().min(buf.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.consume(n);
+            Ok(n)
+        }
+    }
+
+    impl<'a> BufRead for Trickle<'a> {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            self.interrupted = !self.interrupted;
+
+            if self.interrupted {
+                Err(io::ErrorKind::Interrupted.into())
+            } else {
+                Ok(&self.data[..self.step.min(self.data.len())])
+            }
+        }
+
+        fn consume(&mut self, amt: usize) {
+            self.data = &self.data[amt..];
+        }
+    }
+
+    #[derive(Default)]
+    struct Fnv(u64);
+
+    impl Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &b in bytes {
+                self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100_0000_01b3);
+            }
+        }
+    }
+
+    impl FastHasher for Fnv {
+        type Seed = u64;
+        type Output = u64;
+
+        fn with_seed(seed: u64) -> Self {
+            Fnv(seed ^ 0xcbf2_9ce4_8422_2325)
+        }
+    }
+
+    impl StreamHasher for Fnv {}
+
+    #[test]
+    fn test_write_buf_stream() {
+        use std::io::Cursor;
+
+        let data = (0..10_000u32).map(|i| i as u8).collect::<Vec<_>>();
+
+        let mut h0 = Fnv::new();
+        let mut h1 = Fnv::new();
+        let mut h2 = Fnv::new();
+
+        assert_eq!(
+            h0.write_stream(&mut Cursor::new(&data)).unwrap(),
+            data.len()
+        );
+        assert_eq!(
+            h1.write_buf_stream(&mut Cursor::new(&data)).unwrap(),
+            data.len()
+        );
+        assert_eq!(
+            h2.write_buf_stream(&mut Trickle {
+                data: &data,
+                step: 7,
+                interrupted: false,
+            })
+            .unwrap(),
+            data.len()
+        );
+
+        assert_eq!(h0.finish(), h1.finish());
+        assert_eq!(h0.finish(), h2.finish());
+    }
+
+    #[test]
+    fn test_write_stream_straddling() {
+        let data = (0..20_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        let mut expected = Fnv::new();
+        expected.write(&data);
+
+        for &step in &[1, 7, 3000, 4095, 4096, 4097, 8191, 20_000] {
+            let mut h = Fnv::new();
+
+            let n = h
+                .write_stream(&mut Trickle {
+                    data: &data,
+                    step,
+                    interrupted: false,
+                })
+                .unwrap();
+
+            assert_eq!(n, data.len(), "step {}", step);
+            assert_eq!(h.finish(), expected.finish(), "step {}", step);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn test_write_buf() {
+        use bytes::{Buf, Bytes, BytesMut};
+
+        let data = (0..10_000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        let (head, tail) = data.split_at(3000);
+
+        let mut expected = Fnv::new();
+        expected.write(&data);
+
+        let mut h = Fnv::new();
+        h.write_buf(Bytes::copy_from_slice(head).chain(BytesMut::from(tail)));
+        assert_eq!(h.finish(), expected.finish());
+
+        let mut h = Fnv::new();
+        h.write_buf(Bytes::new());
+        assert_eq!(h.finish(), Fnv::new().finish());
+    }
+
+    #[test]
+    fn test_write_usize_portable() {
+        let hash = |f: &dyn Fn(&mut Fnv)| {
+            let mut h = Fnv::new();
+            f(&mut h);
+            h.finish()
+        };
+
+        // the same value written with the 4 and 8 bytes `usize` of the 32-bit and 64-bit targets
+        for &v in &[0_u32, 1, 0x1234_5678, u32::max_value()] {
+            let wide = hash(&|h| h.write(&u64::from(v).to_le_bytes()));
+
+            assert_eq!(hash(&|h| h.write_usize_portable(v as usize)), wide);
+            assert_eq!(hash(&|h| h.write_u64(u64::from(v).to_le())), wide);
+            assert_ne!(hash(&|h| h.write(&v.to_le_bytes())), wide);
+        }
+
+        for &v in &[0_i32, -1, i32::min_value(), i32::max_value()] {
+            assert_eq!(
+                hash(&|h| h.write_isize_portable(v as isize)),
+                hash(&|h| h.write(&i64::from(v).to_le_bytes()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_stream_with_progress() {
+        let data = (0..10_000u32).map(|i| (i * 3) as u8).collect::<Vec<_>>();
+
+        let mut expected = Fnv::new();
+        expected.write_stream(&mut &data[..]).unwrap();
+
+        let mut h = Fnv::new();
+        let mut totals = Vec::new();
+
+        let n = h
+            .write_stream_with_progress(
+                &mut Trickle {
+                    data: &data,
+                    step: 3000,
+                    interrupted: false,
+                },
+                |total| totals.push(total),
+            )
+            .unwrap();
+
+        assert_eq!(n, data.len());
+        assert_eq!(h.finish(), expected.finish());
+        assert!(totals.windows(2).all(|w| w[0] < w[1]), "{:?}", totals);
+        assert_eq!(totals.last(), Some(&data.len()));
+    }
+
+    #[test]
+    fn test_write_stream_dyn() {
+        let data = (0..10_000u32).map(|i| (i * 5) as u8).collect::<Vec<_>>();
+
+        let mut expected = Fnv::new();
+        expected.write(&data);
+
+        let mut r: Box<dyn Read + '_> = Box::new(Trickle {
+            data: &data,
+            step: 3000,
+            interrupted: true,
+        });
+        let mut h = Fnv::new();
+
+        assert_eq!(h.write_stream_dyn(r.as_mut()).unwrap(), data.len());
+        assert_eq!(h.finish(), expected.finish());
+
+        let mut generic = Fnv::new();
+        generic.write_stream(&mut &data[..]).unwrap();
+
+        assert_eq!(h.finish(), generic.finish());
+    }
+
+    #[test]
+    fn test_write_stream_error() {
+        /// A reader which fails after the data.
+        struct Failing<'a>(&'a [u8]);
+
+        impl<'a> Read for Failing<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    return Err(io::ErrorKind::BrokenPipe.into());
+                }
+
+                self.0.read(buf)
+            }
+        }
+
+        let data = [0x5a_u8; 5000];
+        let mut h = Fnv::new();
+        let mut expected = Fnv::new();
+
+        expected.write(&data);
+
+        assert_eq!(
+            h.write_stream(&mut Failing(&data)).unwrap_err().kind(),
+            io::ErrorKind::BrokenPipe
+        );
+        assert_eq!(h.finish(), expected.finish());
+    }
+
+    #[test]
+    fn test_seed_hash() {
+        use std::collections::HashSet;
+
+        let s = Seed::gen();
+        let mut seeds = HashSet::new();
+
+        assert!(seeds.insert(s));
+        assert!(!seeds.insert(s));
+        assert!(seeds.insert(Seed::gen()));
+        assert!(seeds.insert(Seed::gen()));
+        assert_eq!(seeds.len(), 3);
+        assert!(seeds.contains(&s));
+    }
+
+    #[test]
+    fn test_fresh_seed() {
+        let seeds = (0..64).map(|_| Seed::fresh().into()).collect::<Vec<u128>>();
+
+        for (i, a) in seeds.iter().enumerate() {
+            for b in &seeds[i + 1..] {
+                assert_ne!(a, b);
+                // roughly half of the bits should differ
+                assert!((a ^ b).count_ones() > 16);
+            }
+        }
+
+        let deltas = seeds
+            .windows(2)
+            .map(|w| w[1].wrapping_sub(w[0]))
+            .collect::<Vec<_>>();
+
+        assert!(deltas.windows(2).any(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_seed_tuple_order() {
+        let seed = Seed::gen();
+        let mut next = seed;
+        let words = [
+            next.next_word(),
+            next.next_word(),
+            next.next_word(),
+            next.next_word(),
+        ];
+
+        assert_eq!(u64::from(seed), words[0]);
+        assert_eq!(
+            u128::from(seed),
+            u128::from(words[0]) << 64 | u128::from(words[1])
+        );
+        assert_eq!(<(u64, u64)>::from(seed), (words[0], words[1]));
+        assert_eq!(<[u64; 2]>::from(seed), [words[0], words[1]]);
+        assert_eq!(
+            <(u64, u64, u64, u64)>::from(seed),
+            (words[0], words[1], words[2], words[3])
+        );
+        assert_eq!(<[u64; 4]>::from(seed), words);
+
+        let (a, b) = <(u64, u64)>::from(seed);
+        let bytes = <[u8; 16]>::from(seed);
+
+        assert_eq!(bytes[..8], a.to_le_bytes());
+        assert_eq!(bytes[8..], b.to_le_bytes());
+    }
+
+    #[cfg(feature = "xx")]
+    #[test]
+    fn test_salted_state() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        // two processes whose per-thread generators were seeded the same
+        let seed = Seed::from_words(StdRng::seed_from_u64(42).gen());
+        let a = RandomState::<xx::Hash64>::with_seed(seed.salted([1, 2]));
+        let b = RandomState::<xx::Hash64>::with_seed(seed.salted([3, 4]));
+        let c = RandomState::<xx::Hash64>::with_seed(seed.salted([1, 2]));
+
+        let hash = |s: &RandomState<xx::Hash64>, key: &[u8]| {
+            let mut h = s.build_hasher();
+            h.write(key);
+            h.finish()
+        };
+
+        let keys: [&[u8]; 5] = [b"a", b"aa", b"aaaa", &[0; 64], b""];
+
+        for key in keys.iter() {
+            assert_ne!(hash(&a, key), hash(&b, key));
+            assert_eq!(hash(&a, key), hash(&c, key));
+        }
+
+        #[cfg(feature = "secure")]
+        {
+            let mut map = crate::collections::HashMap::default();
+
+            assert_eq!(map.insert(37, "a"), None);
+            assert_eq!(map[&37], "a");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "secure")]
+    fn test_seed_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut seed = Seed::from_words([0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210]);
+
+        let copy = seed;
+
+        seed.zeroize();
+
+        // a zeroed generator only generates zeros
+        assert_eq!(<[u64; 4]>::from(seed), [0; 4]);
+        assert_eq!(seed, Seed::ZERO);
+
+        // the copies keep their state
+        assert_ne!(copy, seed);
+    }
+
+    #[test]
+    fn test_seed_to_u128() {
+        let seed = Seed::gen();
+        let [hi, lo] = <[u64; 2]>::from(seed);
+
+        assert_eq!(seed.to_u128(), u128::from(hi) << 64 | u128::from(lo));
+        assert_eq!(seed.to_u128(), u128::from(seed));
+        assert_eq!((seed.to_u128() >> 64) as u64, u64::from(seed));
+    }
+
+    #[test]
+    fn test_seed_rng() {
+        use rand::RngCore;
+        use xoroshiro128::Xoroshiro128Rng;
+
+        // the words are the same as drawn from the generator before it was hidden
+        let mut seed = Seed::from_words([1, 2]);
+        let mut rng = Xoroshiro128Rng::from_seed_u64([1, 2]);
+
+        for _ in 0..100 {
+            assert_eq!(seed.next_word(), rng.next_u64());
+        }
+
+        let mut bytes = [0; 20];
+        let mut words = [0; 24];
+
+        Seed::from_words([1, 2]).fill_bytes(&mut bytes);
+        Xoroshiro128Rng::from_seed_u64([1, 2]).fill_bytes(&mut words);
+        assert_eq!(bytes, words[..20]);
+
+        let (a, b): (u128, u128) = Seed::from_words([1, 2]).into();
+        let mut rng = Xoroshiro128Rng::from_seed_u64([1, 2]);
+        let words = [
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+        ];
+
+        assert_eq!(a, u128::from(words[1]) << 64 | u128::from(words[0]));
+        assert_eq!(b, u128::from(words[3]) << 64 | u128::from(words[2]));
+    }
+
+    #[test]
+    fn test_seed_quality() {
+        // every bit of the generated seeds is set for about half of the seeds
+        let mut counts = [0_usize; 64];
+
+        for _ in 0..1000 {
+            let word = u64::from(Seed::gen());
+
+            for (bit, count) in counts.iter_mut().enumerate() {
+                *count += (word >> bit & 1) as usize;
+            }
+        }
+
+        for (bit, &count) in counts.iter().enumerate() {
+            assert!(
+                (400..600).contains(&count),
+                "bit {} is set {} / 1000",
+                bit,
+                count
+            );
+        }
+    }
+
+    #[cfg(feature = "city")]
+    #[test]
+    fn test_random_state_eq() {
+        let seed = Seed::gen();
+        let s = RandomState::<city::Hash64>::with_seed(seed);
+
+        assert!(s == RandomState::with_seed(seed));
+        assert!(s == s.clone());
+        assert!(RandomState::<city::Hash64>::new() != RandomState::new());
+
+        let mut h = s.build_hasher();
+        let mut h2 = RandomState::<city::Hash64>::with_seed(seed).build_hasher();
+
+        h.write(b"hello");
+        h2.write(b"hello");
+        assert_eq!(h.finish(), h2.finish());
+    }
+
+    #[cfg(feature = "city")]
+    #[test]
+    fn test_deterministic_state() {
+        let keys = (0..1000u32)
+            .map(|i| i.wrapping_mul(2_654_435_761))
+            .collect::<Vec<_>>();
+        let fill = |seed| {
+            let mut map = HashMap::with_hasher(deterministic_state::<city::Hash64>(seed));
+
+            map.extend(keys.iter().map(|&k| (k, ())));
+            map.remove(&keys[10]);
+            map.insert(keys[10], ());
+            map.keys().copied().collect::<Vec<_>>()
+        };
+
+        assert_eq!(fill(42), fill(42));
+        assert_eq!(fill(42).len(), keys.len());
+        assert!(deterministic_state::<city::Hash64>(42) == deterministic_state(42));
+        assert!(deterministic_state::<city::Hash64>(42) != deterministic_state(43));
+    }
+
+    #[test]
+    fn test_seed_next() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let start = Seed::from_words(StdRng::seed_from_u64(42).gen());
+        let pull = |mut seed: Seed| (0..50).map(|_| u128::from(seed.next())).collect::<Vec<_>>();
+        let seeds = pull(start);
+
+        assert_eq!(seeds, pull(start));
+        assert_eq!(seeds[0], u128::from(start));
+        assert_eq!(
+            seeds.iter().collect::<std::collections::HashSet<_>>().len(),
+            50
+        );
+    }
+
+    #[test]
+    fn test_seed_split() {
+        let seed = Seed::from_words([1, 2]);
+        let (a, b) = seed.split();
+
+        assert_eq!(seed.split(), (a, b));
+        assert_eq!(seed.family(2), [a, b]);
+        assert_ne!(a, b);
+        assert_ne!(a, seed);
+        assert_ne!(b, seed);
+
+        for child in &[a, b] {
+            assert_ne!(u64::from(*child), u64::from(seed));
+            assert_ne!(u128::from(*child), u128::from(seed));
+            assert_ne!(<[u64; 4]>::from(*child), <[u64; 4]>::from(seed));
+        }
+
+        assert_ne!(Seed::from_words([2, 1]).split(), (a, b));
+    }
+
+    #[test]
+    fn test_seed_zero_and_fixed() {
+        assert_eq!(u64::from(Seed::ZERO), 0);
+        assert_eq!(u128::from(Seed::ZERO), 0);
+        assert_eq!(<[u64; 4]>::from(Seed::ZERO), [0; 4]);
+
+        let (a, b) = Seed::ZERO.split();
+
+        assert_eq!(a, b);
+
+        #[cfg(feature = "city")]
+        assert_eq!(
+            city::hash64_with_seed(b"x", Seed::ZERO.into()),
+            10698467147189502074
+        );
+
+        // both are usable in a const context
+        const FIXED: Seed = Seed::fixed(42);
+
+        assert_eq!(FIXED, Seed::fixed(42));
+        assert_eq!(u128::from(Seed::fixed(42)), u128::from(Seed::fixed(42)));
+        assert_ne!(Seed::fixed(42), Seed::fixed(43));
+        assert_ne!(Seed::fixed(0), Seed::ZERO);
+
+        let (a, b) = Seed::fixed(0).split();
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_seed_from_rng() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let u0: u128 = Seed::from_rng(&mut StdRng::seed_from_u64(42)).into();
+        let u1: u128 = Seed::from_rng(&mut StdRng::seed_from_u64(42)).into();
+        let u2: u128 = Seed::from_rng(&mut StdRng::seed_from_u64(43)).into();
+
+        assert_eq!(u0, u1);
+        assert_ne!(u0, u2);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let u3: u128 = Seed::from_rng(&mut rng).into();
+        let u4: u128 = Seed::from_rng(&mut rng).into();
+
+        assert_eq!(u0, u3);
+        assert_ne!(u3, u4);
+        assert!((u3 ^ u4).count_ones() > 16);
+
+        // the same as the words of the generator
+        let u5: u128 = Seed::from_words(StdRng::seed_from_u64(42).gen()).into();
+
+        assert_eq!(u0, u5);
+    }
+
+    thread_local! {
+        // per test thread, so the tests running in parallel don't count each other's calls
+        static HASH_CALLS: Cell<usize> = Cell::new(0);
+        static HASH128_CALLS: Cell<usize> = Cell::new(0);
+    }
+
+    #[derive(Clone, Default)]
+    struct CountingHash;
+
+    impl FastHash for CountingHash {
+        type Hash = u64;
+        type Seed = u64;
+
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
+            HASH_CALLS.with(|calls| calls.set(calls.get() + 1));
+
+            bytes.as_ref().iter().fold(seed, |h, &b| {
+                h.wrapping_mul(31).wrapping_add(u64::from(b))
+            })
+        }
+    }
+
+    trivial_hasher! {
+        CountingHasher(CountingHash) -> u64
+    }
+
+    #[derive(Clone, Default)]
+    struct LimitedHash;
+
+    impl FastHash for LimitedHash {
+        type Hash = u64;
+        type Seed = u64;
+
+        const MAX_INPUT_LEN: usize = 8;
+
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
+            bytes.as_ref().iter().fold(seed, |h, &b| {
+                h.wrapping_mul(31).wrapping_add(u64::from(b))
+            })
+        }
+    }
+
+    trivial_hasher! {
+        LimitedHasher(LimitedHash) -> u64
+    }
+
+    #[test]
+    fn test_try_hash() {
+        let key = b"12345678";
+
+        assert_eq!(LimitedHash::try_hash(key), Ok(LimitedHash::hash(key)));
+        assert_eq!(
+            LimitedHash::try_hash_with_seed(b"", 123),
+            Ok(LimitedHash::hash_with_seed(b"", 123))
+        );
+
+        let err = LimitedHash::try_hash(b"123456789").unwrap_err();
+
+        assert_eq!(err.input_len(), 9);
+        assert_eq!(err.max_input_len(), 8);
+        assert_eq!(
+            err.to_string(),
+            "the input of 9 bytes is longer than the 8 bytes the hash function handles"
+        );
+        assert!(LimitedHash::try_hash_with_seed(b"123456789", 123).is_err());
+
+        assert_eq!(CountingHash::MAX_INPUT_LEN, usize::MAX);
+        #[cfg(feature = "murmur")]
+        assert_eq!(murmur2::Hash64_x64::MAX_INPUT_LEN, i32::MAX as usize);
+    }
+
+    #[test]
+    fn test_finish_memoized() {
+        let mut h = CountingHasher::new();
+
+        h.write(b"hello");
+
+        let calls = HASH_CALLS.with(Cell::get);
+        let hash = h.finish();
+
+        assert_eq!(h.finish(), hash);
+        assert_eq!(HASH_CALLS.with(Cell::get), calls + 1);
+
+        h.write(b"world");
+
+        assert_ne!(h.finish(), hash);
+        assert_eq!(h.finish(), CountingHash::hash(b"helloworld"));
+        assert_eq!(HASH_CALLS.with(Cell::get), calls + 3);
+    }
+
+    #[derive(Clone, Default)]
+    struct CountingHash128;
+
+    impl FastHash for CountingHash128 {
+        type Hash = u128;
+        type Seed = u64;
+
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u128 {
+            HASH128_CALLS.with(|calls| calls.set(calls.get() + 1));
+
+            bytes.as_ref().iter().fold(u128::from(seed), |h, &b| {
+                h.wrapping_mul(31).wrapping_add(u128::from(b))
+            })
+        }
+    }
+
+    trivial_hasher! {
+        CountingHasher128(CountingHash128) -> u128
+    }
+
+    #[test]
+    fn test_digest128_bytes() {
+        for &v in &[0, 1, u128::MAX, 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210] {
+            let digest = Digest128::from(v);
+            let bytes: [u8; 16] = digest.into();
+
+            assert_eq!(bytes, v.to_le_bytes());
+            assert_eq!(Digest128::from(bytes), digest);
+            assert_eq!(u128::from(Digest128::from(bytes)), v);
+        }
+    }
+
+    #[test]
+    fn test_eq_128() {
+        let v = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210_u128;
+        let digest = Digest128(v);
+
+        assert_eq!(digest.low64(), 0xfedc_ba98_7654_3210);
+        assert_eq!(digest.high64(), 0x0123_4567_89ab_cdef);
+        assert_eq!(
+            Digest128::from_halves(digest.low64(), digest.high64()),
+            digest
+        );
+
+        assert!(eq_128(v, digest.low64(), digest.high64()));
+        assert!(!eq_128(v, digest.high64(), digest.low64()));
+        assert!(!eq_128(v, digest.low64() ^ 1, digest.high64()));
+        assert!(eq_128(0, 0, 0));
+        assert!(eq_128(u128::MAX, u64::MAX, u64::MAX));
+
+        // the low half is the first 8 bytes of the little-endian bytes
+        let bytes: [u8; 16] = digest.into();
+
+        assert_eq!(bytes[..8], digest.low64().to_le_bytes());
+        assert_eq!(bytes[8..], digest.high64().to_le_bytes());
+    }
+
+    #[test]
+    fn test_finish_ext_memoized() {
+        let mut h = CountingHasher128::new();
+
+        h.write(b"hello");
+
+        let calls = HASH128_CALLS.with(Cell::get);
+        let hash = h.finish_ext();
+
+        assert_eq!(h.finish_ext(), hash);
+        assert_eq!(h.finish(), hash as u64);
+        assert_eq!(HASH128_CALLS.with(Cell::get), calls + 1);
+
+        h.write(b"world");
+
+        assert_ne!(h.finish_ext(), hash);
+        assert_eq!(h.finish_ext(), CountingHash128::hash(b"helloworld"));
+        assert_eq!(HASH128_CALLS.with(Cell::get), calls + 3);
+    }
+
+    static SETUP_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Copy, Default)]
+    struct DerivedSeed(u64);
+
+    impl From<Seed> for DerivedSeed {
+        fn from(seed: Seed) -> Self {
+            SETUP_CALLS.fetch_add(1, Ordering::SeqCst);
+
+            DerivedSeed(seed.into())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct DerivedHash;
+
+    impl FastHash for DerivedHash {
+        type Hash = u64;
+        type Seed = DerivedSeed;
+
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: DerivedSeed) -> u64 {
+            bytes.as_ref().iter().fold(seed.0, |h, &b| {
+                h.wrapping_mul(31).wrapping_add(u64::from(b))
+            })
+        }
+    }
+
+    trivial_hasher! {
+        DerivedHasher(DerivedHash) -> u64
+    }
+
+    #[test]
+    fn test_cached_state() {
+        let seed = Seed::gen();
+        let calls = SETUP_CALLS.load(Ordering::SeqCst);
+        let s = CachedState::<DerivedHash>::from(seed);
+
+        assert_eq!(SETUP_CALLS.load(Ordering::SeqCst), calls + 1);
+
+        let items: [&[u8]; 3] = [b"hello", b"world", b""];
+
+        for item in items.iter() {
+            let mut h = s.build_hasher();
+            h.write(item);
+
+            assert_eq!(h.finish(), DerivedHash::hash_with_seed(item, seed.into()));
+        }
+
+        // only the uncached path in the loop above derived the seed again
+        assert_eq!(SETUP_CALLS.load(Ordering::SeqCst), calls + 1 + items.len());
+
+        let mut map = HashMap::with_hasher(s);
+
+        map.extend((0..100).map(|i| (i, i)));
+
+        assert_eq!(map[&37], 37);
+        assert_eq!(SETUP_CALLS.load(Ordering::SeqCst), calls + 1 + items.len());
+
+        // `RandomState` caches the derived seed too
+        let calls = SETUP_CALLS.load(Ordering::SeqCst);
+        let s = RandomState::<DerivedHash>::with_seed(seed);
+        let mut map = HashMap::with_hasher(s.clone());
+
+        map.extend((0..100).map(|i| (i, i)));
+
+        assert_eq!(map[&37], 37);
+
+        let mut h = s.build_hasher();
+        h.write(b"hello");
+
+        assert_eq!(
+            h.finish(),
+            DerivedHash::hash_with_seed(b"hello", seed.into())
+        );
+        assert_eq!(SETUP_CALLS.load(Ordering::SeqCst), calls + 2);
+    }
+
     macro_rules! test_hashmap_with_fixed_state {
         ($hash:path) => {
             let mut map = HashMap::with_hasher($hash);
@@ -536,73 +2628,243 @@ mod tests {
     }
 
     #[test]
-    fn test_hashmap_with_hashers() {
-        #[cfg(feature = "city")]
-        test_hashmap_with_hashers![city::Hash32, city::Hash64, city::Hash128];
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "buffered more than 8 bytes")]
+    fn test_buffer_limit() {
+        let mut h = CountingHasher::new().with_buffer_limit(8);
 
-        #[cfg(all(feature = "city", any(feature = "sse42", target_feature = "sse4.2")))]
-        test_hashmap_with_hashers![city::crc::Hash128];
+        h.write(b"hello");
+        h.write(b"wor");
+        assert_eq!(h.len(), 8);
 
-        #[cfg(feature = "farm")]
-        test_hashmap_with_hashers![farm::Hash32, farm::Hash64, farm::Hash128];
+        h.write(b"ld");
+    }
 
-        #[cfg(feature = "lookup")]
-        test_hashmap_with_hashers![lookup3::Hash32];
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "wrote to the hasher after finish")]
+    fn test_finish_guard() {
+        let mut h = CountingHasher::new().with_finish_guard();
+
+        h.write(b"hello");
+        h.finish();
+        h.write(b"world");
+    }
 
-        #[cfg(feature = "metro")]
-        test_hashmap_with_hashers![
-            metro::Hash64_1,
-            metro::Hash64_2,
-            metro::Hash128_1,
-            metro::Hash128_2
-        ];
+    #[test]
+    fn test_finish_guard_reset() {
+        let mut h = CountingHasher::new().with_finish_guard();
 
-        #[cfg(feature = "metro")]
-        #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
-        test_hashmap_with_hashers![
-            metro::crc::Hash64_1,
-            metro::crc::Hash64_2,
-            metro::crc::Hash128_1,
-            metro::crc::Hash128_2
-        ];
+        h.write(b"hello");
+        let hash = h.finish();
 
-        #[cfg(feature = "mum")]
-        test_hashmap_with_hashers![mum::Hash64];
+        h.reset();
+        h.write(b"hello");
+        assert_eq!(h.finish(), hash);
 
-        #[cfg(feature = "murmur")]
-        test_hashmap_with_hashers![
-            murmur::Hash32,
-            murmur::Hash32Aligned,
-            murmur2::Hash32,
-            murmur2::Hash32A,
-            murmur2::Hash32Neutral,
-            murmur2::Hash32Aligned,
-            murmur2::Hash64_x64,
-            murmur2::Hash64_x86,
-            murmur3::Hash32,
-            murmur3::Hash128_x86,
-            murmur3::Hash128_x64
-        ];
+        // without the guard, writing after finish keeps appending
+        let mut h = CountingHasher::new();
 
-        #[cfg(feature = "seahash")]
-        test_hashmap_with_hashers![sea::Hash64];
+        h.write(b"hello");
+        h.finish();
+        h.write(b"world");
+    }
 
+    #[test]
+    fn test_snapshot() {
+        fn check<H: FastHasher + HasherExt, F: FastHash<Hash = u128>>() {
+            let data = (0..1000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+            let mut h = H::new();
+            let mut len = 0;
+
+            for &step in &[0, 1, 3, 16, 100, 880] {
+                h.write(&data[len..len + step]);
+                len += step;
+
+                assert_eq!(h.snapshot(), F::hash(&data[..len]), "{} bytes", len);
+                assert_eq!(h.snapshot(), h.finish_ext());
+            }
+        }
+
+        #[cfg(feature = "city")]
+        check::<city::Hasher128, city::Hash128>();
         #[cfg(feature = "spooky")]
-        test_hashmap_with_hashers![spooky::Hash32, spooky::Hash64, spooky::Hash128];
-
-        #[cfg(feature = "t1ha")]
-        test_hashmap_with_hashers![
-            t1ha0::Hash64,
-            t1ha1::Hash64Le,
-            t1ha1::Hash64Be,
-            t1ha2::Hash64AtOnce,
-            t1ha2::Hash128AtOnce
-        ];
+        check::<spooky::Hasher128, spooky::Hash128>();
+        #[cfg(feature = "xx")]
+        check::<xxh3::Hasher128, xxh3::Hash128>();
+    }
+
+    #[cfg(feature = "murmur")]
+    #[test]
+    fn test_into_parts() {
+        let mut h = murmur3::Hasher32::with_seed(123);
+
+        h.write(b"hello");
+        h.write(b"world");
+
+        let hash = h.finish();
+        let (seed, bytes) = h.into_parts();
+
+        assert_eq!(seed, Some(123));
+        assert_eq!(bytes, b"helloworld");
+        assert_eq!(u64::from(murmur3::hash32_with_seed(&bytes, 123)), hash);
+        assert_eq!(
+            murmur3::Hash128_x64::hash_with_seed(&bytes, seed.unwrap()),
+            murmur3::hash128_x64_with_seed(b"helloworld", 123)
+        );
+
+        assert_eq!(murmur3::Hasher32::new().into_parts(), (None, vec![]));
+    }
+
+    #[test]
+    fn test_reset_with_io_copy() {
+        use std::io::Cursor;
+
+        macro_rules! test_reset {
+            ($hasher:ty, $hash:path, $seed:expr) => {
+                let items: [&[u8]; 3] = [b"hello", &[0x5a; 5000], b""];
+                let mut h = <$hasher>::with_seed($seed);
+
+                for item in items.iter() {
+                    let n = io::copy(&mut Cursor::new(item), &mut h).unwrap();
+
+                    assert_eq!(n as usize, item.len());
+                    assert_eq!(h.finish(), $hash(item, $seed) as u64);
 
+                    h.reset();
+                }
+            };
+        }
+
+        #[cfg(feature = "city")]
+        {
+            test_reset!(city::Hasher64, city::hash64_with_seed, 123);
+        }
         #[cfg(feature = "xx")]
-        test_hashmap_with_hashers![xx::Hash32, xx::Hash64];
+        {
+            test_reset!(xxh3::Hasher64, xxh3::hash64_with_seed, 123);
+            test_reset!(xxh3::Hasher128, xxh3::hash128_with_seed, 0);
+        }
+    }
+
+    macro_rules! each_hash {
+        ($test:ident, $( #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path $(as $variant:ident)?, )*) => {
+            $(
+                #[cfg($cfg)]
+                $test![$hash];
+            )*
+        };
+    }
+
+    // runs the test with every registered hash function
+    macro_rules! for_each_hash {
+        ($test:ident) => {
+            fast_hashes!(each_hash, $test)
+        };
+    }
+
+    #[test]
+    fn test_hashmap_with_hashers() {
+        for_each_hash!(test_hashmap_with_hashers);
+    }
+
+    macro_rules! test_clone_with_hashers {
+        [ $( $hash:path ),* ] => {
+            $( {
+                let prefix = [0x5a_u8; 300];
+                let mut h = $hash.build_hasher();
+
+                h.write(&prefix);
+
+                let mut forked = h.clone();
 
-        #[cfg(feature = "ahash")]
-        test_hashmap_with_hashers![ahash::Hash64]
+                h.write(b"hello");
+                forked.write(b"world");
+
+                let mut expected = $hash.build_hasher();
+                expected.write(&[&prefix[..], b"hello"].concat());
+
+                let mut forked_expected = $hash.build_hasher();
+                forked_expected.write(&[&prefix[..], b"world"].concat());
+
+                assert_eq!(h.finish(), expected.finish(), "{}", stringify!($hash));
+                assert_eq!(forked.finish(), forked_expected.finish(), "{}", stringify!($hash));
+                assert_ne!(h.finish(), forked.finish(), "{}", stringify!($hash));
+
+                drop(h);
+
+                // the clone outlives the original
+                forked.write(b"!");
+                forked_expected.write(b"!");
+                assert_eq!(forked.finish(), forked_expected.finish(), "{}", stringify!($hash));
+            } )*
+        }
+    }
+
+    #[test]
+    fn test_clone_with_hashers() {
+        for_each_hash!(test_clone_with_hashers);
+    }
+
+    fn assert_signed_same_bits<S: BuildHasher>(s: &S, name: &str) {
+        let hash = |write: &dyn Fn(&mut S::Hasher)| {
+            let mut h = s.build_hasher();
+            write(&mut h);
+            h.finish()
+        };
+
+        assert_eq!(
+            hash(&|h| h.write_i8(-1)),
+            hash(&|h| h.write_u8(u8::MAX)),
+            "{}",
+            name
+        );
+        assert_eq!(
+            hash(&|h| h.write_i16(-1)),
+            hash(&|h| h.write_u16(u16::MAX)),
+            "{}",
+            name
+        );
+        assert_eq!(
+            hash(&|h| h.write_i32(-1)),
+            hash(&|h| h.write_u32(u32::MAX)),
+            "{}",
+            name
+        );
+        assert_eq!(
+            hash(&|h| h.write_i64(-1)),
+            hash(&|h| h.write_u64(u64::MAX)),
+            "{}",
+            name
+        );
+        assert_eq!(
+            hash(&|h| h.write_i128(-1)),
+            hash(&|h| h.write_u128(u128::MAX)),
+            "{}",
+            name
+        );
+        assert_eq!(
+            hash(&|h| h.write_isize(-1)),
+            hash(&|h| h.write_usize(usize::MAX)),
+            "{}",
+            name
+        );
+        assert_eq!(
+            hash(&|h| h.write_i64(i64::MIN)),
+            hash(&|h| h.write_u64(1 << 63)),
+            "{}",
+            name
+        );
+    }
+
+    macro_rules! test_signed_with_hashers {
+        [ $( $hash:path ),* ] => {
+            $( assert_signed_same_bits(&$hash, stringify!($hash)); )*
+        }
+    }
+
+    #[test]
+    fn test_signed_with_hashers() {
+        for_each_hash!(test_signed_with_hashers);
     }
 }
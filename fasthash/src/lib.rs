@@ -5,7 +5,7 @@
 //! ```
 //! use std::hash::{Hash, Hasher};
 //!
-//! use fasthash::{metro, MetroHasher};
+//! use fasthash::{metro, FastHash, MetroHasher};
 //!
 //! fn hash<T: Hash>(t: &T) -> u64 {
 //!     let mut s: MetroHasher = Default::default();
@@ -13,7 +13,7 @@
 //!     s.finish()
 //! }
 //!
-//! let h = metro::hash64(b"hello world\xff");
+//! let h = metro::Hash64_1::hash(b"hello world\xff");
 //!
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
@@ -52,8 +52,49 @@
 //! assert_eq!(map.insert(37, "c"), Some("b"));
 //! assert_eq!(map[&37], "c");
 //! ```
+//!
+//! # Empty input
+//!
+//! Hashing an empty input is always well-defined, and returns the seed-dependent
+//! empty hash of the algorithm, e.g. `0xdeadbeef + seed` for `lookup3`,
+//! or `0` for the `MurmurHash` family with the default seed.
+//! Writing an empty slice to a hasher doesn't change its result.
+//!
+//! ```
+//! use fasthash::{xx, FastHash};
+//!
+//! assert_eq!(xx::hash64(b""), 17241709254077376921);
+//! assert_eq!(xx::Hash64::hash(Vec::<u8>::new()), xx::hash64(b""));
+//! ```
+//!
+//! The native functions never receive a null pointer, since a Rust slice is never null,
+//! even if it is empty.
+//!
+//! # Integers
+//!
+//! The hashers don't override the integer writes of `Hasher`, so a signed integer hashes
+//! the same as the unsigned integer of the same bits in native byte order,
+//! e.g. `write_i64(-1)` is the same as `write_u64(u64::MAX)`.
+//!
+//! # `no_std`
+//!
+//! Without the default `std` feature, the crate only depends on `core` and `alloc`.
+//! The hash functions and hashers are still available, but `RandomState`, `StreamHasher`,
+//! `Seed::gen` and the `any` module need `std`, and the CPU features are detected
+//! at compile time instead of runtime. The CI builds the pure Rust hash functions
+//! for `thumbv7em-none-eabi`, a target without `std`.
+//!
+//! # `secure`
+//!
+//! The fast hash functions are vulnerable to `HashDoS` attacks with adversarial keys.
+//! The `secure` feature salts every `Seed::gen`, and so every `RandomState::new`,
+//! with a random salt of the process, and switches the default state of the
+//! `collections` aliases to the keyed `UMASH`, and `Seed` implements `zeroize::Zeroize`
+//! to clear the keying material.
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
 #[macro_use]
 extern crate cfg_if;
 extern crate fasthash_sys as ffi;
@@ -66,24 +107,84 @@ cfg_if! {
     }
 }
 
+#[macro_use]
+mod registry;
 #[macro_use]
 mod hasher;
 
 pub use crate::hasher::{
-    BufHasher, FastHash, FastHasher, Fingerprint, HasherExt, RandomState, Seed, StreamHasher,
+    eq_128, hash128_generic, BufHasher, CachedState, Digest128, FastHash, FastHasher, Fingerprint,
+    HashError, HasherExt, HasherWriteExt, ResetHasher, Seed, DEFAULT_BUFFER_LIMIT,
 };
 
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        pub use crate::hasher::{deterministic_state, RandomState, StreamHasher};
+    }
+}
+
+mod combinator;
+mod framed;
+mod stack;
+
+pub mod crc;
+
+pub use crate::combinator::{
+    ChecksumHasher, CountingHasher, DualSeed, DualStream, FinalMix, FmtHasher, MultiWidth,
+    PepperedHasher,
+};
+pub use crate::framed::{hash_tuple, hash_tuple_with_seed, Framed};
+pub use crate::stack::{CapacityError, StackHasher};
+
+cfg_if! {
+    if #[cfg(feature = "bytemuck")] {
+        mod pod;
+
+        pub use crate::pod::{hash_pod, hash_pod_with_seed};
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        pub use crate::combinator::{BoundedStreamHasher, TimedStream};
+    }
+}
+
+pub mod diagnostics;
+pub mod minhash;
+pub mod rolling;
+
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        pub mod any;
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        pub mod collections;
+    }
+}
+
+cfg_if! {
+    if #[cfg(all(feature = "std", feature = "serde"))] {
+        pub mod serialized;
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "city")] {
         pub mod city;
 
-        cfg_if! {
-            if #[cfg(any(feature = "sse42", target_feature = "sse4.2"))] {
-                pub use crate::city::{Hasher64 as CityHasher, crc::Hasher128 as CityHasherExt};
-            } else {
-                pub use city::{Hasher128 as CityHasherExt, Hasher64 as CityHasher};
-            }
-        }
+        pub use crate::city::{crc::Hasher128 as CityHasherExt, Hasher64 as CityHasher};
+    }
+}
+
+cfg_if! {
+    if #[cfg(all(feature = "clhash", target_arch = "x86_64"))] {
+        pub mod clhash;
+
+        pub use crate::clhash::Hasher64 as CLHasher;
     }
 }
 
@@ -95,6 +196,12 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(all(feature = "city", feature = "farm"))] {
+        pub mod smart;
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "lookup3")] {
         pub mod lookup3;
@@ -113,13 +220,7 @@ cfg_if! {
     if #[cfg(feature = "metro")] {
         pub mod metro;
 
-        cfg_if! {
-            if #[cfg(any(feature = "sse42", target_feature = "sse4.2"))] {
-                pub use crate::metro::{crc::Hasher128_1 as MetroHasherExt, crc::Hasher64_1 as MetroHasher};
-            } else {
-                pub use metro::{Hasher128_1 as MetroHasherExt, Hasher64_1 as MetroHasher};
-            }
-        }
+        pub use crate::metro::{Hasher128_1 as MetroHasherExt, Hasher64_1 as MetroHasher};
     }
 }
 
@@ -152,6 +253,22 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(feature = "nmhash")] {
+        pub mod nmhash;
+
+        pub use crate::nmhash::Hasher32 as NmHasher;
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "siphash")] {
+        pub mod siphash;
+
+        pub use crate::siphash::{SipHasher13, SipHasher24};
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "spooky")] {
         pub mod spooky;
@@ -185,6 +302,14 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(feature = "pengy")] {
+        pub mod pengy;
+
+        pub use crate::pengy::Hasher64 as PengyHasher;
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "seahash")] {
         pub mod sea;
@@ -194,6 +319,14 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(feature = "umash")] {
+        pub mod umash;
+
+        pub use crate::umash::{Hasher128 as UmashHasherExt, Hasher64 as UmashHasher};
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "wy")] {
         pub mod wy;
@@ -206,6 +339,7 @@ cfg_if! {
     if #[cfg(feature = "xx")] {
         pub mod xx;
         pub mod xxh3;
+        pub mod ring;
 
         pub use crate::xx::Hasher64 as XXHasher;
     }
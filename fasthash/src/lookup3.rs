@@ -45,6 +45,8 @@ impl FastHash for Hash32 {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
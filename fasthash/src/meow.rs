@@ -22,11 +22,10 @@
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
-use std::mem;
-use std::os::raw::c_void;
+use core::ffi::c_void;
+use core::mem;
 
 use derive_more::{Deref, From, Into};
-use rand::Rng;
 
 use crate::ffi;
 use crate::hasher::{self, FastHash};
@@ -85,7 +84,7 @@ impl From<hasher::Seed> for Seed {
     #[inline(always)]
     fn from(mut seed: hasher::Seed) -> Seed {
         let mut b = [0; 128];
-        seed.fill(&mut b);
+        seed.fill_bytes(&mut b);
         Seed(b)
     }
 }
@@ -108,6 +107,8 @@ impl FastHash for Hash128 {
     type Hash = u128;
     type Seed = Seed;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: Seed) -> u128 {
         let mut hash = 0u128;
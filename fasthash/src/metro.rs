@@ -21,12 +21,23 @@
 //! You can read more about the design and history
 //! [here](http://www.jandrewrogers.com/2015/05/27/metrohash/).
 //!
+//! # Variants
+//!
+//! The `_1` and `_2` variants only differ by their constants and have the same
+//! speed and statistical quality, `_2` is the recommended default used by `hash64`,
+//! `hash128` and the `metrohash64` and `metrohash128` of the registry. The crate root
+//! `MetroHasher` and `MetroHasherExt` stay on `_1`, as in 0.4, so the values they
+//! compute don't change. The `crc` variants replace a part of the mixing with
+//! the `SSE4.2` CRC instruction, they are faster on CPUs supporting it, but compute
+//! different values, so `hash64_crc` and `hash128_crc` pick them at runtime only when
+//! the hash values don't need to be stable across machines.
+//!
 //! # Example
 //!
 //! ```
 //! use std::hash::{Hash, Hasher};
 //!
-//! use fasthash::{metro, MetroHasher};
+//! use fasthash::{metro, FastHash, MetroHasher};
 //!
 //! fn hash<T: Hash>(t: &T) -> u64 {
 //!     let mut s: MetroHasher = Default::default();
@@ -34,7 +45,7 @@
 //!     s.finish()
 //! }
 //!
-//! let h = metro::hash64(b"hello world\xff");
+//! let h = metro::Hash64_1::hash(b"hello world\xff");
 //!
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
@@ -525,58 +536,284 @@ pub mod crc {
     }
 }
 
-cfg_if! {
-    if #[cfg(any(feature = "sse42", target_feature = "sse4.2"))] {
-        /// `MetroHash` 64-bit hash function for a byte array using HW CRC instruction.
-        #[inline(always)]
-        pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
-            crc::Hash64_1::hash(v)
+/// `MetroHash` 64-bit hash function for a byte array, with the recommended `Hash64_2` variant.
+#[inline(always)]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    Hash64_2::hash(v)
+}
+
+/// `MetroHash` 64-bit hash function for a byte array, with the recommended `Hash64_2` variant.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
+    Hash64_2::hash_with_seed(v, seed)
+}
+
+/// `MetroHash` 128-bit hash function for a byte array, with the recommended `Hash128_2` variant.
+#[inline(always)]
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    Hash128_2::hash(v)
+}
+
+/// `MetroHash` 128-bit hash function for a byte array, with the recommended `Hash128_2` variant.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
+    Hash128_2::hash_with_seed(v, seed)
+}
+
+/// `MetroHash` 128-bit hash function `Hash128_1` for a byte array,
+/// split into the `(low, high)` halves.
+///
+/// On little-endian targets, the low half is the first 64-bit word `v[0]`
+/// written by the reference.
+#[inline(always)]
+pub fn hash128_1_halves<T: AsRef<[u8]>>(v: T, seed: u32) -> (u64, u64) {
+    let h = Hash128_1::hash_with_seed(v, seed);
+
+    (h as u64, (h >> 64) as u64)
+}
+
+/// `MetroHash` 128-bit hash function `Hash128_2` for a byte array,
+/// split into the `(low, high)` halves.
+///
+/// On little-endian targets, the low half is the first 64-bit word `v[0]`
+/// written by the reference.
+#[inline(always)]
+pub fn hash128_2_halves<T: AsRef<[u8]>>(v: T, seed: u32) -> (u64, u64) {
+    let h = Hash128_2::hash_with_seed(v, seed);
+
+    (h as u64, (h >> 64) as u64)
+}
+
+/// The 16 bytes of a `MetroHash` 128-bit hash value, in the layout written by the reference.
+///
+/// Every 128-bit variant, including the CRC ones, writes its two 64-bit state words
+/// in native byte order, which the hash functions read back as a native `u128`,
+/// so the bytes are the native bytes of the hash value, and match the output buffer
+/// of the C functions on the same target.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{metro, FastHash};
+///
+/// let bytes = metro::to_bytes(metro::Hash128_1::hash(b"hello"));
+///
+/// if cfg!(target_endian = "little") {
+///     assert_eq!(&bytes[..4], &[0x8c, 0xcd, 0x1d, 0x43]);
+/// }
+/// ```
+#[inline(always)]
+pub fn to_bytes(hash: u128) -> [u8; 16] {
+    hash.to_ne_bytes()
+}
+
+/// `MetroHash` 64-bit hash function using HW CRC instruction for every key,
+/// hashed independently with the seed, the same as `crc::Hash64_1`.
+///
+/// The keys are hashed in one loop into a preallocated vector, without the per-key
+/// overhead of the hasher API, which dominates for short keys, e.g. of 16 bytes.
+/// Like the `crc` hash functions, it requires a CPU supporting `SSE4.2`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{metro, metro::crc::Hash64_1, FastHash};
+///
+/// let keys = ["hello", "world", "hello"];
+/// let hashes = metro::hash64_crc_many(&keys, 123);
+///
+/// assert_eq!(hashes[0], Hash64_1::hash_with_seed("hello", 123));
+/// assert_eq!(hashes[0], hashes[2]);
+/// ```
+#[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+pub fn hash64_crc_many<I>(keys: I, seed: u32) -> alloc::vec::Vec<u64>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let keys = keys.into_iter();
+    let mut hashes = alloc::vec::Vec::with_capacity(keys.size_hint().0);
+
+    for key in keys {
+        let key = key.as_ref();
+        let mut hash = 0_u64;
+
+        unsafe {
+            ffi::metrohash64crc_1(
+                key.as_ptr(),
+                key.len() as u64,
+                seed,
+                &mut hash as *mut u64 as *mut u8,
+            );
         }
 
-        /// `MetroHash` 64-bit hash function for a byte array using HW CRC instruction.
-        /// For convenience, a 64-bit seed is also hashed into the result.
-        #[inline(always)]
-        pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
-            crc::Hash64_1::hash_with_seed(v, seed)
+        hashes.push(hash);
+    }
+
+    hashes
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "sse42", target_feature = "sse4.2")
+))]
+#[inline(always)]
+fn has_crc() -> bool {
+    has_x86_feature!("sse4.2")
+}
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "sse42", target_feature = "sse4.2")
+)))]
+#[inline(always)]
+fn has_crc() -> bool {
+    false
+}
+
+/// `MetroHash` 64-bit hash function for a byte array,
+/// using the HW CRC instruction if the CPU supports `SSE4.2` at runtime.
+///
+/// The CRC variant computes different values than `hash64`, so the result depends on the CPU,
+/// prefer `hash64` for persisted or shared hash values.
+#[inline(always)]
+pub fn hash64_crc<T: AsRef<[u8]>>(v: T) -> u64 {
+    #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+    {
+        if has_crc() {
+            return crc::Hash64_2::hash(v);
         }
+    }
 
-        /// `MetroHash` 128-bit hash function for a byte array using HW CRC instruction.
-        #[inline(always)]
-        pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
-            crc::Hash128_1::hash(v)
+    Hash64_2::hash(v)
+}
+
+/// `MetroHash` 128-bit hash function for a byte array,
+/// using the HW CRC instruction if the CPU supports `SSE4.2` at runtime.
+///
+/// The CRC variant computes different values than `hash128`, so the result depends on the CPU,
+/// prefer `hash128` for persisted or shared hash values.
+#[inline(always)]
+pub fn hash128_crc<T: AsRef<[u8]>>(v: T) -> u128 {
+    #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+    {
+        if has_crc() {
+            return crc::Hash128_2::hash(v);
         }
+    }
 
-        /// `MetroHash` 128-bit hash function for a byte array. using HW CRC instruction.
-        /// For convenience, a 128-bit seed is also hashed into the result.
-        #[inline(always)]
-        pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
-            crc::Hash128_1::hash_with_seed(v, seed)
+    Hash128_2::hash(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_variant() {
+        assert_eq!(hash64(b"hello"), Hash64_2::hash(b"hello"));
+        assert_eq!(
+            hash64_with_seed(b"hello", 123),
+            Hash64_2::hash_with_seed(b"hello", 123)
+        );
+        assert_eq!(hash128(b"hello"), Hash128_2::hash(b"hello"));
+        assert_eq!(
+            hash128_with_seed(b"hello", 123),
+            Hash128_2::hash_with_seed(b"hello", 123)
+        );
+    }
+
+    #[test]
+    fn test_hash128_halves() {
+        let join = |(low, high): (u64, u64)| u128::from(high) << 64 | u128::from(low);
+
+        for &seed in &[0, 123] {
+            assert_eq!(
+                join(hash128_1_halves(b"hello", seed)),
+                Hash128_1::hash_with_seed(b"hello", seed)
+            );
+            assert_eq!(
+                join(hash128_2_halves(b"hello", seed)),
+                Hash128_2::hash_with_seed(b"hello", seed)
+            );
         }
-    } else {
-        /// `MetroHash` 64-bit hash function for a byte array.
-        #[inline(always)]
-        pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
-            Hash64_1::hash(v)
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_to_bytes() {
+        // the output buffers of the reference for `hello`
+        fn unhex(s: &str) -> [u8; 16] {
+            let mut bytes = [0; 16];
+
+            for (i, b) in bytes.iter_mut().enumerate() {
+                *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).unwrap();
+            }
+
+            bytes
         }
 
-        /// `MetroHash` 64-bit hash function for a byte array.
-        /// For convenience, a 64-bit seed is also hashed into the result.
-        #[inline(always)]
-        pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
-            Hash64_1::hash_with_seed(v, seed)
+        assert_eq!(
+            to_bytes(Hash128_1::hash(b"hello")),
+            unhex("8ccd1d437557bc725dffe5076a3b392f")
+        );
+        assert_eq!(
+            to_bytes(Hash128_2::hash(b"hello")),
+            unhex("51849a62b4cf42fed97203bfac4bfc77")
+        );
+
+        #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+        {
+            if has_crc() {
+                assert_eq!(
+                    to_bytes(crc::Hash128_1::hash(b"hello")),
+                    unhex("fec3012b5c96d2f1f8dd32a0b77bfbe5")
+                );
+                assert_eq!(
+                    to_bytes(crc::Hash128_2::hash(b"hello")),
+                    unhex("8748b5d4311875cbe4e4454e16714e36")
+                );
+            }
+        }
+    }
+
+    #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+    #[test]
+    fn test_hash64_crc_many() {
+        use alloc::vec::Vec;
+
+        if !has_crc() {
+            return;
         }
 
-        /// `MetroHash` 128-bit hash function for a byte array.
-        #[inline(always)]
-        pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
-            Hash128_1::hash(v)
+        let keys = (0..100u8)
+            .map(|i| (0..i).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let hashes = hash64_crc_many(&keys, 123);
+
+        assert_eq!(hashes.len(), keys.len());
+
+        for (key, &hash) in keys.iter().zip(&hashes) {
+            assert_eq!(hash, crc::Hash64_1::hash_with_seed(key, 123));
         }
 
-        /// `MetroHash` 128-bit hash function for a byte array.
-        /// For convenience, a 128-bit seed is also hashed into the result.
-        #[inline(always)]
-        pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
-            Hash128_1::hash_with_seed(v, seed)
+        assert!(hash64_crc_many(Vec::<Vec<u8>>::new(), 123).is_empty());
+    }
+
+    #[test]
+    fn test_crc_variant() {
+        #[cfg(any(feature = "sse42", target_feature = "sse4.2"))]
+        {
+            if has_crc() {
+                assert_eq!(hash64_crc(b"hello"), crc::Hash64_2::hash(b"hello"));
+                assert_eq!(hash128_crc(b"hello"), crc::Hash128_2::hash(b"hello"));
+                return;
+            }
         }
+
+        assert_eq!(hash64_crc(b"hello"), hash64(b"hello"));
+        assert_eq!(hash128_crc(b"hello"), hash128(b"hello"));
     }
 }
@@ -0,0 +1,197 @@
+//! `MinHash` sketches for estimating the Jaccard similarity of sets
+//!
+//! A sketch keeps the minimum hash of the pushed items for each of `k` hash functions,
+//! selected by a family of seeds (see `Seed::family`). The probability that two sets
+//! share the same minimum for a hash function is their Jaccard similarity,
+//! so the fraction of the shared minimums estimates it, with a standard error
+//! of about `1 / sqrt(k)`.
+//!
+//! Only sketches built with the same `k` and seed are comparable.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::{minhash::MinHash, xx};
+//!
+//! let mut a = MinHash::<xx::Hash64>::new(128);
+//! let mut b = MinHash::<xx::Hash64>::new(128);
+//!
+//! for word in "the quick brown fox jumps over the lazy dog".split(' ') {
+//!     a.push(word);
+//! }
+//! for word in "the quick brown fox jumps over the lazy cat".split(' ') {
+//!     b.push(word);
+//! }
+//!
+//! assert!(a.jaccard(&b) > 0.5);
+//! assert_eq!(a.jaccard(&a), 1.0);
+//! ```
+use alloc::vec::Vec;
+
+use num_traits::Bounded;
+use rand::Rng;
+use xoroshiro128::Xoroshiro128Rng;
+
+use crate::hasher::{FastHash, Seed};
+
+/// The seed of the sketches constructed by `MinHash::new`.
+const DEFAULT_SEED: [u64; 2] = [0x6d69_6e68_6173_6821, 0x9e37_79b9_7f4a_7c15];
+
+/// A `MinHash` sketch of a set of byte strings.
+#[derive(Clone)]
+pub struct MinHash<H: FastHash> {
+    seed: Seed,
+    seeds: Vec<H::Seed>,
+    mins: Vec<H::Hash>,
+}
+
+impl<H: FastHash> MinHash<H>
+where
+    H::Seed: From<Seed>,
+{
+    /// Constructs an empty sketch of `k` hash functions with a fixed seed,
+    /// so the sketches are comparable across processes.
+    pub fn new(k: usize) -> Self {
+        Self::with_seed(
+            k,
+            Seed::from_words(Xoroshiro128Rng::from_seed_u64(DEFAULT_SEED).gen()),
+        )
+    }
+
+    /// Constructs an empty sketch of `k` hash functions derived from the seed.
+    pub fn with_seed(k: usize, seed: Seed) -> Self {
+        MinHash {
+            seed,
+            seeds: seed.family(k).into_iter().map(Into::into).collect(),
+            mins: alloc::vec![H::Hash::max_value(); k],
+        }
+    }
+}
+
+impl<H: FastHash> MinHash<H> {
+    /// The number of hash functions of the sketch.
+    #[inline(always)]
+    pub fn k(&self) -> usize {
+        self.mins.len()
+    }
+
+    /// The minimum hash of each hash function.
+    #[inline(always)]
+    pub fn mins(&self) -> &[H::Hash] {
+        &self.mins
+    }
+
+    /// Adds an item to the sketched set.
+    pub fn push<T: AsRef<[u8]>>(&mut self, item: T) {
+        let item = item.as_ref();
+
+        for (min, &seed) in self.mins.iter_mut().zip(self.seeds.iter()) {
+            let h = H::hash_with_seed(item, seed);
+
+            if h < *min {
+                *min = h;
+            }
+        }
+    }
+
+    /// Merges another sketch, as the sketch of the union of both sets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches have a different `k` or seed.
+    pub fn merge(&mut self, other: &Self) {
+        self.check_comparable(other);
+
+        for (min, &h) in self.mins.iter_mut().zip(other.mins.iter()) {
+            if h < *min {
+                *min = h;
+            }
+        }
+    }
+
+    /// Estimates the Jaccard similarity of the sketched sets, between `0.0` and `1.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches have a different `k` or seed.
+    pub fn jaccard(&self, other: &Self) -> f64 {
+        self.check_comparable(other);
+
+        if self.mins.is_empty() {
+            return 0.0;
+        }
+
+        let shared = self
+            .mins
+            .iter()
+            .zip(other.mins.iter())
+            .filter(|(a, b)| a == b)
+            .count();
+
+        shared as f64 / self.k() as f64
+    }
+
+    fn check_comparable(&self, other: &Self) {
+        assert_eq!(self.k(), other.k(), "sketches with a different `k`");
+        assert!(self.seed == other.seed, "sketches with a different seed");
+    }
+}
+
+impl<H: FastHash, T: AsRef<[u8]>> Extend<T> for MinHash<H> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "xx"))]
+mod tests {
+    use super::*;
+    use crate::xx;
+
+    #[test]
+    fn test_jaccard() {
+        let mut a = MinHash::<xx::Hash64>::new(256);
+        let mut b = MinHash::<xx::Hash64>::new(256);
+
+        a.extend((0..1000u32).map(u32::to_le_bytes));
+        b.extend((500..1500u32).map(u32::to_le_bytes));
+
+        // 500 shared items of 1500
+        let j = a.jaccard(&b);
+
+        assert!((j - 1.0 / 3.0).abs() < 0.1, "jaccard {}", j);
+        assert_eq!(a.jaccard(&a), 1.0);
+
+        let mut c = MinHash::<xx::Hash64>::new(256);
+
+        c.extend((2000..3000u32).map(u32::to_le_bytes));
+
+        assert!(a.jaccard(&c) < 0.05);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = MinHash::<xx::Hash64>::new(64);
+        let mut b = MinHash::<xx::Hash64>::new(64);
+        let mut ab = MinHash::<xx::Hash64>::new(64);
+
+        a.extend(&["a", "b", "c"]);
+        b.extend(&["c", "d"]);
+        ab.extend(&["a", "b", "c", "d"]);
+
+        a.merge(&b);
+
+        assert_eq!(a.mins(), ab.mins());
+    }
+
+    #[test]
+    #[should_panic(expected = "different seed")]
+    fn test_different_seed() {
+        let a = MinHash::<xx::Hash64>::new(64);
+        let b = MinHash::<xx::Hash64>::with_seed(64, Seed::gen());
+
+        a.jaccard(&b);
+    }
+}
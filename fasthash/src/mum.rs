@@ -34,6 +34,19 @@
 //! * MUM has a **fast startup**.  It is particular good to hash small keys
 //!   which are a majority of hash table applications
 //!
+//! # Modes
+//!
+//! `hash64` calls `mum_hash`, which selects the `AVX2` build of the hash function
+//! at runtime if the CPU supports it, and only on the targets without unaligned
+//! loads, hashes an unaligned key block by block through an aligned copy.
+//! `hash64_aligned` always hashes the key in one pass from an 8-byte aligned address,
+//! copying an unaligned key first. It hashes the same as `hash64` on the targets
+//! with unaligned loads, like `x86_64` and `aarch64`, and is only faster on the others
+//! for the keys which are already aligned, e.g. slices of `u64`.
+//!
+//! The reference `mum_hash_randomize` isn't exposed, since it replaces the global
+//! constants of the hash function for the whole process, use a random seed instead.
+//!
 //! # Example
 //!
 //! ```
@@ -53,7 +66,10 @@
 //! ```
 //!
 #![allow(non_camel_case_types)]
-use std::os::raw::c_void;
+use alloc::vec::Vec;
+use core::ffi::c_void;
+use core::mem;
+use core::ptr;
 
 use crate::ffi;
 
@@ -120,3 +136,80 @@ pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
 pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     Hash64::hash_with_seed(v, seed)
 }
+
+/// `MumHash` 64-bit hash functions for a byte array, hashed from an aligned address.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::mum;
+///
+/// let words = [0x0123_4567_89ab_cdef_u64; 4];
+/// let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 32) };
+///
+/// assert_eq!(mum::hash64_aligned(bytes), mum::hash64_aligned(bytes.to_vec()));
+/// ```
+#[inline(always)]
+pub fn hash64_aligned<T: AsRef<[u8]>>(v: T) -> u64 {
+    hash64_aligned_with_seed(v, 0)
+}
+
+/// `MumHash` 64-bit hash function for a byte array, hashed from an aligned address.
+/// For convenience, a 64-bit seed is also hashed into the result.
+///
+/// An unaligned key is copied into an aligned buffer first.
+pub fn hash64_aligned_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let bytes = v.as_ref();
+
+    if bytes.as_ptr() as usize % mem::align_of::<u64>() == 0 {
+        return unsafe {
+            ffi::mum_hash_aligned_(bytes.as_ptr() as *const c_void, bytes.len(), seed)
+        };
+    }
+
+    let mut words = Vec::<u64>::with_capacity((bytes.len() + 7) / 8);
+
+    unsafe {
+        // the words have room for all the bytes
+        ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr() as *mut u8, bytes.len());
+
+        ffi::mum_hash_aligned_(words.as_ptr() as *const c_void, bytes.len(), seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash64_aligned() {
+        let words = (0..100u64)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect::<Vec<_>>();
+        let bytes =
+            unsafe { core::slice::from_raw_parts(words.as_ptr() as *const u8, words.len() * 8) };
+
+        for &(start, len) in &[(0, 0), (0, 5), (8, 40), (1, 40), (3, 257), (7, 792)] {
+            let key = &bytes[start..start + len];
+
+            assert_eq!(
+                hash64_aligned_with_seed(key, 123),
+                hash64_aligned_with_seed(key.to_vec(), 123),
+                "start {}, len {}",
+                start,
+                len
+            );
+
+            if cfg!(any(target_arch = "x86_64", target_arch = "aarch64")) {
+                assert_eq!(
+                    hash64_aligned_with_seed(key, 123),
+                    hash64_with_seed(key, 123)
+                );
+            }
+        }
+
+        if cfg!(any(target_arch = "x86_64", target_arch = "aarch64")) {
+            assert_eq!(hash64_aligned(b"hello"), 9723359729180093834);
+        }
+    }
+}
@@ -40,7 +40,7 @@
 //! assert_eq!(h, hash(&"hello world") as u32);
 //! ```
 //!
-use std::os::raw::c_void;
+use core::ffi::c_void;
 
 use crate::ffi;
 
@@ -64,6 +64,8 @@ impl FastHash for Hash32 {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
@@ -113,6 +115,8 @@ impl FastHash for Hash32Aligned {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
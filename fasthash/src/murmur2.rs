@@ -33,6 +33,18 @@
 //! There are two variants which generate 64-bit values; `MurmurHash64A`,
 //! which is optimized for 64-bit processors, and `MurmurHash64B`, for 32-bit ones.
 //!
+//! Each hash function binds one function of the reference implementation:
+//!
+//! - `Hash32`, and `hash32`, is `MurmurHash2`
+//! - `Hash32A`, and `hash32a`, is `MurmurHash2A`
+//! - `Hash32Neutral` is `MurmurHashNeutral2`, the same hash as `MurmurHash2` on little-endian
+//! - `Hash32Aligned` is `MurmurHashAligned2`, the same hash as `MurmurHash2`
+//! - `Hash64_x64` is `MurmurHash64A`
+//! - `Hash64_x86` is `MurmurHash64B`
+//!
+//! `MurmurHash2` and `MurmurHash2A` hash differently, only the former is
+//! the 32-bit `MurmurHash2` of the other implementations.
+//!
 //! # Attacks
 //!
 //! `MurmurHash` was a recommended hash function for hash table implementations.
@@ -64,7 +76,7 @@
 //! ```
 //!
 #![allow(non_camel_case_types)]
-use std::os::raw::c_void;
+use core::ffi::c_void;
 
 use crate::ffi;
 
@@ -88,6 +100,8 @@ impl FastHash for Hash32 {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
@@ -137,6 +151,8 @@ impl FastHash for Hash32A {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
@@ -170,6 +186,10 @@ trivial_hasher! {
 
 /// `MurmurHash2` 32-bit neutral hash functions for the (slower) endian-neutral implementation
 ///
+/// The input is read byte by byte as little-endian words, so the hash of the same bytes
+/// is identical on all platforms, and it can be persisted or shared between them.
+/// On little-endian platforms it equals `Hash32`, which reads the words in the native byte order.
+///
 /// # Example
 ///
 /// ```
@@ -186,6 +206,8 @@ impl FastHash for Hash32Neutral {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
@@ -235,6 +257,8 @@ impl FastHash for Hash32Aligned {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
@@ -287,6 +311,8 @@ impl FastHash for Hash64_x64 {
     type Hash = u64;
     type Seed = u64;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
@@ -339,6 +365,8 @@ impl FastHash for Hash64_x86 {
     type Hash = u64;
     type Seed = u64;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
@@ -373,13 +401,26 @@ trivial_hasher! {
 /// `MurmurHash2` 32-bit hash functions for a byte array.
 #[inline(always)]
 pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
-    Hash32A::hash(v)
+    Hash32::hash(v)
 }
 
 /// `MurmurHash2` 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline(always)]
 pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    Hash32::hash_with_seed(v, seed)
+}
+
+/// `MurmurHash2A` 32-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash32a<T: AsRef<[u8]>>(v: T) -> u32 {
+    Hash32A::hash(v)
+}
+
+/// `MurmurHash2A` 32-bit hash function for a byte array.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash32a_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Hash32A::hash_with_seed(v, seed)
 }
 
@@ -403,3 +444,126 @@ pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
         Hash64_x86::hash_with_seed(v, seed)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    /// A portable `MurmurHashNeutral2`, which assembles the words from the bytes explicitly.
+    fn murmur_neutral2(bytes: &[u8], seed: u32) -> u32 {
+        const M: u32 = 0x5bd1_e995;
+
+        let mut h = seed ^ bytes.len() as u32;
+        let mut chunks = bytes.chunks_exact(4);
+
+        for chunk in &mut chunks {
+            let mut k = u32::from(chunk[0])
+                | u32::from(chunk[1]) << 8
+                | u32::from(chunk[2]) << 16
+                | u32::from(chunk[3]) << 24;
+
+            k = k.wrapping_mul(M);
+            k ^= k >> 24;
+            k = k.wrapping_mul(M);
+
+            h = h.wrapping_mul(M) ^ k;
+        }
+
+        let tail = chunks.remainder();
+
+        if !tail.is_empty() {
+            for (i, &b) in tail.iter().enumerate() {
+                h ^= u32::from(b) << (8 * i);
+            }
+            h = h.wrapping_mul(M);
+        }
+
+        h ^= h >> 13;
+        h = h.wrapping_mul(M);
+        h ^ (h >> 15)
+    }
+
+    #[test]
+    fn test_neutral_endian() {
+        let data: Vec<u8> = (0..64u8).map(|b| b.wrapping_mul(37)).collect();
+
+        for len in 0..data.len() {
+            for &seed in &[0, 123, 0xdead_beef] {
+                let h = Hash32Neutral::hash_with_seed(&data[..len], seed);
+
+                assert_eq!(h, murmur_neutral2(&data[..len], seed), "len {}", len);
+
+                if cfg!(target_endian = "little") {
+                    assert_eq!(h, Hash32::hash_with_seed(&data[..len], seed), "len {}", len);
+                }
+            }
+        }
+
+        // the same words hash the same when serialized as little-endian on any platform
+        let words = [0x0102_0304_u32, 0xa0b0_c0d0];
+        let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+
+        assert_eq!(Hash32Neutral::hash(&bytes), murmur_neutral2(&bytes, 0));
+        assert_eq!(Hash32Neutral::hash(b"hello"), 3848350155);
+    }
+
+    // the verification value of SMHasher, the hash of the hashes of the prefixes of `0..=255`
+    fn verification<F: Fn(&[u8], u32) -> Vec<u8>>(f: F) -> u32 {
+        let key = (0..=255u8).collect::<Vec<_>>();
+        let hashes = (0..256)
+            .flat_map(|i| f(&key[..i], 256 - i as u32))
+            .collect::<Vec<_>>();
+        let h = f(&hashes, 0);
+
+        u32::from_le_bytes([h[0], h[1], h[2], h[3]])
+    }
+
+    #[test]
+    fn test_smhasher_verification() {
+        assert_eq!(
+            verification(|b, seed| Hash32Neutral::hash_with_seed(b, seed)
+                .to_le_bytes()
+                .to_vec()),
+            0x2786_4C1E
+        );
+
+        // the reference reads the words of the input in native byte order
+        if cfg!(target_endian = "little") {
+            assert_eq!(
+                verification(|b, seed| hash32_with_seed(b, seed).to_le_bytes().to_vec()),
+                0x2786_4C1E
+            );
+            assert_eq!(
+                verification(|b, seed| hash32a_with_seed(b, seed).to_le_bytes().to_vec()),
+                0x7FBD_4396
+            );
+            assert_eq!(
+                verification(|b, seed| {
+                    Hash64_x64::hash_with_seed(b, u64::from(seed))
+                        .to_le_bytes()
+                        .to_vec()
+                }),
+                0x1F0D_3804
+            );
+            assert_eq!(
+                verification(|b, seed| {
+                    Hash64_x86::hash_with_seed(b, u64::from(seed))
+                        .to_le_bytes()
+                        .to_vec()
+                }),
+                0xDD53_7C05
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash32() {
+        assert_eq!(hash32(b"hello"), 3848350155);
+        assert_eq!(hash32_with_seed(b"hello", 123), 2385981934);
+        assert_eq!(hash32a(b"hello"), 259931098);
+        assert_eq!(hash32a_with_seed(b"hello", 123), 509510832);
+        assert_eq!(hash32(b""), 0);
+    }
+}
@@ -11,6 +11,11 @@
 //! compile and run any of them on any platform, but your performance with the
 //! non-native version will be less than optimal.
 //!
+//! `Best128` and `best128` select the native 128-bit version at compile time,
+//! `Hash128_x64` on 64-bit targets and `Hash128_x86` otherwise, so the hash values
+//! differ between 32-bit and 64-bit targets. Pick one of the versions explicitly
+//! if the hash values are persisted or shared between platforms.
+//!
 //! # Example
 //!
 //! ```
@@ -30,7 +35,7 @@
 //! ```
 //!
 #![allow(non_camel_case_types)]
-use std::os::raw::c_void;
+use core::ffi::c_void;
 
 use crate::ffi;
 
@@ -54,6 +59,8 @@ impl FastHash for Hash32 {
     type Hash = u32;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
@@ -117,6 +124,8 @@ impl FastHash for Hash128_x86 {
     type Hash = u128;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
         unsafe {
@@ -180,6 +189,8 @@ impl FastHash for Hash128_x64 {
     type Hash = u128;
     type Seed = u32;
 
+    const MAX_INPUT_LEN: usize = i32::MAX as usize;
+
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
         unsafe {
@@ -216,6 +227,53 @@ trivial_hasher! {
     Hasher128_x64(Hash128_x64) -> u128
 }
 
+/// `MurmurHash3_x64_128` for a byte array, as the 16 bytes written by the reference.
+///
+/// The reference writes the two 64-bit halves in native byte order, so on little-endian
+/// targets the bytes are the little-endian bytes of `Hash128_x64::hash`, e.g. of
+/// `Digest128::from(Hash128_x64::hash(v))`.
+#[inline(always)]
+pub fn hash128_x64_bytes<T: AsRef<[u8]>>(v: T) -> [u8; 16] {
+    let mut bytes = [0_u8; 16];
+
+    unsafe {
+        ffi::MurmurHash3_x64_128(
+            v.as_ref().as_ptr() as *const c_void,
+            v.as_ref().len() as i32,
+            0,
+            bytes.as_mut_ptr() as *mut c_void,
+        );
+    }
+
+    bytes
+}
+
+/// `MurmurHash3` 128-bit hash functions for the native pointer width.
+#[cfg(target_pointer_width = "64")]
+pub type Best128 = Hash128_x64;
+
+/// `MurmurHash3` 128-bit hash functions for the native pointer width.
+#[cfg(not(target_pointer_width = "64"))]
+pub type Best128 = Hash128_x86;
+
+/// `MurmurHash3` 128-bit hash functions for a byte array,
+/// with the fastest version for the native pointer width.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{murmur3, FastHash};
+///
+/// assert_eq!(
+///     murmur3::best128(b"hello", 123),
+///     murmur3::Best128::hash_with_seed(b"hello", 123)
+/// );
+/// ```
+#[inline(always)]
+pub fn best128<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
+    Best128::hash_with_seed(v, seed)
+}
+
 /// `MurmurHash3` 32-bit hash functions for a byte array.
 #[inline(always)]
 pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
@@ -229,6 +287,96 @@ pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Hash32::hash_with_seed(v, seed)
 }
 
+/// `MurmurHash3` 32-bit hash function for a byte array,
+/// matching Guava's `Hashing.murmur3_32_fixed(seed).hashBytes(bytes).asInt()`.
+///
+/// The seed and the hash are Java `int`s, i.e. the `u32` of `hash32_with_seed` as `i32`.
+/// Hash the UTF-8 bytes of a string to match `hashString(s, UTF_8)`, or use
+/// `hash32_guava_chars` to match `hashUnencodedChars(s)`. The deprecated `murmur3_32`
+/// only differs for `hashString` of strings with characters outside of the BMP.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::murmur3;
+///
+/// assert_eq!(murmur3::hash32_guava("hello", 0), 0x248b_fa47);
+/// assert_eq!(murmur3::hash32_guava("毎月１日,毎週月曜日", 0), 0xfc5b_a834_u32 as i32);
+/// assert_eq!(murmur3::hash32_guava(42_i32.to_le_bytes(), 0), -1134849565);
+/// ```
+#[inline(always)]
+pub fn hash32_guava<T: AsRef<[u8]>>(v: T, seed: i32) -> i32 {
+    hash32_with_seed(v, seed as u32) as i32
+}
+
+/// `MurmurHash3` 32-bit hash function for the UTF-16 code units of a string,
+/// matching Guava's `Hashing.murmur3_32_fixed(seed).hashUnencodedChars(s).asInt()`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::murmur3;
+///
+/// assert_eq!(murmur3::hash32_guava_chars("hello", 0), -675079799);
+/// ```
+pub fn hash32_guava_chars(s: &str, seed: i32) -> i32 {
+    hash32_guava(utf16_le_bytes(s), seed)
+}
+
+/// The UTF-16 code units of a string as little-endian bytes, like a Java `char[]`.
+fn utf16_le_bytes(s: &str) -> alloc::vec::Vec<u8> {
+    s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+/// `MurmurHash3_x64_128` 128-bit hash function for the UTF-16 code units of a string,
+/// matching Guava's `Hashing.murmur3_128(seed).hashUnencodedChars(s)`, for the interop
+/// with the JVM or .NET, which hash the UTF-16 representation of their strings.
+///
+/// The string is encoded as little-endian UTF-16 bytes on every platform, the order
+/// Guava feeds a `char` in. The characters outside of the BMP are encoded as surrogate
+/// pairs, like in a Java `String`. A `str` can't hold an unpaired surrogate, so hash
+/// the little-endian bytes of such code units with `Hash128_x64` directly.
+///
+/// The low 64 bits are the first 8 bytes of the Guava `HashCode`, i.e. its `asLong()`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::murmur3;
+///
+/// let h = murmur3::hash128_utf16("hello", 0);
+///
+/// assert_eq!(h, 164255438849030241186017339212075881351);
+/// assert_eq!(h as u64, murmur3::hash64_utf16("hello", 0));
+/// ```
+pub fn hash128_utf16(s: &str, seed: u32) -> u128 {
+    let h = Hash128_x64::hash_with_seed(utf16_le_bytes(s), seed);
+
+    // the reference writes the first half of the hash first, in native byte order
+    if cfg!(target_endian = "big") {
+        h.rotate_left(64)
+    } else {
+        h
+    }
+}
+
+/// `MurmurHash3_x64_128` 64-bit hash function for the UTF-16 code units of a string,
+/// matching Guava's `Hashing.murmur3_128(seed).hashUnencodedChars(s).asLong()` as `u64`.
+///
+/// See `hash128_utf16` for the encoding of the string.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::murmur3;
+///
+/// assert_eq!(murmur3::hash64_utf16("hello", 0) as i64, -1283840835669994617);
+/// ```
+#[inline(always)]
+pub fn hash64_utf16(s: &str, seed: u32) -> u64 {
+    hash128_utf16(s, seed) as u64
+}
+
 /// `MurmurHash3` 128-bit hash functions for a byte array.
 #[inline(always)]
 pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
@@ -249,3 +397,220 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
         Hash128_x86::hash_with_seed(v, seed)
     }
 }
+
+/// `MurmurHash3_x86_128` 128-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash128_x86<T: AsRef<[u8]>>(v: T) -> u128 {
+    Hash128_x86::hash(v)
+}
+
+/// `MurmurHash3_x86_128` 128-bit hash functions for a byte array.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash128_x86_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
+    Hash128_x86::hash_with_seed(v, seed)
+}
+
+/// `MurmurHash3_x64_128` 128-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash128_x64<T: AsRef<[u8]>>(v: T) -> u128 {
+    Hash128_x64::hash(v)
+}
+
+/// `MurmurHash3_x64_128` 128-bit hash functions for a byte array.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash128_x64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
+    Hash128_x64::hash_with_seed(v, seed)
+}
+
+/// Narrows a 64-bit seed to the 32-bit seed of every `MurmurHash3` variant.
+///
+/// The high 32 bits are xor-ed into the low 32 bits, so a seed which fits in 32 bits
+/// is unchanged, and seeds which only differ in their high bits still select
+/// different hash functions.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::murmur3;
+///
+/// assert_eq!(murmur3::widen_seed(123), 123);
+/// assert_eq!(murmur3::widen_seed(0x0000_0001_0000_0000), 1);
+/// assert_eq!(
+///     murmur3::hash32_with_seed(b"hello", murmur3::widen_seed(123)),
+///     murmur3::hash32_with_seed(b"hello", 123)
+/// );
+/// ```
+#[inline(always)]
+pub fn widen_seed(seed: u64) -> u32 {
+    (seed ^ (seed >> 32)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_hash128_x64_bytes() {
+        use crate::hasher::Digest128;
+
+        for v in &[&b""[..], b"hello", b"helloworld", &[0x5a; 100]] {
+            let bytes: [u8; 16] = Digest128::from(Hash128_x64::hash(v)).into();
+
+            assert_eq!(hash128_x64_bytes(v), bytes);
+            assert_eq!(
+                Digest128::from(hash128_x64_bytes(v)).0,
+                Hash128_x64::hash(v)
+            );
+        }
+    }
+
+    #[test]
+    fn test_best128_pointer_width() {
+        fn same_type<T>(_: T, _: T) {}
+
+        #[cfg(target_pointer_width = "64")]
+        same_type(Best128, Hash128_x64);
+        #[cfg(not(target_pointer_width = "64"))]
+        same_type(Best128, Hash128_x86);
+    }
+
+    // hashes the keys {}, {0}, {0, 1}, ... {0 .. 254} with the seeds 256 - len,
+    // then hashes the concatenated hashes with the seed 0, like SMHasher
+    fn verification<F: Fn(&[u8], u32) -> Vec<u8>>(f: F) -> u32 {
+        let key = (0..=255u8).collect::<Vec<_>>();
+        let hashes = (0..256)
+            .flat_map(|i| f(&key[..i], 256 - i as u32))
+            .collect::<Vec<_>>();
+        let h = f(&hashes, 0);
+
+        u32::from_le_bytes([h[0], h[1], h[2], h[3]])
+    }
+
+    #[test]
+    fn test_smhasher_verification() {
+        assert_eq!(
+            verification(|b, seed| hash32_with_seed(b, seed).to_le_bytes().to_vec()),
+            0xB0F5_7EE3
+        );
+
+        // the reference writes the 64-bit or 32-bit words of the hash in native byte order
+        if cfg!(target_endian = "little") {
+            assert_eq!(
+                verification(|b, seed| hash128_x86_with_seed(b, seed).to_le_bytes().to_vec()),
+                0xB3EC_E62A
+            );
+            assert_eq!(
+                verification(|b, seed| hash128_x64_with_seed(b, seed).to_le_bytes().to_vec()),
+                0x6384_BA69
+            );
+        }
+    }
+
+    #[test]
+    fn test_guava() {
+        // the strings of `Murmur3Hash32Test` of Guava, hashed by `murmur3_32_fixed`
+        let strings = [
+            ("", 0, 0),
+            ("k", 0xcfbd_a5d1_u32 as i32, 679745764),
+            ("hell", 0xa167_dbf3_u32 as i32, 1510782915),
+            ("hello", 0x248b_fa47, -675079799),
+            ("http://www.google.com/", 0x3d41_b97c, 1935035788),
+            (
+                "The quick brown fox jumps over the lazy dog",
+                0x2e4f_f723,
+                -528633700,
+            ),
+            ("ABCDefGHI\u{799}", 0xb5a4_be05_u32 as i32, -17154442),
+            ("毎月１日,毎週月曜日", 0xfc5b_a834_u32 as i32, 1786503474),
+            ("surrogate pairs: \u{1f4b0}", 0x12c3_212c, 1673175431),
+        ];
+
+        for &(s, utf8, chars) in &strings {
+            assert_eq!(hash32_guava(s, 0), utf8, "{}", s);
+            assert_eq!(hash32_guava_chars(s, 0), chars, "{}", s);
+        }
+
+        assert_eq!(hash32_guava(0_i32.to_le_bytes(), 0), 593689054);
+        assert_eq!(hash32_guava((-42_i32).to_le_bytes(), 0), -189366624);
+        assert_eq!(
+            hash32_guava(b"hello", -1),
+            hash32_with_seed(b"hello", u32::MAX) as i32
+        );
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_guava_128() {
+        // the known values of Guava's `Murmur3Hash128Test`, `(h1, h2)` as `long`s
+        let strings: [(u32, u64, u64, &str); 3] = [
+            (0, 0x6299_4269_3e10_f867, 0x92db_0b82_baeb_5347, "hell"),
+            (1, 0xa78d_dff5_adae_8d10, 0x1289_00ef_2090_0135, "hello"),
+            (2, 0x8a48_6b23_f422_e826, 0xf962_a2c5_8947_765f, "hello "),
+        ];
+
+        for &(seed, h1, h2, s) in &strings {
+            assert_eq!(
+                Hash128_x64::hash_with_seed(s, seed),
+                u128::from(h2) << 64 | u128::from(h1),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_utf16() {
+        // `murmur3_128().hashUnencodedChars(s).asLong()`
+        let strings = [
+            ("", 0),
+            ("hello", -1283840835669994617),
+            (
+                "The quick brown fox jumps over the lazy dog",
+                -4611010704792506804,
+            ),
+            ("毎月１日,毎週月曜日", -7923385850672137436),
+            ("surrogate pairs: \u{1f4b0}", 3298594768781428295),
+        ];
+
+        for &(s, long) in &strings {
+            assert_eq!(hash64_utf16(s, 0) as i64, long, "{}", s);
+            assert_eq!(hash128_utf16(s, 0) as u64, hash64_utf16(s, 0));
+        }
+
+        // a surrogate pair is hashed as its two code units
+        assert_eq!(utf16_le_bytes("\u{1f4b0}"), [0x3d, 0xd8, 0xb0, 0xdc]);
+        assert_ne!(hash64_utf16("hello", 0), hash64_utf16("hello", 1));
+    }
+
+    #[test]
+    fn test_widen_seed() {
+        for &seed in &[0, 1, 123, u64::from(u32::MAX)] {
+            assert_eq!(widen_seed(seed), seed as u32);
+        }
+
+        assert_ne!(widen_seed(1 << 32), widen_seed(2 << 32));
+        assert_eq!(widen_seed(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_hash128_variants() {
+        let x86 = Hash128_x86::hash_with_seed(b"hello", 123);
+        let x64 = Hash128_x64::hash_with_seed(b"hello", 123);
+
+        assert_eq!(x86, 39646137218600763345533167485429249129);
+        assert_eq!(x64, 19243349499071459060235768594146641163);
+        assert_ne!(x86, x64);
+
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(best128(b"hello", 123), x64);
+        } else {
+            assert_eq!(best128(b"hello", 123), x86);
+        }
+        assert_eq!(best128(b"hello", 123), hash128_with_seed(b"hello", 123));
+    }
+}
@@ -0,0 +1,179 @@
+//! `nmhash`: a 32-bit hash function optimized for vectorization
+//!
+//! by James Z.M. Gao
+//!
+//! https://github.com/gzm55/hash-garage
+//!
+//! `NMHASH32` and its `NMHASH32X` variant pass SMHasher, and `NMHASH32X` is faster
+//! for short keys at the cost of a weaker avalanche for the long ones.
+//! Both take a 32-bit seed.
+//!
+//! The header only implementation is vendored from `smhasher`, at `NMH_VERSION` 2.
+//!
+//! # Example
+//!
+//! ```
+//! use std::hash::{Hash, Hasher};
+//!
+//! use fasthash::{nmhash, NmHasher};
+//!
+//! fn hash<T: Hash>(t: &T) -> u64 {
+//!     let mut s: NmHasher = Default::default();
+//!     t.hash(&mut s);
+//!     s.finish()
+//! }
+//!
+//! let h = nmhash::hash32(b"hello world\xff");
+//!
+//! assert_eq!(h as u64, hash(&"hello world"));
+//! ```
+//!
+use crate::ffi;
+
+use crate::hasher::FastHash;
+
+/// `NMHASH32` 32-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{nmhash::Hash32, FastHash};
+///
+/// assert_eq!(Hash32::hash(b"hello"), Hash32::hash_with_seed(b"hello", 0));
+/// assert_ne!(Hash32::hash(b"hello"), Hash32::hash_with_seed(b"hello", 123));
+/// ```
+#[derive(Clone, Default)]
+pub struct Hash32;
+
+impl FastHash for Hash32 {
+    type Hash = u32;
+    type Seed = u32;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
+        unsafe {
+            ffi::nmhash32(
+                bytes.as_ref().as_ptr() as *const _,
+                bytes.as_ref().len(),
+                seed,
+            )
+        }
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{nmhash, nmhash::Hasher32, FastHasher};
+    ///
+    /// let mut h = Hasher32::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish(), nmhash::hash32(b"hello") as u64);
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), nmhash::hash32(b"helloworld") as u64);
+    /// ```
+    Hasher32(Hash32) -> u32
+}
+
+/// `NMHASH32X` 32-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{nmhash::Hash32X, FastHash};
+///
+/// assert_eq!(Hash32X::hash(b"hello"), Hash32X::hash_with_seed(b"hello", 0));
+/// assert_ne!(Hash32X::hash(b"hello"), Hash32X::hash_with_seed(b"hello", 123));
+/// ```
+#[derive(Clone, Default)]
+pub struct Hash32X;
+
+impl FastHash for Hash32X {
+    type Hash = u32;
+    type Seed = u32;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
+        unsafe {
+            ffi::nmhash32x(
+                bytes.as_ref().as_ptr() as *const _,
+                bytes.as_ref().len(),
+                seed,
+            )
+        }
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{nmhash, nmhash::Hasher32X, FastHasher};
+    ///
+    /// let mut h = Hasher32X::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish(), nmhash::hash32x(b"hello") as u64);
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), nmhash::hash32x(b"helloworld") as u64);
+    /// ```
+    Hasher32X(Hash32X) -> u32
+}
+
+/// `NMHASH32` 32-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    Hash32::hash(v)
+}
+
+/// `NMHASH32` 32-bit hash function for a byte array.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    Hash32::hash_with_seed(v, seed)
+}
+
+/// `NMHASH32X` 32-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash32x<T: AsRef<[u8]>>(v: T) -> u32 {
+    Hash32X::hash(v)
+}
+
+/// `NMHASH32X` 32-bit hash function for a byte array.
+/// For convenience, a 32-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash32x_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    Hash32X::hash_with_seed(v, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn smhasher_verification(hash: fn(&[u8], u32) -> u32) -> u32 {
+        // hashes the keys {}, {0}, {0, 1}, ... {0 .. 254} with the seeds 256 - len,
+        // then hashes the concatenated hashes with the seed 0
+        let key = (0..=255u8).collect::<Vec<_>>();
+        let hashes = (0..256)
+            .flat_map(|i| hash(&key[..i], 256 - i as u32).to_le_bytes())
+            .collect::<Vec<_>>();
+
+        hash(&hashes, 0)
+    }
+
+    #[test]
+    fn test_smhasher_verification() {
+        assert_eq!(smhasher_verification(hash32_with_seed), 0x12A3_0553);
+        assert_eq!(smhasher_verification(hash32x_with_seed), 0xA858_0227);
+    }
+}
@@ -0,0 +1,162 @@
+//! `pengyhash`: a simple and fast 64-bit hash function
+//!
+//! by Alberto Fajardo
+//!
+//! `pengyhash` v0.2 passes SMHasher, and is short enough to be implemented in pure Rust,
+//! so it doesn't need the native library.
+//!
+//! The reference takes a 32-bit seed, which is extended to a 64-bit seed here.
+//! The hash values of the seeds which fit in 32 bits are identical to the reference.
+//!
+//! # Example
+//!
+//! ```
+//! use std::hash::{Hash, Hasher};
+//!
+//! use fasthash::{pengy, PengyHasher};
+//!
+//! fn hash<T: Hash>(t: &T) -> u64 {
+//!     let mut s: PengyHasher = Default::default();
+//!     t.hash(&mut s);
+//!     s.finish()
+//! }
+//!
+//! let h = pengy::hash64(b"hello world\xff");
+//!
+//! assert_eq!(h, hash(&"hello world"));
+//! ```
+//!
+use core::convert::TryInto;
+
+use crate::hasher::FastHash;
+
+#[inline(always)]
+fn round(s: &mut [u64; 4], b: &[u64; 4], seed: u64) {
+    s[0] = s[0].wrapping_add(s[1]).wrapping_add(b[3]);
+    s[1] = s[0].wrapping_add(s[1].rotate_left(14)).wrapping_add(seed);
+    s[2] = s[2].wrapping_add(s[3]).wrapping_add(b[2]);
+    s[3] = s[2].wrapping_add(s[3].rotate_left(23));
+    s[0] = s[0].wrapping_add(s[3]).wrapping_add(b[1]);
+    s[3] = s[0] ^ s[3].rotate_left(16);
+    s[2] = s[2].wrapping_add(s[1]).wrapping_add(b[0]);
+    s[1] = s[2] ^ s[1].rotate_left(40);
+}
+
+#[inline(always)]
+fn load(b: &mut [u64; 4], block: &[u8]) {
+    for (w, bytes) in b.iter_mut().zip(block.chunks_exact(8)) {
+        *w = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+}
+
+fn pengyhash(bytes: &[u8], seed: u64) -> u64 {
+    let mut b = [0_u64; 4];
+    let mut s = [0, 0, 0, bytes.len() as u64];
+    let mut blocks = bytes.chunks_exact(32);
+
+    for block in &mut blocks {
+        load(&mut b, block);
+        round(&mut s, &b, 0);
+    }
+
+    // the tail only overwrites the leading bytes of the last block, like `memcpy` in the reference
+    let tail = blocks.remainder();
+    let mut block = [0_u8; 32];
+
+    for (dst, w) in block.chunks_exact_mut(8).zip(b.iter()) {
+        dst.copy_from_slice(&w.to_le_bytes());
+    }
+    block[..tail.len()].copy_from_slice(tail);
+    load(&mut b, &block);
+
+    for _ in 0..6 {
+        round(&mut s, &b, seed);
+    }
+
+    s[0].wrapping_add(s[1])
+        .wrapping_add(s[2])
+        .wrapping_add(s[3])
+}
+
+/// `pengyhash` 64-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{pengy::Hash64, FastHash};
+///
+/// assert_eq!(Hash64::hash(b"hello"), 16216849072302672261);
+/// assert_eq!(Hash64::hash_with_seed(b"hello", 123), 9079223790445393170);
+/// assert_eq!(Hash64::hash(b"helloworld"), 3542539606493928684);
+/// ```
+#[derive(Clone, Default)]
+pub struct Hash64;
+
+impl FastHash for Hash64 {
+    type Hash = u64;
+    type Seed = u64;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
+        pengyhash(bytes.as_ref(), seed)
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{pengy::Hasher64, FastHasher};
+    ///
+    /// let mut h = Hasher64::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish(), 16216849072302672261);
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), 3542539606493928684);
+    /// ```
+    Hasher64(Hash64) -> u64
+}
+
+/// `pengyhash` 64-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    Hash64::hash(v)
+}
+
+/// `pengyhash` 64-bit hash function for a byte array.
+/// For convenience, a 64-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    Hash64::hash_with_seed(v, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_smhasher_verification() {
+        // hashes the keys {}, {0}, {0, 1}, ... {0 .. 254} with the seeds 256 - len,
+        // then hashes the concatenated hashes with the seed 0
+        let key = (0..=255u8).collect::<Vec<_>>();
+        let hashes = (0..256)
+            .flat_map(|i| hash64_with_seed(&key[..i], 256 - i as u64).to_le_bytes())
+            .collect::<Vec<_>>();
+
+        assert_eq!(hash64(&hashes) as u32, 0x1FC2_217B);
+    }
+
+    #[test]
+    fn test_long_input() {
+        let data = b"hello world, this is a longer input of more than 32 bytes";
+
+        assert_eq!(hash64(&data[..]), 6012304135431585841);
+        assert_eq!(hash64(b""), 0);
+    }
+}
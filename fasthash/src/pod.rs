@@ -0,0 +1,123 @@
+//! Hashing of plain old data records in one shot
+use num_traits::AsPrimitive;
+
+use crate::hasher::FastHash;
+
+/// Hashes the in-memory bytes of a `bytemuck::Pod` value with the hash function.
+///
+/// The value is reinterpreted as bytes without copying and hashed in one shot,
+/// the fast path for fixed-layout records like a `#[repr(C)]` struct of integers,
+/// instead of writing it field by field.
+///
+/// The hash depends on the memory layout of the value, so it is only stable within
+/// a platform, the integers are hashed in the native byte order, and `usize`
+/// or the alignment of the fields differ between targets. A `Pod` type can't have
+/// implicit padding, any padding is an explicit field of the record, and is hashed
+/// like the other fields, so it should be zeroed.
+///
+/// # Example
+///
+/// ```
+/// use bytemuck::{Pod, Zeroable};
+/// use fasthash::{city, hash_pod};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// unsafe impl Zeroable for Point {}
+/// unsafe impl Pod for Point {}
+///
+/// let p = Point { x: 1, y: 2 };
+/// let bytes = [1u32.to_ne_bytes(), 2u32.to_ne_bytes()].concat();
+///
+/// assert_eq!(hash_pod::<_, city::Hash64>(&p), city::hash64(&bytes));
+/// ```
+#[inline(always)]
+pub fn hash_pod<T: bytemuck::Pod, H>(value: &T) -> u64
+where
+    H: FastHash,
+    H::Hash: AsPrimitive<u64>,
+{
+    H::hash(bytemuck::bytes_of(value)).as_()
+}
+
+/// Hashes the in-memory bytes of a `bytemuck::Pod` value with the hash function and seed.
+#[inline(always)]
+pub fn hash_pod_with_seed<T: bytemuck::Pod, H>(value: &T, seed: H::Seed) -> u64
+where
+    H: FastHash,
+    H::Hash: AsPrimitive<u64>,
+{
+    H::hash_with_seed(bytemuck::bytes_of(value), seed).as_()
+}
+
+#[cfg(all(test, feature = "city", feature = "murmur"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use bytemuck::{Pod, Zeroable};
+
+    use super::*;
+    use crate::{city, murmur3};
+
+    #[derive(Clone, Copy)]
+    #[repr(C)]
+    struct Record {
+        id: u64,
+        kind: u16,
+        flags: u8,
+        _pad: [u8; 5],
+        score: u32,
+        count: u32,
+    }
+
+    unsafe impl Zeroable for Record {}
+    unsafe impl Pod for Record {}
+
+    fn record(id: u64) -> Record {
+        Record {
+            id,
+            kind: 7,
+            flags: 0x80,
+            _pad: [0; 5],
+            score: 12345,
+            count: 2,
+        }
+    }
+
+    // the bytes of the record written field by field, in the native byte order
+    fn fields(r: &Record) -> Vec<u8> {
+        [
+            &r.id.to_ne_bytes()[..],
+            &r.kind.to_ne_bytes(),
+            &[r.flags],
+            &r._pad,
+            &r.score.to_ne_bytes(),
+            &r.count.to_ne_bytes(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_hash_pod() {
+        let r = record(42);
+
+        assert_eq!(fields(&r).len(), core::mem::size_of::<Record>());
+        assert_eq!(hash_pod::<_, city::Hash64>(&r), city::hash64(fields(&r)));
+        assert_eq!(
+            hash_pod_with_seed::<_, murmur3::Hash128_x64>(&r, 123),
+            murmur3::hash128_x64_with_seed(fields(&r), 123) as u64
+        );
+
+        // stable for the same value, and sensitive to every field
+        let hash = |r: &Record| hash_pod::<_, city::Hash64>(r);
+
+        assert_eq!(hash(&r), hash(&record(42)));
+        assert_ne!(hash(&r), hash(&record(43)));
+        assert_ne!(hash(&r), hash(&Record { count: 3, ..r }));
+    }
+}
@@ -0,0 +1,448 @@
+//! The registry of the hash functions of the crate
+//!
+//! `fast_hashes!` lists every exported `FastHash` type once, with its name, aliases and
+//! the `cfg` it is compiled under, and expands the list into the given callback macro.
+//! The uniform tests and `AnyAlgorithm` are both generated from it, so a new hash function
+//! is tested and named as soon as it is registered here.
+//!
+//! Each entry has the form
+//!
+//! ```text
+//! #[cfg(...)]
+//! "name" | "alias" => crate::module::Hash as Variant,
+//! ```
+//!
+//! where the optional `as Variant` makes the hash function selectable at runtime,
+//! as the variant of `AnyAlgorithm` and `AnyHasher` named `Variant`.
+
+/// Expands the registered hash functions into `$callback! { $($arg,)* entries.. }`.
+#[allow(unused_macros)]
+macro_rules! fast_hashes {
+    ($callback:ident $(, $arg:ident)*) => {
+        $callback! {
+            $($arg,)*
+
+            #[cfg(feature = "ahash")]
+            "ahash64" | "ahash" => crate::ahash::Hash64 as AHash64,
+
+            #[cfg(feature = "city")]
+            "cityhash32" | "city32" => crate::city::Hash32 as CityHash32,
+            #[cfg(feature = "city")]
+            "cityhash64" | "city64" => crate::city::Hash64 as CityHash64,
+            #[cfg(feature = "city")]
+            "cityhash128" | "city128" => crate::city::Hash128 as CityHash128,
+            #[cfg(feature = "city")]
+            "cityhash128_crc" | "city128_crc" => crate::city::crc::Hash128,
+
+            #[cfg(all(feature = "clhash", target_arch = "x86_64"))]
+            "clhash64" | "clhash" => crate::clhash::Hash64,
+
+            #[cfg(feature = "farm")]
+            "farmhash32" | "farm32" => crate::farm::Hash32 as FarmHash32,
+            #[cfg(feature = "farm")]
+            "farmhash64" | "farm64" => crate::farm::Hash64 as FarmHash64,
+            #[cfg(feature = "farm")]
+            "farmhash128" | "farm128" => crate::farm::Hash128 as FarmHash128,
+
+            #[cfg(feature = "highway")]
+            "highwayhash64" | "highway64" => crate::highway::Hash64 as HighwayHash64,
+            #[cfg(feature = "highway")]
+            "highwayhash128" | "highway128" => crate::highway::Hash128 as HighwayHash128,
+
+            #[cfg(feature = "lookup3")]
+            "lookup3" => crate::lookup3::Hash32 as Lookup3,
+
+            #[cfg(all(feature = "metro", feature = "aes"))]
+            "meowhash128" | "meow128" => crate::meow::Hash128,
+
+            #[cfg(feature = "metro")]
+            "metrohash64" | "metro64" => crate::metro::Hash64_2 as MetroHash64,
+            #[cfg(feature = "metro")]
+            "metrohash64_1" | "metro64_1" => crate::metro::Hash64_1,
+            #[cfg(feature = "metro")]
+            "metrohash128" | "metro128" => crate::metro::Hash128_2 as MetroHash128,
+            #[cfg(feature = "metro")]
+            "metrohash128_1" | "metro128_1" => crate::metro::Hash128_1,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            "metrohash64_crc_1" | "metro64_crc_1" => crate::metro::crc::Hash64_1,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            "metrohash64_crc_2" | "metro64_crc_2" => crate::metro::crc::Hash64_2,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            "metrohash128_crc_1" | "metro128_crc_1" => crate::metro::crc::Hash128_1,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            "metrohash128_crc_2" | "metro128_crc_2" => crate::metro::crc::Hash128_2,
+
+            #[cfg(feature = "mum")]
+            "mumhash64" | "mum64" => crate::mum::Hash64 as MumHash64,
+
+            #[cfg(feature = "murmur")]
+            "murmurhash1_32" | "murmur1_32" => crate::murmur::Hash32 as MurmurHash1_32,
+            #[cfg(feature = "murmur")]
+            "murmurhash1_32_aligned" => crate::murmur::Hash32Aligned,
+            #[cfg(feature = "murmur")]
+            "murmurhash2_32" | "murmur2_32" => crate::murmur2::Hash32 as MurmurHash2_32,
+            #[cfg(feature = "murmur")]
+            "murmurhash2_32a" => crate::murmur2::Hash32A,
+            #[cfg(feature = "murmur")]
+            "murmurhash2_32_neutral" => crate::murmur2::Hash32Neutral,
+            #[cfg(feature = "murmur")]
+            "murmurhash2_32_aligned" => crate::murmur2::Hash32Aligned,
+            #[cfg(feature = "murmur")]
+            "murmurhash2_64" | "murmur2_x64_64" => crate::murmur2::Hash64_x64 as MurmurHash2_64,
+            #[cfg(feature = "murmur")]
+            "murmurhash2_64_x86" | "murmur2_x86_64" => crate::murmur2::Hash64_x86,
+            #[cfg(feature = "murmur")]
+            "murmurhash3_32" | "murmur3_32" => crate::murmur3::Hash32 as MurmurHash3_32,
+            #[cfg(feature = "murmur")]
+            "murmurhash3_128_x86" | "murmur3_x86_128" => crate::murmur3::Hash128_x86,
+            #[cfg(feature = "murmur")]
+            "murmurhash3_128" | "murmur3_x64_128" => crate::murmur3::Hash128_x64 as MurmurHash3_128,
+
+            #[cfg(feature = "nmhash")]
+            "nmhash32" => crate::nmhash::Hash32 as NmHash32,
+            #[cfg(feature = "nmhash")]
+            "nmhash32x" => crate::nmhash::Hash32X as NmHash32X,
+
+            #[cfg(feature = "pengy")]
+            "pengyhash64" | "pengy64" => crate::pengy::Hash64 as PengyHash64,
+
+            #[cfg(feature = "seahash")]
+            "seahash64" | "sea64" => crate::sea::Hash64 as SeaHash64,
+
+            #[cfg(feature = "siphash")]
+            "siphash13" | "sip13" => crate::siphash::SipHash13 as SipHash13,
+            #[cfg(feature = "siphash")]
+            "siphash24" | "sip24" => crate::siphash::SipHash24 as SipHash24,
+
+            #[cfg(feature = "spooky")]
+            "spookyhash32" | "spooky32" => crate::spooky::Hash32 as SpookyHash32,
+            #[cfg(feature = "spooky")]
+            "spookyhash64" | "spooky64" => crate::spooky::Hash64 as SpookyHash64,
+            #[cfg(feature = "spooky")]
+            "spookyhash128" | "spooky128" => crate::spooky::Hash128 as SpookyHash128,
+
+            #[cfg(feature = "t1ha")]
+            "t1ha0_64" => crate::t1ha0::Hash64 as T1ha0_64,
+            #[cfg(feature = "t1ha")]
+            "t1ha0_32" => crate::t1ha0::Hash32,
+            #[cfg(feature = "t1ha")]
+            "t1ha0_32le" => crate::t1ha0::Hash64_32Le,
+            #[cfg(feature = "t1ha")]
+            "t1ha0_32be" => crate::t1ha0::Hash64_32Be,
+            // `t1ha1_64` is the native byte order, like `t1ha1::Hash64`
+            #[cfg(all(feature = "t1ha", target_endian = "little"))]
+            "t1ha1_64" | "t1ha1_64le" => crate::t1ha1::Hash64Le as T1ha1_64,
+            #[cfg(all(feature = "t1ha", target_endian = "little"))]
+            "t1ha1_64be" => crate::t1ha1::Hash64Be,
+            #[cfg(all(feature = "t1ha", target_endian = "big"))]
+            "t1ha1_64le" => crate::t1ha1::Hash64Le,
+            #[cfg(all(feature = "t1ha", target_endian = "big"))]
+            "t1ha1_64" | "t1ha1_64be" => crate::t1ha1::Hash64Be as T1ha1_64,
+            #[cfg(feature = "t1ha")]
+            "t1ha2_64" => crate::t1ha2::Hash64AtOnce,
+            #[cfg(feature = "t1ha")]
+            "t1ha2_128" => crate::t1ha2::Hash128AtOnce as T1ha2_128,
+
+            #[cfg(feature = "umash")]
+            "umash64" => crate::umash::Hash64 as Umash64,
+            #[cfg(feature = "umash")]
+            "umash128" => crate::umash::Hash128 as Umash128,
+
+            #[cfg(feature = "wy")]
+            "wyhash64" | "wy64" => crate::wy::Hash64 as WyHash64,
+
+            #[cfg(feature = "xx")]
+            "xxhash32" | "xxh32" => crate::xx::Hash32 as XXHash32,
+            #[cfg(feature = "xx")]
+            "xxhash64" | "xxh64" => crate::xx::Hash64 as XXHash64,
+            #[cfg(feature = "xx")]
+            "xxh3_64" => crate::xxh3::Hash64 as XXH3_64,
+            #[cfg(feature = "xx")]
+            "xxh3_128" => crate::xxh3::Hash128 as XXH3_128,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::any::TypeId;
+
+    use num_traits::ToPrimitive;
+
+    use crate::hasher::FastHash;
+
+    macro_rules! registered {
+        ($( #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path $(as $variant:ident)?, )*) => {{
+            let mut hashes: Vec<(&'static str, TypeId)> = Vec::new();
+
+            $(
+                #[cfg($cfg)]
+                hashes.push(($name, TypeId::of::<$hash>()));
+            )*
+
+            hashes
+        }};
+    }
+
+    macro_rules! registered_names {
+        ($( #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path $(as $variant:ident)?, )*) => {{
+            let mut names: Vec<&'static str> = Vec::new();
+
+            $(
+                #[cfg($cfg)]
+                names.extend(&[$name $(, $alias)*]);
+            )*
+
+            names
+        }};
+    }
+
+    macro_rules! registered_hashes {
+        ($key:ident, $( #[cfg($cfg:meta)] $name:literal $(| $alias:literal)* => $hash:path $(as $variant:ident)?, )*) => {{
+            let mut hashes: Vec<(&'static str, u128)> = Vec::new();
+
+            $(
+                #[cfg($cfg)]
+                hashes.push(($name, <$hash as FastHash>::hash($key).to_u128().unwrap()));
+            )*
+
+            hashes
+        }};
+    }
+
+    #[test]
+    fn test_registry_count() {
+        let hashes = fast_hashes!(registered);
+
+        // the number of `FastHash` types each module exports
+        let mut expected = 0;
+
+        #[cfg(feature = "ahash")]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "city")]
+        {
+            expected += 4;
+        }
+        #[cfg(all(feature = "clhash", target_arch = "x86_64"))]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "farm")]
+        {
+            expected += 3;
+        }
+        #[cfg(feature = "highway")]
+        {
+            expected += 2;
+        }
+        #[cfg(feature = "lookup3")]
+        {
+            expected += 1;
+        }
+        #[cfg(all(feature = "metro", feature = "aes"))]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "metro")]
+        {
+            expected += 4;
+        }
+        #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+        {
+            expected += 4;
+        }
+        #[cfg(feature = "mum")]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "murmur")]
+        {
+            expected += 2 + 6 + 3;
+        }
+        #[cfg(feature = "nmhash")]
+        {
+            expected += 2;
+        }
+        #[cfg(feature = "pengy")]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "seahash")]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "siphash")]
+        {
+            expected += 2;
+        }
+        #[cfg(feature = "spooky")]
+        {
+            expected += 3;
+        }
+        #[cfg(feature = "t1ha")]
+        {
+            expected += 4 + 2 + 2;
+        }
+        #[cfg(feature = "umash")]
+        {
+            expected += 2;
+        }
+        #[cfg(feature = "wy")]
+        {
+            expected += 1;
+        }
+        #[cfg(feature = "xx")]
+        {
+            expected += 2 + 2;
+        }
+
+        assert_eq!(hashes.len(), expected);
+
+        // every hash function is registered once
+        for (i, &(name, id)) in hashes.iter().enumerate() {
+            for &(_, other_id) in &hashes[..i] {
+                assert_ne!(id, other_id, "{} is registered twice", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry_names() {
+        let names = fast_hashes!(registered_names);
+
+        // the names and aliases are selected ignoring ASCII case, so they are unique without it
+        for (i, name) in names.iter().enumerate() {
+            for other_name in &names[..i] {
+                assert!(
+                    !name.eq_ignore_ascii_case(other_name),
+                    "{} is registered twice",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry_hashes() {
+        const KEY: &[u8] = b"hello world";
+
+        let hashes = fast_hashes!(registered_hashes, KEY);
+        let hash = |name: &str| {
+            hashes
+                .iter()
+                .find(|&&(other_name, _)| other_name == name)
+                .map(|&(_, hash)| hash)
+                .unwrap_or_else(|| panic!("{} isn't registered", name))
+        };
+
+        // the module functions, with the registered name of the hash function they call
+        let mut functions: Vec<(&str, u128)> = Vec::new();
+
+        #[cfg(feature = "city")]
+        {
+            functions.push(("cityhash32", crate::city::hash32(KEY).into()));
+            functions.push(("cityhash64", crate::city::hash64(KEY).into()));
+            functions.push(("cityhash128_crc", crate::city::hash128(KEY)));
+        }
+        #[cfg(all(feature = "clhash", target_arch = "x86_64"))]
+        {
+            functions.push(("clhash64", crate::clhash::hash64(KEY).into()));
+        }
+        #[cfg(feature = "farm")]
+        {
+            functions.push(("farmhash32", crate::farm::hash32(KEY).into()));
+            functions.push(("farmhash64", crate::farm::hash64(KEY).into()));
+            functions.push(("farmhash128", crate::farm::hash128(KEY)));
+        }
+        #[cfg(feature = "highway")]
+        {
+            functions.push(("highwayhash64", crate::highway::hash64(KEY).into()));
+            functions.push(("highwayhash128", crate::highway::hash128(KEY)));
+        }
+        #[cfg(feature = "lookup3")]
+        {
+            functions.push(("lookup3", crate::lookup3::hash32(KEY).into()));
+        }
+        #[cfg(all(feature = "metro", feature = "aes"))]
+        {
+            functions.push(("meowhash128", crate::meow::hash128(KEY)));
+        }
+        #[cfg(feature = "metro")]
+        {
+            functions.push(("metrohash64", crate::metro::hash64(KEY).into()));
+            functions.push(("metrohash128", crate::metro::hash128(KEY)));
+        }
+        #[cfg(feature = "mum")]
+        {
+            functions.push(("mumhash64", crate::mum::hash64(KEY).into()));
+        }
+        #[cfg(feature = "murmur")]
+        {
+            let (murmur2_64, murmur3_128) = if cfg!(target_pointer_width = "64") {
+                ("murmurhash2_64", "murmurhash3_128")
+            } else {
+                ("murmurhash2_64_x86", "murmurhash3_128_x86")
+            };
+
+            functions.push(("murmurhash1_32", crate::murmur::hash32(KEY).into()));
+            functions.push((
+                "murmurhash1_32_aligned",
+                crate::murmur::hash32_aligned(KEY).into(),
+            ));
+            functions.push(("murmurhash2_32", crate::murmur2::hash32(KEY).into()));
+            functions.push(("murmurhash2_32a", crate::murmur2::hash32a(KEY).into()));
+            functions.push((murmur2_64, crate::murmur2::hash64(KEY).into()));
+            functions.push(("murmurhash3_32", crate::murmur3::hash32(KEY).into()));
+            functions.push((murmur3_128, crate::murmur3::hash128(KEY)));
+            functions.push(("murmurhash3_128_x86", crate::murmur3::hash128_x86(KEY)));
+            functions.push(("murmurhash3_128", crate::murmur3::hash128_x64(KEY)));
+        }
+        #[cfg(feature = "nmhash")]
+        {
+            functions.push(("nmhash32", crate::nmhash::hash32(KEY).into()));
+            functions.push(("nmhash32x", crate::nmhash::hash32x(KEY).into()));
+        }
+        #[cfg(feature = "pengy")]
+        {
+            functions.push(("pengyhash64", crate::pengy::hash64(KEY).into()));
+        }
+        #[cfg(feature = "siphash")]
+        {
+            functions.push(("siphash13", crate::siphash::hash64_13(KEY).into()));
+            functions.push(("siphash24", crate::siphash::hash64_24(KEY).into()));
+        }
+        #[cfg(feature = "spooky")]
+        {
+            functions.push(("spookyhash32", crate::spooky::hash32(KEY).into()));
+            functions.push(("spookyhash64", crate::spooky::hash64(KEY).into()));
+            functions.push(("spookyhash128", crate::spooky::hash128(KEY)));
+        }
+        #[cfg(feature = "t1ha")]
+        {
+            functions.push(("t1ha2_64", crate::t1ha::hash64(KEY).into()));
+        }
+        #[cfg(feature = "umash")]
+        {
+            functions.push(("umash64", crate::umash::hash64(KEY).into()));
+            functions.push(("umash128", crate::umash::hash128(KEY)));
+        }
+        #[cfg(feature = "wy")]
+        {
+            functions.push(("wyhash64", crate::wy::hash64(KEY).into()));
+        }
+        #[cfg(feature = "xx")]
+        {
+            functions.push(("xxhash32", crate::xx::hash32(KEY).into()));
+            functions.push(("xxhash64", crate::xx::hash64(KEY).into()));
+            functions.push(("xxh3_64", crate::xxh3::hash64(KEY).into()));
+            functions.push(("xxh3_128", crate::xxh3::hash128(KEY)));
+        }
+
+        for &(name, expected) in &functions {
+            assert_eq!(hash(name), expected, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Consistent hashing on a ring of 64-bit positions
+//!
+//! Every key and every node is mapped to a position on a ring of `2^64` positions,
+//! and a key is assigned to the first node at or after its position, wrapping
+//! around the ring. When a node is removed, only the keys assigned to it move to
+//! the next node, the other keys keep their assignment.
+//!
+//! The positions are computed with the 64-bit `xxHash`, which is stable across
+//! platforms and releases, so the assignment could be shared between processes.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::ring;
+//!
+//! let nodes = ["cache-a", "cache-b", "cache-c"]
+//!     .iter()
+//!     .map(|name| ring::position(name.as_bytes(), 0))
+//!     .collect::<Vec<_>>();
+//!
+//! let node = ring::assign(b"user:42", &nodes);
+//!
+//! assert!(node < nodes.len());
+//! assert_eq!(node, ring::assign(b"user:42", &nodes));
+//! ```
+use crate::xx;
+
+/// Returns the stable position of a key on the ring.
+#[inline(always)]
+pub fn position<T: AsRef<[u8]>>(key: T, seed: u64) -> u64 {
+    xx::hash64_with_seed(key, seed)
+}
+
+/// Returns the index of the node which the key is assigned to.
+///
+/// The key is placed at `position(key, 0)` and assigned to the node with the smallest
+/// position at or after it, wrapping around to the node with the smallest position.
+/// If several nodes share a position, the first one is used.
+///
+/// # Panics
+///
+/// Panics if `nodes` is empty.
+pub fn assign<T: AsRef<[u8]>>(key: T, nodes: &[u64]) -> usize {
+    assign_position(position(key, 0), nodes)
+}
+
+/// Returns the index of the node which the position is assigned to.
+///
+/// # Panics
+///
+/// Panics if `nodes` is empty.
+pub fn assign_position(pos: u64, nodes: &[u64]) -> usize {
+    assert!(!nodes.is_empty(), "no nodes on the ring");
+
+    let mut next: Option<(u64, usize)> = None;
+    let mut first = (nodes[0], 0);
+
+    for (idx, &node) in nodes.iter().enumerate() {
+        if node >= pos && next.map_or(true, |(p, _)| node < p) {
+            next = Some((node, idx));
+        }
+        if node < first.0 {
+            first = (node, idx);
+        }
+    }
+
+    next.unwrap_or(first).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_wrapping() {
+        let nodes = [100, 300, 200];
+
+        assert_eq!(assign_position(0, &nodes), 0);
+        assert_eq!(assign_position(100, &nodes), 0);
+        assert_eq!(assign_position(101, &nodes), 2);
+        assert_eq!(assign_position(250, &nodes), 1);
+        assert_eq!(assign_position(301, &nodes), 0);
+        assert_eq!(assign_position(u64::max_value(), &nodes), 0);
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let nodes = (0..10)
+            .map(|i| position(format!("node-{}", i), 0))
+            .collect::<Vec<_>>();
+        let removed = 3;
+        let remains = nodes
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| idx != removed)
+            .map(|(_, &node)| node)
+            .collect::<Vec<_>>();
+
+        let mut moved = 0;
+
+        for i in 0..1000 {
+            let key = format!("key-{}", i);
+            let before = nodes[assign(&key, &nodes)];
+            let after = remains[assign(&key, &remains)];
+
+            if before == nodes[removed] {
+                moved += 1;
+            } else {
+                assert_eq!(before, after, "{} was remapped", key);
+            }
+        }
+
+        assert!(moved > 0);
+    }
+}
@@ -0,0 +1,295 @@
+//! Rolling hash of a sliding window, e.g. for the Rabin-Karp substring search
+//!
+//! `RollingHasher` keeps a polynomial hash of the bytes of a window, modulo the Mersenne
+//! prime `2^61 - 1`, with a random base drawn from `Seed`. Appending a byte at the end
+//! or removing the byte at the front of the window updates the hash in constant time,
+//! which the hash functions of the crate can't do, so the wrapped hash function only
+//! finishes the polynomial hash into its output, it doesn't hash the window bytes.
+//!
+//! Two windows with the same bytes have the same hash for the same seed,
+//! and different windows collide with a probability of about `len / 2^61`.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::{rolling::RollingHasher, xx};
+//!
+//! let text = b"the quick brown fox jumps over the lazy dog";
+//! let pattern = b"fox";
+//!
+//! let mut needle = RollingHasher::<xx::Hash64>::new();
+//! let mut window = RollingHasher::<xx::Hash64>::new();
+//!
+//! pattern.iter().for_each(|&b| needle.append(b));
+//!
+//! let mut found = None;
+//!
+//! for (i, &b) in text.iter().enumerate() {
+//!     window.append(b);
+//!
+//!     if window.len() > pattern.len() {
+//!         window.remove_front(text[i - pattern.len()]);
+//!     }
+//!     if window.len() == pattern.len()
+//!         && window.finish() == needle.finish()
+//!         && &text[i + 1 - pattern.len()..=i] == pattern
+//!     {
+//!         found = Some(i + 1 - pattern.len());
+//!         break;
+//!     }
+//! }
+//!
+//! assert_eq!(found, Some(16));
+//! ```
+use core::marker::PhantomData;
+
+use rand::Rng;
+use xoroshiro128::Xoroshiro128Rng;
+
+use crate::hasher::{FastHash, Seed};
+
+/// The modulus of the polynomial hash, the Mersenne prime `2^61 - 1`.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// The seed of the hashers constructed by `RollingHasher::new`.
+const DEFAULT_SEED: [u64; 2] = [0x726f_6c6c_696e_6721, 0x9e37_79b9_7f4a_7c15];
+
+#[inline(always)]
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let x = u128::from(a) * u128::from(b);
+    let r = (x as u64 & MODULUS) + (x >> 61) as u64;
+
+    if r >= MODULUS {
+        r - MODULUS
+    } else {
+        r
+    }
+}
+
+#[inline(always)]
+fn add_mod(a: u64, b: u64) -> u64 {
+    let r = a + b;
+
+    if r >= MODULUS {
+        r - MODULUS
+    } else {
+        r
+    }
+}
+
+#[inline(always)]
+fn sub_mod(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + MODULUS - b
+    }
+}
+
+fn pow_mod(mut base: u64, mut exp: u64) -> u64 {
+    let mut r = 1;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            r = mul_mod(r, base);
+        }
+        base = mul_mod(base, base);
+        exp >>= 1;
+    }
+
+    r
+}
+
+/// A polynomial rolling hash of a sliding window of bytes.
+///
+/// The byte `b` of the window is the digit `b + 1`, so the windows which only differ
+/// by their leading zero bytes still hash differently.
+#[derive(Clone)]
+pub struct RollingHasher<H: FastHash> {
+    base: u64,
+    inv_base: u64,
+    seed: H::Seed,
+    hash: u64,
+    // `base` to the power of `len`
+    pow: u64,
+    len: usize,
+    _hash: PhantomData<H>,
+}
+
+impl<H: FastHash> RollingHasher<H>
+where
+    H::Seed: From<Seed>,
+{
+    /// Constructs a hasher of an empty window with a fixed seed,
+    /// so the hashes are comparable across processes.
+    pub fn new() -> Self {
+        Self::with_seed(Seed::from_words(
+            Xoroshiro128Rng::from_seed_u64(DEFAULT_SEED).gen(),
+        ))
+    }
+
+    /// Constructs a hasher of an empty window with the base and the seed of
+    /// the hash function derived from the seed.
+    pub fn with_seed(seed: Seed) -> Self {
+        let (base, seed) = seed.split();
+        // a base in `2..MODULUS`, so it is invertible and not trivial
+        let base = u64::from(base) % (MODULUS - 2) + 2;
+
+        RollingHasher {
+            base,
+            inv_base: pow_mod(base, MODULUS - 2),
+            seed: seed.into(),
+            hash: 0,
+            pow: 1,
+            len: 0,
+            _hash: PhantomData,
+        }
+    }
+}
+
+impl<H: FastHash> Default for RollingHasher<H>
+where
+    H::Seed: From<Seed>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: FastHash> RollingHasher<H> {
+    /// Appends a byte at the end of the window.
+    #[inline]
+    pub fn append(&mut self, byte: u8) {
+        self.hash = add_mod(mul_mod(self.hash, self.base), u64::from(byte) + 1);
+        self.pow = mul_mod(self.pow, self.base);
+        self.len += 1;
+    }
+
+    /// Removes the byte at the front of the window.
+    ///
+    /// The hasher doesn't keep the bytes of the window, so the caller passes the removed byte,
+    /// the hash is meaningless if it isn't the first byte of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window is empty.
+    #[inline]
+    pub fn remove_front(&mut self, byte: u8) {
+        assert!(self.len > 0, "removed a byte from an empty window");
+
+        self.pow = mul_mod(self.pow, self.inv_base);
+        self.hash = sub_mod(self.hash, mul_mod(u64::from(byte) + 1, self.pow));
+        self.len -= 1;
+    }
+
+    /// Returns the number of bytes in the window.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the window is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the window, keeping the seed.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.hash = 0;
+        self.pow = 1;
+        self.len = 0;
+    }
+
+    /// Returns the polynomial hash of the window, below `2^61 - 1`.
+    #[inline(always)]
+    pub fn value(&self) -> u64 {
+        self.hash
+    }
+
+    /// Finishes the polynomial hash of the window with the hash function.
+    #[inline(always)]
+    pub fn finish(&self) -> H::Hash {
+        H::hash_with_seed(self.hash.to_le_bytes(), self.seed)
+    }
+}
+
+#[cfg(all(test, feature = "xx"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::xx;
+
+    // hashes the window from scratch, with the definition of the polynomial hash
+    fn brute_force(h: &RollingHasher<xx::Hash64>, window: &[u8]) -> u64 {
+        window.iter().fold(0, |hash, &b| {
+            ((u128::from(hash) * u128::from(h.base) + u128::from(b) + 1) % u128::from(MODULUS))
+                as u64
+        })
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let data = (0..2000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect::<Vec<_>>();
+
+        for &width in &[1, 7, 64] {
+            let mut h = RollingHasher::<xx::Hash64>::with_seed(Seed::gen());
+
+            for (i, &b) in data.iter().enumerate() {
+                h.append(b);
+
+                if i >= width {
+                    h.remove_front(data[i - width]);
+                }
+
+                let window = &data[(i + 1).saturating_sub(width)..=i];
+                let mut fresh = h.clone();
+
+                fresh.clear();
+                window.iter().for_each(|&b| fresh.append(b));
+
+                assert_eq!(h.len(), window.len());
+                assert_eq!(
+                    h.value(),
+                    brute_force(&h, window),
+                    "width {}, at {}",
+                    width,
+                    i
+                );
+                assert_eq!(h.finish(), fresh.finish());
+            }
+        }
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let mut a = RollingHasher::<xx::Hash64>::new();
+        let mut b = RollingHasher::<xx::Hash64>::new();
+
+        a.append(0);
+        a.append(1);
+        b.append(1);
+
+        assert_ne!(a.value(), b.value());
+
+        a.remove_front(0);
+
+        assert_eq!(a.value(), b.value());
+        assert_eq!(a.finish(), b.finish());
+
+        a.remove_front(1);
+
+        assert!(a.is_empty());
+        assert_eq!(a.value(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty window")]
+    fn test_remove_from_empty() {
+        RollingHasher::<xx::Hash64>::new().remove_front(0);
+    }
+}
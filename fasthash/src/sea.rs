@@ -27,10 +27,14 @@
 //!
 pub use seahash::{hash as hash64, hash_seeded as hash64_with_seeds, SeaHasher as Hasher64};
 
-use crate::hasher::{FastHash, FastHasher, StreamHasher};
+#[cfg(feature = "std")]
+use crate::hasher::StreamHasher;
+use crate::hasher::{FastHash, FastHasher};
 
 /// `SeaHash` 64-bit hash functions
 ///
+/// The seed `(k1, k2, k3, k4)` is passed in order to `seahash::hash_seeded`.
+///
 /// # Example
 ///
 /// ```
@@ -78,13 +82,14 @@ impl FastHasher for Hasher64 {
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for Hasher64 {}
 
 #[cfg(test)]
 mod tests {
     use std::hash::Hasher;
 
-    use super::Hasher64;
+    use super::*;
 
     #[test]
     fn test_seahash64() {
@@ -96,4 +101,17 @@ mod tests {
         h.write(b"world");
         assert_eq!(h.finish(), 9532038143498849405);
     }
+
+    #[test]
+    fn test_seed_slots() {
+        let h = seahash::hash_seeded(b"hello", 1, 2, 3, 4);
+
+        assert_eq!(Hash64::hash_with_seed(b"hello", (1, 2, 3, 4)), h);
+        assert_ne!(Hash64::hash_with_seed(b"hello", (2, 1, 3, 4)), h);
+        assert_ne!(Hash64::hash_with_seed(b"hello", (1, 2, 4, 3)), h);
+
+        let mut hasher = <Hasher64 as FastHasher>::with_seed((1, 2, 3, 4));
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish(), h);
+    }
 }
@@ -0,0 +1,463 @@
+//! Deterministic hashing of `serde::Serialize` values
+//!
+//! `hash_serialized` serializes a value straight into a hasher, without an intermediate
+//! format like JSON, and writes every element in a self-delimiting frame,
+//! like the `Framed` trait does for composite keys:
+//!
+//! - a `bool` is written as one byte, `0` or `1`,
+//! - an integer is written as its fixed-width little endian bytes,
+//! - a float is written as the little endian bytes of its bits,
+//!   a `char` as a 32-bit little endian integer,
+//! - a string or byte array is prefixed with its length as a 64-bit little endian integer,
+//! - an `Option` is written as a `0` byte for `None`, or a `1` byte followed by the value,
+//! - a unit or unit struct writes nothing, a newtype struct writes its value,
+//! - a tuple or tuple struct writes its elements in order,
+//! - a sequence or map is prefixed with the number of elements as a 64-bit little endian
+//!   integer, followed by the elements, or the keys and values in turn,
+//! - a struct writes the name of every field, framed like a string, followed by its value,
+//! - an enum variant is prefixed with its index as a 32-bit little endian integer,
+//!   followed by its content, like a struct of the same kind.
+//!
+//! The names of the types and variants are not written, but the names of the fields are,
+//! so reordering or renaming the fields changes the hash value.
+//! The framing is part of the hash value, and will not change between releases.
+//!
+//! # Example
+//!
+//! ```
+//! use serde::Serialize;
+//!
+//! use fasthash::{serialized::hash_serialized, xx};
+//!
+//! #[derive(Serialize)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let h = hash_serialized::<_, xx::Hasher64>(&Point { x: 1, y: 2 }).unwrap();
+//!
+//! assert_eq!(h, hash_serialized::<_, xx::Hasher64>(&Point { x: 1, y: 2 }).unwrap());
+//! assert_ne!(h, hash_serialized::<_, xx::Hasher64>(&Point { x: 2, y: 1 }).unwrap());
+//! ```
+use std::error;
+use std::fmt;
+use std::hash::Hasher;
+
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::framed::Framed;
+use crate::hasher::FastHasher;
+
+/// Hashes the serialized value with a new hasher.
+///
+/// # Errors
+///
+/// Fails if the value fails to serialize,
+/// or serializes a sequence or map of unknown length.
+pub fn hash_serialized<T, H>(value: &T) -> Result<u64, Error>
+where
+    T: Serialize + ?Sized,
+    H: FastHasher,
+{
+    let mut h = H::new();
+
+    write_serialized(value, &mut h)?;
+
+    Ok(h.finish())
+}
+
+/// Writes the serialized value into the hasher.
+///
+/// # Errors
+///
+/// Fails if the value fails to serialize,
+/// or serializes a sequence or map of unknown length.
+pub fn write_serialized<T, H>(value: &T, h: &mut H) -> Result<(), Error>
+where
+    T: Serialize + ?Sized,
+    H: Hasher,
+{
+    value.serialize(&mut FrameSerializer { h })
+}
+
+/// The error returned when a value fails to serialize into a hasher.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+struct FrameSerializer<'a, H> {
+    h: &'a mut H,
+}
+
+impl<'a, H: Hasher> FrameSerializer<'a, H> {
+    #[inline(always)]
+    fn write_len(&mut self, len: Option<usize>) -> Result<(), Error> {
+        let len = len.ok_or_else(|| Error("the length must be known in advance".into()))?;
+
+        len.write_framed(self.h);
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, H: Hasher> Serializer for &'b mut FrameSerializer<'a, H> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        (v as u8).write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        v.to_bits().write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        v.to_bits().write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        (v as u32).write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        v.write_framed(self.h);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_u8(0)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        (&mut *self).serialize_u8(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        variant_index.write_framed(self.h);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Error> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        variant_index.write_framed(self.h);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Error> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        variant_index.write_framed(self.h);
+        Ok(self)
+    }
+}
+
+macro_rules! impl_serialize_elements {
+    ($($trait:ident :: $method:ident),*) => {$(
+        impl<'a, 'b, H: Hasher> ser::$trait for &'b mut FrameSerializer<'a, H> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_serialize_elements! {
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+}
+
+impl<'a, 'b, H: Hasher> ser::SerializeMap for &'b mut FrameSerializer<'a, H> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_serialize_fields {
+    ($($trait:ident),*) => {$(
+        impl<'a, 'b, H: Hasher> ser::$trait for &'b mut FrameSerializer<'a, H> {
+            type Ok = ();
+            type Error = Error;
+
+            fn serialize_field<T: Serialize + ?Sized>(
+                &mut self,
+                key: &'static str,
+                value: &T,
+            ) -> Result<(), Error> {
+                key.write_framed(self.h);
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_serialize_fields! { SerializeStruct, SerializeStructVariant }
+
+#[cfg(all(test, feature = "xx"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::*;
+    use crate::xx;
+
+    #[derive(Serialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Serialize)]
+    struct ReorderedPoint {
+        y: i32,
+        x: i32,
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(Point, u32),
+        Named { name: String, points: Vec<Point> },
+    }
+
+    fn hash<T: Serialize + ?Sized>(value: &T) -> u64 {
+        hash_serialized::<_, xx::Hasher64>(value).unwrap()
+    }
+
+    #[test]
+    fn test_framing() {
+        let mut h = xx::Hasher64::new();
+
+        "x".write_framed(&mut h);
+        1i32.write_framed(&mut h);
+        "y".write_framed(&mut h);
+        2i32.write_framed(&mut h);
+
+        assert_eq!(hash(&Point { x: 1, y: 2 }), h.finish());
+        assert_eq!(
+            hash(&(1i32, 2i32)),
+            xx::hash64(&[1u8, 0, 0, 0, 2, 0, 0, 0][..])
+        );
+        assert_eq!(hash(&Some(1u8)), xx::hash64(&[1u8, 1][..]));
+        assert_eq!(hash(&None::<u8>), xx::hash64(&[0u8][..]));
+    }
+
+    #[test]
+    fn test_field_order() {
+        assert_eq!(hash(&Point { x: 1, y: 2 }), hash(&Point { x: 1, y: 2 }));
+        assert_ne!(hash(&Point { x: 1, y: 2 }), hash(&Point { x: 2, y: 1 }));
+        assert_ne!(
+            hash(&Point { x: 1, y: 2 }),
+            hash(&ReorderedPoint { y: 2, x: 1 })
+        );
+    }
+
+    #[test]
+    fn test_unambiguous() {
+        assert_ne!(hash(&("ab", "c")), hash(&("a", "bc")));
+        assert_ne!(
+            hash(&vec![vec![1u8], vec![]]),
+            hash(&vec![vec![], vec![1u8]])
+        );
+        assert_ne!(hash(&Shape::Empty), hash(&()));
+
+        let shapes = [
+            Shape::Empty,
+            Shape::Circle(Point { x: 0, y: 0 }, 1),
+            Shape::Named {
+                name: "line".into(),
+                points: vec![Point { x: 0, y: 0 }, Point { x: 1, y: 1 }],
+            },
+        ];
+
+        assert_ne!(hash(&shapes[..2]), hash(&shapes[1..]));
+
+        let map = (0..10)
+            .map(|i| (i, i.to_string()))
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(hash(&map), hash(&map.clone()));
+    }
+
+    #[test]
+    fn test_unknown_length() {
+        struct Unsized;
+
+        impl Serialize for Unsized {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq((0..3).filter(|i| i % 2 == 0))
+            }
+        }
+
+        assert!(hash_serialized::<_, xx::Hasher64>(&Unsized).is_err());
+    }
+}
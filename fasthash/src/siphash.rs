@@ -0,0 +1,227 @@
+//! `SipHash`: a fast short-input PRF
+//!
+//! by Jean-Philippe Aumasson and Daniel J. Bernstein
+//!
+//! https://github.com/veorq/SipHash
+//!
+//! `SipHash` is keyed and resists `HashDoS` attacks, unlike the other hash functions
+//! of the crate, at the cost of throughput. `SipHash-1-3` is the default hasher of `std`,
+//! and `SipHash-2-4` is the original one, both are implemented in pure Rust here,
+//! so they could be compared with the fast hash functions through the same API.
+//!
+//! The 128-bit seed is the key, its low 64 bits are the first half of the key,
+//! i.e. the key bytes of the reference are `u128::to_le_bytes` of the seed.
+//!
+//! # Example
+//!
+//! ```
+//! use std::hash::{Hash, Hasher};
+//!
+//! use fasthash::{siphash, SipHasher13};
+//!
+//! fn hash<T: Hash>(t: &T) -> u64 {
+//!     let mut s: SipHasher13 = Default::default();
+//!     t.hash(&mut s);
+//!     s.finish()
+//! }
+//!
+//! let h = siphash::hash64_13(b"hello world\xff");
+//!
+//! assert_eq!(h, hash(&"hello world"));
+//! ```
+//!
+use core::convert::TryInto;
+
+use crate::hasher::FastHash;
+
+#[inline(always)]
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+#[inline(always)]
+fn compress(v: &mut [u64; 4], m: u64, rounds: usize) {
+    v[3] ^= m;
+    for _ in 0..rounds {
+        sip_round(v);
+    }
+    v[0] ^= m;
+}
+
+fn siphash(bytes: &[u8], key: u128, c_rounds: usize, d_rounds: usize) -> u64 {
+    let k0 = key as u64;
+    let k1 = (key >> 64) as u64;
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+    let mut blocks = bytes.chunks_exact(8);
+
+    for block in &mut blocks {
+        compress(
+            &mut v,
+            u64::from_le_bytes(block.try_into().unwrap()),
+            c_rounds,
+        );
+    }
+
+    // the last block holds the tail bytes, and the length modulo 256 in its top byte
+    let tail = blocks.remainder();
+    let mut last = [0_u8; 8];
+
+    last[..tail.len()].copy_from_slice(tail);
+    last[7] = bytes.len() as u8;
+    compress(&mut v, u64::from_le_bytes(last), c_rounds);
+
+    v[2] ^= 0xff;
+    for _ in 0..d_rounds {
+        sip_round(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+/// `SipHash-1-3` 64-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{siphash::SipHash13, FastHash};
+///
+/// assert_eq!(SipHash13::hash(b"hello"), 16350172494705860510);
+/// assert_eq!(SipHash13::hash_with_seed(b"hello", 456 << 64 | 123), 3762501369017507354);
+/// assert_eq!(SipHash13::hash(b"helloworld"), 1348462810646499051);
+/// ```
+#[derive(Clone, Default)]
+pub struct SipHash13;
+
+impl FastHash for SipHash13 {
+    type Hash = u64;
+    type Seed = u128;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u64 {
+        siphash(bytes.as_ref(), seed, 1, 3)
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{siphash::SipHasher13, FastHasher};
+    ///
+    /// let mut h = SipHasher13::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish(), 16350172494705860510);
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), 1348462810646499051);
+    /// ```
+    SipHasher13(SipHash13) -> u64
+}
+
+/// `SipHash-2-4` 64-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{siphash::SipHash24, FastHash};
+///
+/// assert_eq!(SipHash24::hash(b"hello"), 10142490492830962361);
+/// assert_eq!(SipHash24::hash_with_seed(b"hello", 456 << 64 | 123), 3917950836011642579);
+/// assert_eq!(SipHash24::hash(b"helloworld"), 3762648441266353690);
+/// ```
+#[derive(Clone, Default)]
+pub struct SipHash24;
+
+impl FastHash for SipHash24 {
+    type Hash = u64;
+    type Seed = u128;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u64 {
+        siphash(bytes.as_ref(), seed, 2, 4)
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{siphash::SipHasher24, FastHasher};
+    ///
+    /// let mut h = SipHasher24::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish(), 10142490492830962361);
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), 3762648441266353690);
+    /// ```
+    SipHasher24(SipHash24) -> u64
+}
+
+/// `SipHash-1-3` 64-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash64_13<T: AsRef<[u8]>>(v: T) -> u64 {
+    SipHash13::hash(v)
+}
+
+/// `SipHash-1-3` 64-bit hash function for a byte array.
+/// For convenience, a 128-bit key is also hashed into the result.
+#[inline(always)]
+pub fn hash64_13_with_key<T: AsRef<[u8]>>(v: T, key: u128) -> u64 {
+    SipHash13::hash_with_seed(v, key)
+}
+
+/// `SipHash-2-4` 64-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash64_24<T: AsRef<[u8]>>(v: T) -> u64 {
+    SipHash24::hash(v)
+}
+
+/// `SipHash-2-4` 64-bit hash function for a byte array.
+/// For convenience, a 128-bit key is also hashed into the result.
+#[inline(always)]
+pub fn hash64_24_with_key<T: AsRef<[u8]>>(v: T, key: u128) -> u64 {
+    SipHash24::hash_with_seed(v, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_reference_vectors() {
+        // the key 00 01 .. 0f and the messages 00 01 .. (len - 1) of the reference `vectors.h`
+        let key = u128::from_le_bytes([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+        let msg = (0..64u8).collect::<Vec<_>>();
+
+        assert_eq!(hash64_24_with_key(&msg[..0], key), 0x726f_db47_dd0e_0e31);
+        assert_eq!(hash64_24_with_key(&msg[..1], key), 0x74f8_39c5_93dc_67fd);
+        assert_eq!(hash64_24_with_key(&msg[..2], key), 0x0d6c_8009_d9a9_4f5a);
+        assert_eq!(hash64_24_with_key(&msg[..15], key), 0xa129_ca61_49be_45e5);
+        assert_eq!(hash64_24_with_key(&msg[..63], key), 0x958a_324c_eb06_4572);
+
+        assert_eq!(hash64_13_with_key(&msg[..0], key), 0xabac_0158_050f_c4dc);
+    }
+}
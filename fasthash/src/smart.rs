@@ -0,0 +1,91 @@
+//! Length based selection between `CityHash` and `FarmHash`
+//!
+//! `smart::hash64` hashes the short keys with `FarmHash`, and the keys of at least
+//! `CITY_MIN_LEN` bytes with `CityHash`. The two algorithms hash the same key
+//! to different values, so the algorithm is returned with the hash,
+//! and `AlgoId::hash64` reproduces it later.
+//!
+//! The threshold is a tuning constant, not part of any external format,
+//! but it won't change without a breaking release, since it changes the hash values.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::{farm, smart::{self, AlgoId}};
+//!
+//! let (h, algo) = smart::hash64(b"hello");
+//!
+//! assert_eq!(algo, AlgoId::Farm64);
+//! assert_eq!(h, farm::hash64(b"hello"));
+//! assert_eq!(algo.hash64(b"hello"), h);
+//! ```
+use crate::{city, farm};
+
+/// The length from which `hash64` selects `CityHash` instead of `FarmHash`.
+///
+/// It is the crossover of `city::hash64` and `farm::hash64` in the
+/// `smart::hash64 crossover` group of `benches/hash.rs`, run it with
+/// `cargo bench --bench hash -- crossover` before tuning the threshold.
+pub const CITY_MIN_LEN: usize = 64;
+
+/// The algorithm selected by `hash64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlgoId {
+    /// `city::hash64`
+    City64,
+    /// `farm::hash64`
+    Farm64,
+}
+
+impl AlgoId {
+    /// Returns the algorithm `hash64` selects for a key of `len` bytes.
+    #[inline(always)]
+    pub fn for_len(len: usize) -> AlgoId {
+        if len < CITY_MIN_LEN {
+            AlgoId::Farm64
+        } else {
+            AlgoId::City64
+        }
+    }
+
+    /// Hashes the key with the algorithm.
+    #[inline(always)]
+    pub fn hash64<T: AsRef<[u8]>>(self, v: T) -> u64 {
+        match self {
+            AlgoId::City64 => city::hash64(v),
+            AlgoId::Farm64 => farm::hash64(v),
+        }
+    }
+}
+
+/// Hashes a byte array with the algorithm selected by its length,
+/// returning the hash and the selected algorithm.
+#[inline(always)]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> (u64, AlgoId) {
+    let algo = AlgoId::for_len(v.as_ref().len());
+
+    (algo.hash64(v), algo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch() {
+        assert_eq!(AlgoId::for_len(0), AlgoId::Farm64);
+        assert_eq!(AlgoId::for_len(63), AlgoId::Farm64);
+        assert_eq!(AlgoId::for_len(64), AlgoId::City64);
+        assert_eq!(AlgoId::for_len(usize::MAX), AlgoId::City64);
+
+        assert_eq!(hash64(b"hello"), (14403600180753024522, AlgoId::Farm64));
+
+        let key = [0x5a_u8; 64];
+
+        assert_eq!(
+            hash64(&key[..63]),
+            (farm::hash64(&key[..63]), AlgoId::Farm64)
+        );
+        assert_eq!(hash64(&key[..]), (city::hash64(&key[..]), AlgoId::City64));
+    }
+}
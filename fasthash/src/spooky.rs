@@ -44,13 +44,16 @@
 //! assert_eq!(h as u64, hash(&"hello world"));
 //! ```
 //!
-use std::hash::Hasher;
-use std::os::raw::c_void;
-use std::ptr::NonNull;
+use core::ffi::c_void;
+use core::fmt;
+use core::hash::Hasher;
+use core::ptr::NonNull;
 
 use crate::ffi;
 
-use crate::hasher::{FastHash, FastHasher, HasherExt, StreamHasher};
+#[cfg(feature = "std")]
+use crate::hasher::StreamHasher;
+use crate::hasher::{FastHash, FastHasher, HasherExt, SpillState};
 
 /// `SpookyHash` 32-bit hash functions
 ///
@@ -104,7 +107,7 @@ trivial_hasher! {
     /// h.write(b"world");
     /// assert_eq!(h.finish(), 3874077464);
     /// ```
-    Hasher32(Hash32) -> u32
+    Hasher32(Hash32) -> u32, spill Hasher128
 }
 
 /// `SpookyHash` 64-bit hash functions
@@ -159,27 +162,38 @@ trivial_hasher! {
     /// h.write(b"world");
     /// assert_eq!(h.finish(), 18412934266828208920);
     /// ```
-    Hasher64(Hash64) -> u64
+    Hasher64(Hash64) -> u64, spill Hasher128
 }
 
 /// `SpookyHash` 128-bit hash functions
 ///
+/// The low 64 bits of the seed and the hash are the first `hash1` of the reference
+/// implementation, and the high 64 bits are the second `hash2`. `Hash32` and `Hash64`
+/// seed both halves with the same value and return the truncated `hash1`, so a 64-bit
+/// hash is the low half of the 128-bit hash with the seed repeated in both halves.
+///
 /// # Example
 ///
 /// ```
-/// use fasthash::{spooky::Hash128, FastHash};
+/// use fasthash::{spooky::{Hash128, Hash64}, FastHash};
 ///
 /// assert_eq!(
 ///     Hash128::hash(b"hello"),
-///     112634988270796077198737188616407610157
+///     302842287093856334275628846379354566592
 /// );
 /// assert_eq!(
 ///     Hash128::hash_with_seed(b"hello", 123),
-///     133968859623340440246086107642109008647
+///     189917703828281713534156897210392460365
 /// );
 /// assert_eq!(
 ///     Hash128::hash(b"helloworld"),
-///     339658686066216790682429200470429822413
+///     256109770468917419513039807727259990808
+/// );
+///
+/// assert_eq!(Hash128::hash(b"hello") as u64, Hash64::hash(b"hello"));
+/// assert_eq!(
+///     Hash128::hash_with_seed(b"hello", 123 << 64 | 123) as u64,
+///     Hash64::hash_with_seed(b"hello", 123)
 /// );
 /// ```
 #[derive(Clone, Default)]
@@ -191,24 +205,27 @@ impl FastHash for Hash128 {
 
     #[inline(always)]
     fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
-        let mut hi = (seed >> 64) as u64;
-        let mut lo = seed as u64;
+        let mut hash1 = seed as u64;
+        let mut hash2 = (seed >> 64) as u64;
 
         unsafe {
             ffi::SpookyHasherHash(
                 bytes.as_ref().as_ptr() as *const c_void,
                 bytes.as_ref().len(),
-                &mut hi,
-                &mut lo,
+                &mut hash1,
+                &mut hash2,
             );
         }
 
-        u128::from(hi).wrapping_shl(64) + u128::from(lo)
+        u128::from(hash2).wrapping_shl(64) + u128::from(hash1)
     }
 }
 
 /// An implementation of `std::hash::Hasher` and `fasthash::HasherExt`.
 ///
+/// The seed is the pair of `(hash1, hash2)`, and `finish_ext` returns them
+/// in the same halves as `Hash128`.
+///
 /// # Example
 ///
 /// ```
@@ -219,12 +236,11 @@ impl FastHash for Hash128 {
 /// let mut h = Hasher128::new();
 ///
 /// h.write(b"hello");
-/// assert_eq!(h.finish_ext(), 112634988270796077198737188616407610157);
+/// assert_eq!(h.finish_ext(), 302842287093856334275628846379354566592);
 ///
 /// h.write(b"world");
-/// assert_eq!(h.finish_ext(), 339658686066216790682429200470429822413);
+/// assert_eq!(h.finish_ext(), 256109770468917419513039807727259990808);
 /// ```
-#[derive(Clone)]
 pub struct Hasher128(NonNull<c_void>);
 
 impl Default for Hasher128 {
@@ -240,6 +256,64 @@ impl Drop for Hasher128 {
     }
 }
 
+impl fmt::Debug for Hasher128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Hasher128").finish()
+    }
+}
+
+impl Clone for Hasher128 {
+    fn clone(&self) -> Self {
+        unsafe {
+            Hasher128(NonNull::new_unchecked(ffi::SpookyHasherClone(
+                self.0.as_ptr(),
+            )))
+        }
+    }
+}
+
+impl Hasher128 {
+    /// Returns the 32-bit hash of the written bytes, the low 32 bits of `finish128`.
+    ///
+    /// The 32-bit, 64-bit and 128-bit hashes are finalized from the same state,
+    /// so a stream is hashed once for all of them. With the seed `(seed, seed)`
+    /// they are `Hash32` with the seed for a `seed` below `2^32`, `Hash64` with the seed,
+    /// and `Hash128` with the seed repeated in both halves.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{spooky, spooky::Hasher128, FastHasher};
+    ///
+    /// let mut h = Hasher128::with_seed((123, 123));
+    ///
+    /// h.write(b"hello");
+    /// h.write(b"world");
+    ///
+    /// assert_eq!(h.finish32(), spooky::hash32_with_seed(b"helloworld", 123));
+    /// assert_eq!(h.finish64(), spooky::hash64_with_seed(b"helloworld", 123));
+    /// assert_eq!(h.finish128(), spooky::hash128_with_seed(b"helloworld", 123 << 64 | 123));
+    /// ```
+    #[inline(always)]
+    pub fn finish32(&self) -> u32 {
+        self.finish128() as u32
+    }
+
+    /// Returns the 64-bit hash of the written bytes, the low 64 bits of `finish128`.
+    #[inline(always)]
+    pub fn finish64(&self) -> u64 {
+        self.finish128() as u64
+    }
+
+    /// Returns the 128-bit hash of the written bytes, the same as `finish_ext`.
+    #[inline(always)]
+    pub fn finish128(&self) -> u128 {
+        self.finish_ext()
+    }
+}
+
 impl Hasher for Hasher128 {
     #[inline(always)]
     fn finish(&self) -> u64 {
@@ -248,6 +322,10 @@ impl Hasher for Hasher128 {
 
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
         unsafe {
             ffi::SpookyHasherUpdate(
                 self.0.as_ptr(),
@@ -261,14 +339,14 @@ impl Hasher for Hasher128 {
 impl HasherExt for Hasher128 {
     #[inline(always)]
     fn finish_ext(&self) -> u128 {
-        let mut hi = 0_u64;
-        let mut lo = 0_u64;
+        let mut hash1 = 0_u64;
+        let mut hash2 = 0_u64;
 
         unsafe {
-            ffi::SpookyHasherFinal(self.0.as_ptr(), &mut hi, &mut lo);
+            ffi::SpookyHasherFinal(self.0.as_ptr(), &mut hash1, &mut hash2);
         }
 
-        u128::from(hi).wrapping_shl(64) + u128::from(lo)
+        u128::from(hash2).wrapping_shl(64) + u128::from(hash1)
     }
 }
 
@@ -288,9 +366,35 @@ impl FastHasher for Hasher128 {
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for Hasher128 {}
 
+impl SpillState<Hash32> for Hasher128 {
+    #[inline(always)]
+    fn with_hash_seed(seed: u32) -> Self {
+        Hasher128::with_seed((u64::from(seed), u64::from(seed)))
+    }
+
+    #[inline(always)]
+    fn finish_hash(&self) -> u32 {
+        self.finish32()
+    }
+}
+
+impl SpillState<Hash64> for Hasher128 {
+    #[inline(always)]
+    fn with_hash_seed(seed: u64) -> Self {
+        Hasher128::with_seed((seed, seed))
+    }
+
+    #[inline(always)]
+    fn finish_hash(&self) -> u64 {
+        self.finish64()
+    }
+}
+
 impl_build_hasher!(Hasher128, Hash128);
+impl_write!(Hasher128);
 
 /// `SpookyHash` 32-bit hash functions for a byte array.
 #[inline(always)]
@@ -330,3 +434,229 @@ pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
 pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
     Hash128::hash_with_seed(v, seed)
 }
+
+/// `SpookyHash` 128-bit hash function for a byte array, as the reference digest bytes.
+///
+/// The reference `SpookyHash::Hash128` takes the seeds in and returns the hash in
+/// the `hash1` and `hash2` words, the bytes are `hash1` followed by `hash2`,
+/// both in little-endian order, i.e. the memory of the two words on the
+/// little-endian targets the reference is defined for.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::spooky;
+///
+/// let bytes = spooky::hash128_bytes(b"hello", 0, 0);
+///
+/// assert_eq!(bytes, spooky::hash128(b"hello").to_le_bytes());
+/// assert_eq!(
+///     spooky::hash128_from_bytes(bytes),
+///     (0x54bc_b5ac_e8e4_2fc0, 0xe3d5_4d8b_c6e7_f32d)
+/// );
+/// ```
+#[inline(always)]
+pub fn hash128_bytes<T: AsRef<[u8]>>(v: T, seed1: u64, seed2: u64) -> [u8; 16] {
+    let (mut hash1, mut hash2) = (seed1, seed2);
+
+    unsafe {
+        ffi::SpookyHasherHash(
+            v.as_ref().as_ptr() as *const c_void,
+            v.as_ref().len(),
+            &mut hash1,
+            &mut hash2,
+        );
+    }
+
+    let mut bytes = [0_u8; 16];
+
+    bytes[..8].copy_from_slice(&hash1.to_le_bytes());
+    bytes[8..].copy_from_slice(&hash2.to_le_bytes());
+
+    bytes
+}
+
+/// Parses the digest bytes of `hash128_bytes` into the `(hash1, hash2)` words.
+#[inline(always)]
+pub fn hash128_from_bytes(bytes: [u8; 16]) -> (u64, u64) {
+    let mut hash1 = [0_u8; 8];
+    let mut hash2 = [0_u8; 8];
+
+    hash1.copy_from_slice(&bytes[..8]);
+    hash2.copy_from_slice(&bytes[8..]);
+
+    (u64::from_le_bytes(hash1), u64::from_le_bytes(hash2))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::{BufHasher, ResetHasher};
+
+    #[test]
+    fn test_seed_slots() {
+        let (seed1, seed2) = (123_u64, 456_u64);
+        let (mut hash1, mut hash2) = (seed1, seed2);
+
+        unsafe {
+            ffi::SpookyHasherHash(
+                b"hello".as_ptr() as *const c_void,
+                5,
+                &mut hash1,
+                &mut hash2,
+            );
+        }
+
+        let h = u128::from(hash2) << 64 | u128::from(hash1);
+
+        assert_eq!(
+            Hash128::hash_with_seed(b"hello", u128::from(seed2) << 64 | u128::from(seed1)),
+            h
+        );
+
+        let mut hasher = Hasher128::with_seed((seed1, seed2));
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish_ext(), h);
+
+        let mut hasher = Hasher128::with_seed((seed2, seed1));
+        hasher.write(b"hello");
+        assert_ne!(hasher.finish_ext(), h);
+    }
+
+    #[test]
+    fn test_hash128_bytes() {
+        fn unhex(s: &str) -> [u8; 16] {
+            let mut bytes = [0; 16];
+
+            for (i, b) in bytes.iter_mut().enumerate() {
+                *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).unwrap();
+            }
+
+            bytes
+        }
+
+        assert_eq!(
+            hash128_bytes(b"hello", 0, 0),
+            unhex("c02fe4e8acb5bc542df3e7c68b4dd5e3")
+        );
+        assert_eq!(
+            hash128_bytes(b"hello", 123, 0),
+            unhex("4d3ce98ac21586568f8ece26bcd1e08e")
+        );
+
+        for &(seed1, seed2) in &[(0, 0), (123, 456), (456, 123)] {
+            let bytes = hash128_bytes(b"helloworld", seed1, seed2);
+            let (hash1, hash2) = hash128_from_bytes(bytes);
+            let seed = u128::from(seed2) << 64 | u128::from(seed1);
+
+            assert_eq!(
+                u128::from(hash2) << 64 | u128::from(hash1),
+                Hash128::hash_with_seed(b"helloworld", seed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash64_is_low_half_of_hash128() {
+        // covers both the short (< 192 bytes) and the long path of the reference
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+
+        for &len in &[0, 1, 15, 16, 31, 32, 191, 192, 193, 1000] {
+            let data = &data[..len];
+
+            for &seed in &[0_u64, 123, 0xdead_beef_dead_beef] {
+                let seed128 = u128::from(seed) << 64 | u128::from(seed);
+                let h = Hash128::hash_with_seed(data, seed128);
+
+                assert_eq!(h as u64, Hash64::hash_with_seed(data, seed), "len {}", len);
+                assert_eq!(
+                    h as u32,
+                    Hash32::hash_with_seed(data, seed as u32),
+                    "len {}",
+                    len
+                );
+
+                let mut hasher = Hasher128::with_seed((seed, seed));
+                hasher.write(data);
+
+                assert_eq!(hasher.finish_ext(), h, "len {}", len);
+                assert_eq!(hasher.finish(), Hash64::hash_with_seed(data, seed));
+            }
+        }
+
+        assert_eq!(Hash128::hash(b"hello") as u64, Hash64::hash(b"hello"));
+    }
+
+    #[test]
+    fn test_spill_into_hasher128() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+
+        for &seed in &[None, Some(123_u64)] {
+            let new = || match seed {
+                Some(seed) => Hasher64::with_seed(seed),
+                None => Hasher64::new(),
+            };
+            let hash = |data: &[u8]| match seed {
+                Some(seed) => Hash64::hash_with_seed(data, seed),
+                None => Hash64::hash(data),
+            };
+
+            let seed32 = seed.unwrap_or_default() as u32;
+            let mut h = new().with_buffer_limit(100);
+            let mut h32 = Hasher32::with_seed(seed32).with_buffer_limit(100);
+
+            for chunk in data.chunks(30) {
+                h.write(chunk);
+                h32.write(chunk);
+            }
+
+            // the bytes past the limit are hashed by the native state, not buffered
+            assert_eq!(h.len(), 0);
+            assert_eq!(h.finish(), hash(&data));
+            assert_eq!(
+                h32.finish(),
+                u64::from(Hash32::hash_with_seed(&data, seed32))
+            );
+
+            let mut cloned = h.clone();
+
+            h.write(b"hello");
+            cloned.write(b"world");
+
+            assert_eq!(h.finish(), hash(&[&data[..], b"hello"].concat()));
+            assert_eq!(cloned.finish(), hash(&[&data[..], b"world"].concat()));
+
+            // a reset hasher buffers again
+            h.reset();
+            h.write(&data[..100]);
+
+            assert_eq!(h.len(), 100);
+            assert_eq!(h.finish(), hash(&data[..100]));
+        }
+    }
+
+    #[test]
+    fn test_finish_all_widths() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 13) as u8).collect();
+
+        for &seed in &[0_u32, 123, 0xdead_beef] {
+            let seed64 = u64::from(seed);
+            let mut h = Hasher128::with_seed((seed64, seed64));
+
+            for (i, chunk) in data.chunks(100).enumerate() {
+                h.write(chunk);
+
+                let data = &data[..(i + 1) * 100];
+
+                assert_eq!(h.finish32(), Hash32::hash_with_seed(data, seed));
+                assert_eq!(h.finish64(), Hash64::hash_with_seed(data, seed64));
+                assert_eq!(
+                    h.finish128(),
+                    Hash128::hash_with_seed(data, u128::from(seed64) << 64 | u128::from(seed64))
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Allocation free hasher for bounded keys
+use core::fmt;
+use core::hash::Hasher;
+
+use num_traits::AsPrimitive;
+
+use crate::hasher::{FastHash, FastHasher, Seed};
+
+/// The error returned when the written bytes don't fit in the buffer of a `StackHasher`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError {
+    capacity: usize,
+}
+
+impl CapacityError {
+    /// The capacity of the buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "wrote more than the {} bytes of the buffer",
+            self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// A hasher which buffers the bytes in a fixed array of `N` bytes instead of a `Vec`.
+///
+/// Like the buffering hashers, it hashes the written bytes with the one-shot
+/// hash function on `finish`, but it never allocates, for the real-time code
+/// hashing keys of a bounded size. Writing more than `N` bytes in total panics,
+/// `try_write` reports the overflow instead.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{city, FastHasher, StackHasher};
+///
+/// let mut h = StackHasher::<city::Hash64, 16>::new();
+///
+/// h.write(b"hello");
+/// assert_eq!(h.finish(), city::hash64(b"hello"));
+///
+/// assert!(h.try_write(&[0; 12]).is_err());
+/// assert_eq!(h.finish(), city::hash64(b"hello"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct StackHasher<H: FastHash, const N: usize> {
+    seed: Option<H::Seed>,
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<H: FastHash, const N: usize> StackHasher<H, N> {
+    /// Writes the bytes into the buffer, or fails without writing any of them
+    /// if they don't fit in the remaining capacity.
+    #[inline]
+    pub fn try_write(&mut self, bytes: &[u8]) -> Result<(), CapacityError> {
+        let end = self
+            .len
+            .checked_add(bytes.len())
+            .filter(|&end| end <= N)
+            .ok_or(CapacityError { capacity: N })?;
+
+        self.buf[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+
+        Ok(())
+    }
+
+    /// Returns the number of the buffered bytes.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes are buffered.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Hashes the buffered bytes with the full output of the hash function.
+    #[inline]
+    pub fn finalize(&self) -> H::Hash {
+        let bytes = &self.buf[..self.len];
+
+        self.seed
+            .map_or_else(|| H::hash(bytes), |seed| H::hash_with_seed(bytes, seed))
+    }
+}
+
+impl<H: FastHash, const N: usize> AsRef<[u8]> for StackHasher<H, N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl<H, const N: usize> Hasher for StackHasher<H, N>
+where
+    H: FastHash,
+    H::Hash: AsPrimitive<u64>,
+{
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.finalize().as_()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        if let Err(err) = self.try_write(bytes) {
+            panic!("{}", err);
+        }
+    }
+}
+
+impl<H, const N: usize> FastHasher for StackHasher<H, N>
+where
+    H: FastHash,
+    H::Hash: AsPrimitive<u64>,
+    H::Seed: From<Seed>,
+{
+    type Seed = H::Seed;
+    type Output = H::Hash;
+
+    #[inline(always)]
+    fn new() -> Self {
+        StackHasher {
+            seed: None,
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn with_seed(seed: Self::Seed) -> Self {
+        StackHasher {
+            seed: Some(seed),
+            buf: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<H, const N: usize> Default for StackHasher<H, N>
+where
+    H: FastHash,
+    H::Hash: AsPrimitive<u64>,
+    H::Seed: From<Seed>,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        StackHasher::new()
+    }
+}
+
+#[cfg(all(test, feature = "city", feature = "murmur"))]
+mod tests {
+    use super::*;
+    use crate::{city, murmur3};
+
+    #[test]
+    fn test_stack_hasher() {
+        let key = [0x5a_u8; 32];
+
+        let mut h = StackHasher::<city::Hash64, 64>::new();
+
+        h.write(&key[..10]);
+        h.write(&key[10..]);
+
+        assert_eq!(h.len(), 32);
+        assert_eq!(h.finish(), city::hash64(&key));
+
+        let mut h = StackHasher::<murmur3::Hash128_x64, 64>::with_seed(123);
+
+        h.write(&key);
+
+        assert_eq!(h.finalize(), murmur3::hash128_x64_with_seed(&key, 123));
+        assert_eq!(h.finish(), murmur3::hash128_x64_with_seed(&key, 123) as u64);
+    }
+
+    #[test]
+    fn test_try_write_overflow() {
+        let mut h = StackHasher::<city::Hash64, 8>::new();
+
+        assert_eq!(h.try_write(b"hello"), Ok(()));
+        assert_eq!(h.try_write(b"world").unwrap_err().capacity(), 8);
+        assert_eq!(h.as_ref(), b"hello");
+        assert_eq!(h.try_write(b"!!!"), Ok(()));
+        assert_eq!(h.finish(), city::hash64(b"hello!!!"));
+        assert!(h.try_write(b"!").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "wrote more than the 8 bytes of the buffer")]
+    fn test_write_overflow() {
+        let mut h = StackHasher::<city::Hash64, 8>::new();
+
+        h.write(b"helloworld");
+    }
+}
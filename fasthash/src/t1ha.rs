@@ -60,11 +60,14 @@ use crate::hasher::FastHash;
 ///      Provides streaming mode and 128-bit result.
 ///
 pub mod t1ha2 {
-    use std::hash::Hasher;
-    use std::mem;
-    use std::ptr;
+    use alloc::boxed::Box;
+    use core::hash::Hasher;
+    use core::mem;
+    use core::ptr;
 
-    use crate::hasher::{FastHash, FastHasher, HasherExt, StreamHasher};
+    #[cfg(feature = "std")]
+    use crate::hasher::StreamHasher;
+    use crate::hasher::{FastHash, FastHasher, HasherExt};
 
     /// The at-once variant with 64-bit result
     ///
@@ -156,6 +159,11 @@ pub mod t1ha2 {
     /// Due performance reason 64- and 128-bit results are completely different each other,
     /// i.e. 64-bit result is NOT any part of 128-bit. */
     ///
+    /// The seed `(seed_x, seed_y)` is passed in order to `t1ha2_init`.
+    ///
+    /// `finish` and `finish_ext` finalize the context in place, so the bytes written
+    /// after them don't continue the same hash, `snapshot` finalizes a copy instead.
+    ///
     /// # Example
     ///
     /// ```
@@ -171,7 +179,6 @@ pub mod t1ha2 {
     /// h.write(b"world");
     /// assert_eq!(h.finish_ext(), 189154943715293976030023582550666960629);
     /// ```
-    #[derive(Clone)]
     pub struct Hasher128(ptr::NonNull<ffi::t1ha_context_t>);
 
     impl Default for Hasher128 {
@@ -186,9 +193,23 @@ pub mod t1ha2 {
         }
     }
 
+    impl Clone for Hasher128 {
+        fn clone(&self) -> Self {
+            unsafe {
+                let ctx = Box::new(*self.0.as_ptr());
+
+                Hasher128(ptr::NonNull::new_unchecked(Box::into_raw(ctx)))
+            }
+        }
+    }
+
     impl Hasher for Hasher128 {
         #[inline(always)]
         fn write(&mut self, bytes: &[u8]) {
+            if bytes.is_empty() {
+                return;
+            }
+
             unsafe {
                 ffi::t1ha2_update(self.0.as_ptr(), bytes.as_ptr() as *const _, bytes.len());
             }
@@ -207,6 +228,14 @@ pub mod t1ha2 {
 
             (u128::from(hi) << 64) + u128::from(lo)
         }
+
+        fn snapshot(&self) -> u128 {
+            let mut ctx = unsafe { *self.0.as_ptr() };
+            let mut hi = 0;
+            let lo = unsafe { ffi::t1ha2_final(&mut ctx, &mut hi) };
+
+            (u128::from(hi) << 64) + u128::from(lo)
+        }
     }
 
     impl FastHasher for Hasher128 {
@@ -226,10 +255,58 @@ pub mod t1ha2 {
         }
     }
 
+    #[cfg(feature = "std")]
     impl StreamHasher for Hasher128 {}
 
     impl_build_hasher!(Hasher128, Hash64AtOnce);
     impl_build_hasher!(Hasher128, Hash128AtOnce);
+    impl_write!(Hasher128);
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_seed_slots() {
+            let h = unsafe {
+                let mut ctx: ffi::t1ha_context_t = mem::zeroed();
+                let mut hi = 0;
+
+                ffi::t1ha2_init(&mut ctx, 123, 456);
+                ffi::t1ha2_update(&mut ctx, b"hello".as_ptr() as *const _, 5);
+
+                let lo = ffi::t1ha2_final(&mut ctx, &mut hi);
+
+                (u128::from(hi) << 64) + u128::from(lo)
+            };
+
+            let mut hasher = Hasher128::with_seed((123, 456));
+            hasher.write(b"hello");
+            assert_eq!(hasher.finish_ext(), h);
+
+            let mut hasher = Hasher128::with_seed((456, 123));
+            hasher.write(b"hello");
+            assert_ne!(hasher.finish_ext(), h);
+        }
+
+        #[test]
+        fn test_snapshot() {
+            let hash = |data: &[u8]| {
+                let mut h = Hasher128::with_seed((123, 456));
+                h.write(data);
+                h.finish_ext()
+            };
+
+            let mut h = Hasher128::with_seed((123, 456));
+
+            h.write(b"hello");
+            assert_eq!(h.snapshot(), hash(b"hello"));
+            assert_eq!(h.snapshot(), hash(b"hello"));
+
+            h.write(b"world");
+            assert_eq!(h.snapshot(), hash(b"helloworld"));
+        }
+    }
 }
 
 ///
@@ -371,6 +448,10 @@ pub mod t1ha1 {
 ///      for the current processor. For instance, on IA32 (x86) actual function
 ///      will be selected in runtime, depending on current CPU capabilities
 ///
+/// t1ha doesn't provide any CRC32-accelerated variant, the hardware acceleration
+/// is engaged through the AES-NI implementations selected by `t1ha0` at runtime.
+/// `Hash32` folds the 64-bit `t1ha0` result for users who need a 32-bit hash.
+///
 pub mod t1ha0 {
     use crate::hasher::FastHash;
 
@@ -380,6 +461,9 @@ pub mod t1ha0 {
 
     /// `T1Hash` 64-bit hash functions.
     ///
+    /// The implementation is selected by the native `t1ha0_resolve` for the current CPU,
+    /// so the hash values may differ between machines, see `t1ha::T1ha0`.
+    ///
     /// # Example
     ///
     /// ```
@@ -429,8 +513,238 @@ pub mod t1ha0 {
         /// ```
         Hasher64(Hash64) -> u64
     }
+
+    /// `T1Hash` 32-bit hash functions, folded from the 64-bit `t1ha0` result.
+    ///
+    /// The upper and lower halves of `t1ha0::Hash64` are xor-ed together,
+    /// so it engages the same hardware acceleration as `t1ha0::Hash64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fasthash::{FastHash, t1ha0::{Hash32, Hash64}};
+    ///
+    /// // the values depend on the implementation selected for the CPU, like `Hash64`
+    /// let fold = |h: u64| (h ^ (h >> 32)) as u32;
+    ///
+    /// assert_eq!(Hash32::hash(b"hello"), fold(Hash64::hash(b"hello")));
+    /// assert_eq!(
+    ///     Hash32::hash_with_seed(b"hello", 123),
+    ///     fold(Hash64::hash_with_seed(b"hello", 123))
+    /// );
+    /// assert_ne!(Hash32::hash(b"hello"), Hash32::hash(b"helloworld"));
+    /// ```
+    #[derive(Clone, Default)]
+    pub struct Hash32;
+
+    impl FastHash for Hash32 {
+        type Hash = u32;
+        type Seed = u64;
+
+        #[inline(always)]
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u32 {
+            let h = Hash64::hash_with_seed(bytes, seed);
+
+            (h ^ (h >> 32)) as u32
+        }
+    }
+
+    trivial_hasher! {
+        /// # Example
+        ///
+        /// ```
+        /// use std::hash::Hasher;
+        ///
+        /// use fasthash::{t1ha0::{Hash32, Hasher32}, FastHash, FastHasher};
+        ///
+        /// let mut h = Hasher32::new();
+        ///
+        /// h.write(b"hello");
+        /// assert_eq!(h.finish(), u64::from(Hash32::hash(b"hello")));
+        ///
+        /// h.write(b"world");
+        /// assert_eq!(h.finish(), u64::from(Hash32::hash(b"helloworld")));
+        /// ```
+        Hasher32(Hash32) -> u32
+    }
+
+    /// `T1Hash` 64-bit hash functions for 32-bit little-endian platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fasthash::{FastHash, t1ha0::Hash64_32Le};
+    ///
+    /// let h = Hash64_32Le::hash(b"hello");
+    ///
+    /// assert_eq!(h, Hash64_32Le::hash_with_seed(b"hello", 0));
+    /// assert_ne!(h, Hash64_32Le::hash_with_seed(b"hello", 123));
+    /// assert_ne!(h, Hash64_32Le::hash(b"helloworld"));
+    /// ```
+    #[derive(Clone, Default)]
+    pub struct Hash64_32Le;
+
+    impl FastHash for Hash64_32Le {
+        type Hash = u64;
+        type Seed = u64;
+
+        #[inline(always)]
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
+            unsafe {
+                ffi::t1ha0_32le(
+                    bytes.as_ref().as_ptr() as *const _,
+                    bytes.as_ref().len(),
+                    seed,
+                )
+            }
+        }
+    }
+
+    trivial_hasher! {
+        /// # Example
+        ///
+        /// ```
+        /// use std::hash::Hasher;
+        ///
+        /// use fasthash::{t1ha0::{Hash64_32Le, Hasher64_32Le}, FastHash, FastHasher};
+        ///
+        /// let mut h = Hasher64_32Le::new();
+        ///
+        /// h.write(b"hello");
+        /// assert_eq!(h.finish(), Hash64_32Le::hash(b"hello"));
+        ///
+        /// h.write(b"world");
+        /// assert_eq!(h.finish(), Hash64_32Le::hash(b"helloworld"));
+        /// ```
+        Hasher64_32Le(Hash64_32Le) -> u64
+    }
+
+    /// `T1Hash` 64-bit hash functions for 32-bit big-endian platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fasthash::{FastHash, t1ha0::Hash64_32Be};
+    ///
+    /// let h = Hash64_32Be::hash(b"hello");
+    ///
+    /// assert_eq!(h, Hash64_32Be::hash_with_seed(b"hello", 0));
+    /// assert_ne!(h, Hash64_32Be::hash_with_seed(b"hello", 123));
+    /// assert_ne!(h, Hash64_32Be::hash(b"helloworld"));
+    /// ```
+    #[derive(Clone, Default)]
+    pub struct Hash64_32Be;
+
+    impl FastHash for Hash64_32Be {
+        type Hash = u64;
+        type Seed = u64;
+
+        #[inline(always)]
+        fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
+            unsafe {
+                ffi::t1ha0_32be(
+                    bytes.as_ref().as_ptr() as *const _,
+                    bytes.as_ref().len(),
+                    seed,
+                )
+            }
+        }
+    }
+
+    trivial_hasher! {
+        /// # Example
+        ///
+        /// ```
+        /// use std::hash::Hasher;
+        ///
+        /// use fasthash::{t1ha0::{Hash64_32Be, Hasher64_32Be}, FastHash, FastHasher};
+        ///
+        /// let mut h = Hasher64_32Be::new();
+        ///
+        /// h.write(b"hello");
+        /// assert_eq!(h.finish(), Hash64_32Be::hash(b"hello"));
+        ///
+        /// h.write(b"world");
+        /// assert_eq!(h.finish(), Hash64_32Be::hash(b"helloworld"));
+        /// ```
+        Hasher64_32Be(Hash64_32Be) -> u64
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use alloc::vec::Vec;
+
+        use super::*;
+        use crate::t1ha1;
+
+        #[test]
+        fn test_dispatch_matches_fixed_variant() {
+            type Variant = fn(&[u8], u64) -> u64;
+
+            let mut variants = Vec::<Variant>::new();
+
+            variants.extend_from_slice(&[
+                |b, seed| t1ha1::Hash64Le::hash_with_seed(b, seed),
+                |b, seed| t1ha1::Hash64Be::hash_with_seed(b, seed),
+                |b, seed| Hash64_32Le::hash_with_seed(b, seed),
+                |b, seed| Hash64_32Be::hash_with_seed(b, seed),
+            ]);
+
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "aes"))]
+            variants.extend_from_slice(&[
+                |b, seed| unsafe {
+                    ffi::t1ha0_ia32aes_noavx(b.as_ptr() as *const _, b.len(), seed)
+                },
+                |b, seed| unsafe { ffi::t1ha0_ia32aes_avx(b.as_ptr() as *const _, b.len(), seed) },
+                |b, seed| unsafe { ffi::t1ha0_ia32aes_avx2(b.as_ptr() as *const _, b.len(), seed) },
+            ]);
+
+            let data = (0..=255u8).collect::<Vec<_>>();
+
+            // the selection is fixed for the process, so a single variant matches every input
+            assert!(
+                variants.iter().any(|f| (0..data.len()).all(|len| {
+                    let seed = len as u64 * 7919;
+
+                    Hash64::hash_with_seed(&data[..len], seed) == f(&data[..len], seed)
+                })),
+                "t1ha0 matches none of the fixed variants"
+            );
+        }
+
+        #[test]
+        fn test_hash32_fold() {
+            for (bytes, seed) in &[
+                (&b""[..], 0),
+                (&b"hello"[..], 0),
+                (&b"hello"[..], 123),
+                (&b"helloworld"[..], u64::max_value()),
+            ] {
+                let h = Hash64::hash_with_seed(bytes, *seed);
+
+                assert_eq!(Hash32::hash_with_seed(bytes, *seed), (h ^ (h >> 32)) as u32);
+            }
+        }
+    }
 }
 
+/// The runtime-dispatched `t1ha0`, the recommended entry point of t1ha for speed.
+///
+/// The native `t1ha0_resolve` selects the fastest implementation for the current CPU
+/// at the first call: AES-NI with AVX2, AVX or neither, otherwise `t1ha1` on 64-bit
+/// or `t1ha0_32` on 32-bit platforms. A single binary gets the fastest available
+/// implementation, but the hash values may differ between machines, so they should
+/// never be persisted or shared between machines. Use `t1ha2` for stable hash values.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{t1ha::T1ha0, FastHash};
+///
+/// assert_eq!(T1ha0::hash(b"hello"), T1ha0::hash(b"hello"));
+/// ```
+pub use self::t1ha0::Hash64 as T1ha0;
+
 /// `T1Hash` 64-bit hash functions for a byte array.
 #[inline(always)]
 pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
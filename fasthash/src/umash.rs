@@ -0,0 +1,638 @@
+//! `UMASH`: a fast almost universal 64-bit string hash
+//!
+//! by Paul Khuong, Backtrace I/O, Inc.
+//!
+//! https://github.com/backtrace-labs/umash
+//!
+//! UMASH is a string hash function with throughput comparable to the fastest
+//! non-cryptographic hashes, and a provable bound on the collision probability
+//! of any two distinct inputs of at most `s` bytes (`ceil(s / 4096) * 2^-55`)
+//! for a randomly generated set of parameters.
+//!
+//! The collision bound only holds as long as the `Params` stay secret, so the
+//! parameters should be derived from a random `Seed`, like any other keyed hash.
+//! The same `Params` also compute a 128-bit fingerprint, the concatenation of two
+//! independent UMASH values, with a collision probability of about `2^-83`.
+//!
+//! # Key generation
+//!
+//! `Params::derive(bits, key)` expands the 32-byte `key` with the Salsa20 stream
+//! cipher, using `bits` as the nonce, and then fixes up any parameter that does
+//! not satisfy the UMASH constraints (see `umash_params_prepare`). The derivation
+//! is deterministic: the same `bits` and `key` always produce the same `Params`.
+//! Without an explicit key, the reference default key is used.
+//!
+//! Converting a `fasthash::Seed` into `Params` draws the 256-bit key first and
+//! the 64-bit `bits` next from the seed generator.
+//!
+//! The parameter derivation is much more expensive than hashing short keys,
+//! so `RandomState` derives the `Params` once and shares them with every hasher
+//! it builds.
+//!
+//! # Example
+//!
+//! ```
+//! use std::hash::{Hash, Hasher};
+//!
+//! use fasthash::umash;
+//!
+//! fn hash<T: Hash>(t: &T) -> u64 {
+//!     let mut s: umash::Hasher64 = Default::default();
+//!     t.hash(&mut s);
+//!     s.finish()
+//! }
+//!
+//! let h = umash::hash64(b"hello world\xff");
+//!
+//! assert_eq!(h, hash(&"hello world"));
+//! ```
+//!
+use alloc::sync::Arc;
+use core::ffi::{c_int, c_void};
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+use core::hash::Hasher;
+use core::ptr;
+
+use crate::ffi;
+use crate::hasher::{self, FastHash, FastHasher, SpillState};
+
+lazy_static::lazy_static! {
+    static ref DEFAULT_PARAMS: Params = Params::derive(0, None);
+}
+
+/// The `UMASH` parameters, derived from a 256-bit key.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::umash::Params;
+///
+/// let params = Params::derive(42, Some(&[7; 32]));
+///
+/// assert_eq!(params.hash(b"hello", 0), Params::derive(42, Some(&[7; 32])).hash(b"hello", 0));
+/// assert_ne!(params.hash(b"hello", 0), Params::derive(43, Some(&[7; 32])).hash(b"hello", 0));
+/// assert_ne!(params.hash(b"hello", 0), params.hash(b"hello", 123));
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug)]
+pub struct Params(ffi::umash_params);
+
+impl Params {
+    /// Deterministically derives the parameters from `bits` and a 32-byte `key`.
+    ///
+    /// The reference default key is used if `key` is `None`.
+    pub fn derive(bits: u64, key: Option<&[u8; 32]>) -> Params {
+        let mut params = ffi::umash_params {
+            poly: [[0; 2]; 2],
+            oh: [0; 34],
+        };
+
+        unsafe {
+            ffi::umash_params_derive(
+                &mut params,
+                bits,
+                key.map_or(ptr::null(), |key| key.as_ptr() as *const c_void),
+            );
+        }
+
+        Params(params)
+    }
+
+    /// Computes the 64-bit `UMASH` value of a byte array.
+    #[inline(always)]
+    pub fn hash<T: AsRef<[u8]>>(&self, bytes: T, seed: u64) -> u64 {
+        unsafe {
+            ffi::umash_full(
+                &self.0,
+                seed,
+                0,
+                bytes.as_ref().as_ptr() as *const c_void,
+                bytes.as_ref().len(),
+            )
+        }
+    }
+
+    /// Computes the 128-bit `UMASH` fingerprint of a byte array,
+    /// as a pair of two independent 64-bit hash values.
+    #[inline(always)]
+    pub fn fingerprint<T: AsRef<[u8]>>(&self, bytes: T, seed: u64) -> (u64, u64) {
+        let fp = unsafe {
+            ffi::umash_fprint(
+                &self.0,
+                seed,
+                bytes.as_ref().as_ptr() as *const c_void,
+                bytes.as_ref().len(),
+            )
+        };
+
+        (fp.hash[0], fp.hash[1])
+    }
+}
+
+impl Default for Params {
+    #[inline(always)]
+    fn default() -> Self {
+        *DEFAULT_PARAMS
+    }
+}
+
+impl From<hasher::Seed> for Params {
+    #[inline(always)]
+    fn from(mut seed: hasher::Seed) -> Params {
+        let words: [u64; 4] = seed.draw();
+        let bits: u64 = seed.draw();
+        let mut key = [0u8; 32];
+
+        for (chunk, word) in key.chunks_mut(8).zip(words.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        Params::derive(bits, Some(&key))
+    }
+}
+
+/// The `UMASH` seed, a set of parameters and a 64-bit seed value.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{umash, FastHash};
+///
+/// let seed = umash::Seed::from(123u64);
+///
+/// assert_eq!(umash::Hash64::hash_with_seed(b"hello", seed), seed.params().hash(b"hello", 123));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Seed {
+    params: Params,
+    seed: u64,
+}
+
+impl Seed {
+    /// Constructs a `UMASH` seed with parameters and a 64-bit seed value.
+    #[inline(always)]
+    pub fn new(params: Params, seed: u64) -> Seed {
+        Seed { params, seed }
+    }
+
+    /// The parameters of the seed.
+    #[inline(always)]
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// The 64-bit seed value.
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl From<u64> for Seed {
+    #[inline(always)]
+    fn from(seed: u64) -> Seed {
+        Seed::new(Params::default(), seed)
+    }
+}
+
+impl From<Params> for Seed {
+    #[inline(always)]
+    fn from(params: Params) -> Seed {
+        Seed::new(params, 0)
+    }
+}
+
+impl From<hasher::Seed> for Seed {
+    #[inline(always)]
+    fn from(mut seed: hasher::Seed) -> Seed {
+        let value = seed.draw();
+
+        Seed::new(seed.into(), value)
+    }
+}
+
+/// `UMASH` 64-bit hash functions
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{umash::Hash64, FastHash};
+///
+/// let h = Hash64::hash(b"hello");
+///
+/// assert_eq!(h, Hash64::hash_with_seed(b"hello", 0u64.into()));
+/// assert_ne!(h, Hash64::hash_with_seed(b"hello", 123u64.into()));
+/// assert_ne!(h, Hash64::hash(b"helloworld"));
+/// ```
+#[derive(Clone, Default)]
+pub struct Hash64;
+
+impl FastHash for Hash64 {
+    type Hash = u64;
+    type Seed = Seed;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: Seed) -> u64 {
+        seed.params.hash(bytes, seed.seed)
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{umash::{self, Hasher64}, FastHasher};
+    ///
+    /// let mut h = Hasher64::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish(), umash::hash64(b"hello"));
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), umash::hash64(b"helloworld"));
+    /// ```
+    Hasher64(Hash64) -> u64, spill State
+}
+
+/// `UMASH` 128-bit fingerprint functions
+///
+/// The first hash value of the fingerprint is in the low 64 bits.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{umash::{self, Hash128}, FastHash};
+///
+/// let (lo, hi) = umash::fingerprint(b"hello");
+///
+/// assert_eq!(Hash128::hash(b"hello"), u128::from(hi) << 64 | u128::from(lo));
+/// assert_ne!(Hash128::hash(b"hello"), Hash128::hash_with_seed(b"hello", 123u64.into()));
+/// ```
+#[derive(Clone, Default)]
+pub struct Hash128;
+
+impl FastHash for Hash128 {
+    type Hash = u128;
+    type Seed = Seed;
+
+    #[inline(always)]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: Seed) -> u128 {
+        let (lo, hi) = seed.params.fingerprint(bytes, seed.seed);
+
+        u128::from(hi).wrapping_shl(64) + u128::from(lo)
+    }
+}
+
+trivial_hasher! {
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{umash::{self, Hasher128}, FastHasher, HasherExt};
+    ///
+    /// let mut h = Hasher128::new();
+    ///
+    /// h.write(b"hello");
+    /// assert_eq!(h.finish_ext(), umash::hash128(b"hello"));
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish_ext(), umash::hash128(b"helloworld"));
+    /// ```
+    Hasher128(Hash128) -> u128, spill State
+}
+
+// The incremental API of `umash.h`, which the generated bindings don't cover.
+//
+// A `umash_state` or a `umash_fp_state` owns no allocation, it only borrows the `oh`
+// of its parameters and may be copied byte by byte, so it is declared as an opaque
+// buffer larger and more aligned than the C structs, which take about 200 bytes.
+#[repr(C, align(64))]
+#[derive(Clone, Copy)]
+struct RawState([u8; 512]);
+
+extern "C" {
+    fn umash_init(state: *mut RawState, params: *const ffi::umash_params, seed: u64, which: c_int);
+
+    fn umash_fp_init(state: *mut RawState, params: *const ffi::umash_params, seed: u64);
+
+    fn umash_sink_update(sink: *mut RawState, data: *const c_void, n_bytes: usize);
+
+    fn umash_digest(state: *const RawState) -> u64;
+
+    fn umash_fp_digest(state: *const RawState) -> ffi::umash_fp;
+}
+
+/// The incremental state of the 64-bit `UMASH` or the fingerprint, which
+/// `Hasher64` and `Hasher128` switch to past their buffer limit.
+#[derive(Clone)]
+struct State {
+    // the raw state points into the parameters, which are shared by its clones
+    params: Arc<Params>,
+    raw: RawState,
+    fingerprint: bool,
+}
+
+impl State {
+    fn new(seed: Seed, fingerprint: bool) -> State {
+        let params = Arc::new(seed.params);
+        let mut raw = RawState([0; 512]);
+
+        unsafe {
+            if fingerprint {
+                umash_fp_init(&mut raw, &params.0, seed.seed);
+            } else {
+                umash_init(&mut raw, &params.0, seed.seed, 0);
+            }
+        }
+
+        State {
+            params,
+            raw,
+            fingerprint,
+        }
+    }
+
+    fn digest(&self) -> u64 {
+        unsafe { umash_digest(&self.raw) }
+    }
+
+    fn fp_digest(&self) -> (u64, u64) {
+        let fp = unsafe { umash_fp_digest(&self.raw) };
+
+        (fp.hash[0], fp.hash[1])
+    }
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("State")
+            .field("params", &self.params)
+            .field("fingerprint", &self.fingerprint)
+            .finish()
+    }
+}
+
+impl Hasher for State {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        if self.fingerprint {
+            self.fp_digest().0
+        } else {
+            self.digest()
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
+        unsafe {
+            umash_sink_update(&mut self.raw, bytes.as_ptr() as *const c_void, bytes.len());
+        }
+    }
+}
+
+impl SpillState<Hash64> for State {
+    #[inline(always)]
+    fn with_hash_seed(seed: Seed) -> Self {
+        State::new(seed, false)
+    }
+
+    #[inline(always)]
+    fn finish_hash(&self) -> u64 {
+        self.digest()
+    }
+}
+
+impl SpillState<Hash128> for State {
+    #[inline(always)]
+    fn with_hash_seed(seed: Seed) -> Self {
+        State::new(seed, true)
+    }
+
+    #[inline(always)]
+    fn finish_hash(&self) -> u128 {
+        let (lo, hi) = self.fp_digest();
+
+        u128::from(hi).wrapping_shl(64) + u128::from(lo)
+    }
+}
+
+/// `RandomState` for `UMASH`, which derives the parameters once.
+///
+/// Every hasher built by the same `RandomState` shares the same `Params`,
+/// instead of deriving them again from a `fasthash::Seed`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fasthash::umash;
+///
+/// let mut map = HashMap::with_hasher(umash::RandomState::new());
+///
+/// assert_eq!(map.insert(37, "a"), None);
+/// assert_eq!(map.insert(37, "b"), Some("a"));
+/// assert_eq!(map[&37], "b");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct RandomState(Seed);
+
+#[cfg(feature = "std")]
+impl RandomState {
+    /// Constructs a new `RandomState` with randomly derived parameters.
+    #[inline(always)]
+    pub fn new() -> Self {
+        RandomState(hasher::Seed::gen().into())
+    }
+
+    /// Constructs a new `RandomState` with the given seed.
+    #[inline(always)]
+    pub fn with_seed(seed: Seed) -> Self {
+        RandomState(seed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for RandomState {
+    #[inline(always)]
+    fn default() -> Self {
+        RandomState::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl BuildHasher for RandomState {
+    type Hasher = Hasher64;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Hasher64 {
+        Hasher64::with_seed(self.0)
+    }
+}
+
+/// `UMASH` 64-bit hash functions for a byte array.
+#[inline(always)]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    Hash64::hash(v)
+}
+
+/// `UMASH` 64-bit hash function for a byte array.
+/// For convenience, a 64-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    Hash64::hash_with_seed(v, seed.into())
+}
+
+/// `UMASH` 128-bit fingerprint functions for a byte array.
+#[inline(always)]
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    Hash128::hash(v)
+}
+
+/// `UMASH` 128-bit fingerprint function for a byte array.
+/// For convenience, a 64-bit seed is also hashed into the result.
+#[inline(always)]
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u128 {
+    Hash128::hash_with_seed(v, seed.into())
+}
+
+/// `UMASH` fingerprint of a byte array, as a pair of two independent hash values.
+#[inline(always)]
+pub fn fingerprint<T: AsRef<[u8]>>(v: T) -> (u64, u64) {
+    Params::default().fingerprint(v, 0)
+}
+
+/// `UMASH` fingerprint of a byte array with the parameters and seed.
+#[inline(always)]
+pub fn fingerprint_with_seed<T: AsRef<[u8]>>(v: T, seed: Seed) -> (u64, u64) {
+    seed.params.fingerprint(v, seed.seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::{BufHasher, HasherExt};
+
+    // The reference example of UMASH, `example.c` in the repository, and its output
+    // in the README: the parameters derived from `bits = 0` and the 32-byte key
+    // `"hello example.c"` padded with NULs, with the seed 42.
+    //
+    // $ ./example "the quick brown fox"
+    // Input: the quick brown fox
+    // Fingerprint: 398c5bb5cc113d03, 3a52693519575aba
+    // Hash 0: 398c5bb5cc113d03
+    fn example_params() -> Params {
+        let mut key = [0; 32];
+        key[..15].copy_from_slice(b"hello example.c");
+
+        Params::derive(0, Some(&key))
+    }
+
+    #[test]
+    fn test_reference_example() {
+        let params = example_params();
+        let input = b"the quick brown fox";
+
+        assert_eq!(params.hash(input, 42), 0x398c5bb5cc113d03);
+        assert_eq!(
+            params.fingerprint(input, 42),
+            (0x398c5bb5cc113d03, 0x3a52693519575aba)
+        );
+
+        let seed = Seed::new(params, 42);
+
+        assert_eq!(Hash64::hash_with_seed(input, seed), 0x398c5bb5cc113d03);
+        assert_eq!(
+            Hash128::hash_with_seed(input, seed),
+            0x3a52693519575aba_398c5bb5cc113d03
+        );
+        assert_eq!(
+            fingerprint_with_seed(input, seed),
+            (0x398c5bb5cc113d03, 0x3a52693519575aba)
+        );
+    }
+
+    #[test]
+    fn test_spill_into_state() {
+        let data = (0..5000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        let seed = Seed::new(example_params(), 42);
+
+        let mut h = Hasher64::with_seed(seed).with_buffer_limit(1000);
+        let mut h128 = Hasher128::with_seed(seed).with_buffer_limit(1000);
+
+        for chunk in data.chunks(300) {
+            h.write(chunk);
+            h128.write(chunk);
+        }
+
+        // the bytes past the limit are hashed by the native state, not buffered
+        assert_eq!(h.len(), 0);
+        assert_eq!(h.finish(), Hash64::hash_with_seed(&data, seed));
+        assert_eq!(h128.finish_ext(), Hash128::hash_with_seed(&data, seed));
+
+        let mut cloned = h.clone();
+        drop(h128);
+
+        h.write(b"hello");
+        cloned.write(b"world");
+
+        assert_eq!(
+            h.finish(),
+            Hash64::hash_with_seed([&data[..], b"hello"].concat(), seed)
+        );
+        assert_eq!(
+            cloned.finish(),
+            Hash64::hash_with_seed([&data[..], b"world"].concat(), seed)
+        );
+
+        // the default parameters, across the limit in a single write
+        let mut h = Hasher64::new().with_buffer_limit(10);
+
+        h.write(b"the quick brown fox");
+
+        assert_eq!(h.finish(), hash64(b"the quick brown fox"));
+    }
+
+    #[test]
+    fn test_derive() {
+        let params = example_params();
+
+        // the derivation is deterministic, and depends on both `bits` and the key
+        assert_eq!(params.0.poly, example_params().0.poly);
+        assert_eq!(&params.0.oh[..], &example_params().0.oh[..]);
+        assert_ne!(params.0.poly, Params::derive(1, Some(&[0; 32])).0.poly);
+        assert_ne!(params.0.poly, Params::derive(0, None).0.poly);
+    }
+
+    #[test]
+    fn test_default_params() {
+        // the reference default key of `umash_params_derive`
+        let params = Params::derive(0, Some(b"Do not use UMASH VS adversaries."));
+
+        for &input in &[&b""[..], b"hello", b"hello world", b"the quick brown fox"] {
+            assert_eq!(hash64(input), params.hash(input, 0));
+            assert_eq!(hash64_with_seed(input, 123), params.hash(input, 123));
+            assert_eq!(fingerprint(input), params.fingerprint(input, 0));
+
+            let (lo, hi) = params.fingerprint(input, 123);
+
+            assert_eq!(lo, params.hash(input, 123));
+            assert_eq!(
+                hash128_with_seed(input, 123),
+                u128::from(hi) << 64 | u128::from(lo)
+            );
+        }
+    }
+}
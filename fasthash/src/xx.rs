@@ -9,6 +9,9 @@
 //! dispersion and randomness qualities of hash functions. Code is highly portable,
 //! and hashes are identical on all platforms (little / big endian).
 //!
+//! The streaming hashers could save their intermediate state to bytes with `save_state`,
+//! and resume it later with `load_state`, e.g. to checkpoint hashing a huge file.
+//!
 //!
 //! # Example
 //!
@@ -28,13 +31,29 @@
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
-use std::hash::Hasher;
-use std::os::raw::c_void;
-use std::ptr::NonNull;
+use alloc::alloc::{handle_alloc_error, Layout};
+use alloc::vec::Vec;
+use core::ffi::c_void;
+use core::hash::Hasher;
+use core::ptr::NonNull;
 
 use crate::ffi;
 
-use crate::hasher::{FastHash, FastHasher, StreamHasher};
+#[cfg(feature = "std")]
+use crate::hasher::StreamHasher;
+use crate::hasher::{FastHash, FastHasher};
+
+/// Allocates a native 32-bit state, aborting like a Rust allocation if it fails.
+fn new_state32() -> NonNull<ffi::XXH32_state_t> {
+    NonNull::new(unsafe { ffi::XXH32_createState() })
+        .unwrap_or_else(|| handle_alloc_error(Layout::new::<ffi::XXH32_state_t>()))
+}
+
+/// Allocates a native 64-bit state, aborting like a Rust allocation if it fails.
+fn new_state64() -> NonNull<ffi::XXH64_state_t> {
+    NonNull::new(unsafe { ffi::XXH64_createState() })
+        .unwrap_or_else(|| handle_alloc_error(Layout::new::<ffi::XXH64_state_t>()))
+}
 
 /// xxHash 32-bit hash functions
 ///
@@ -122,6 +141,30 @@ pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     Hash64::hash_with_seed(v, seed)
 }
 
+/// xxHash 64-bit hash function for every item, hashed independently with the seed.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::xx;
+///
+/// let rows = ["hello", "world", "hello"];
+/// let hashes = xx::hash_all(&rows, 123);
+///
+/// assert_eq!(hashes[0], xx::hash64_with_seed("hello", 123));
+/// assert_eq!(hashes[0], hashes[2]);
+/// ```
+pub fn hash_all<I>(items: I, seed: u64) -> Vec<u64>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    items
+        .into_iter()
+        .map(|item| hash64_with_seed(item, seed))
+        .collect()
+}
+
 /// An implementation of `std::hash::Hasher`.
 ///
 /// # Example
@@ -163,11 +206,112 @@ impl Drop for Hasher32 {
 impl Clone for Hasher32 {
     fn clone(&self) -> Self {
         unsafe {
-            let state = ffi::XXH32_createState();
+            let state = new_state32();
 
-            ffi::XXH32_copyState(state, self.0.as_ptr());
+            ffi::XXH32_copyState(state.as_ptr(), self.0.as_ptr());
 
-            Hasher32(NonNull::new_unchecked(state))
+            Hasher32(state)
+        }
+    }
+}
+
+impl Hasher32 {
+    /// The size of a saved state in bytes.
+    pub const STATE_SIZE: usize = 44;
+
+    /// Serializes the intermediate state of the hasher,
+    /// which could be persisted and resumed later with `load_state`.
+    ///
+    /// The state is encoded in a portable little endian layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{xx::{self, Hasher32}, FastHasher};
+    ///
+    /// let mut h = Hasher32::new();
+    ///
+    /// h.write(b"hello");
+    ///
+    /// let state = h.save_state();
+    /// let mut h = Hasher32::load_state(&state).unwrap();
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), u64::from(xx::hash32(b"helloworld")));
+    /// ```
+    pub fn save_state(&self) -> Vec<u8> {
+        let state = unsafe { *self.0.as_ptr() };
+        let mut buf = Vec::with_capacity(Self::STATE_SIZE);
+
+        for v in &[
+            state.total_len_32,
+            state.large_len,
+            state.v1,
+            state.v2,
+            state.v3,
+            state.v4,
+        ] {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        // the internal buffer is treated as a byte array
+        for v in &state.mem32 {
+            buf.extend_from_slice(&v.to_ne_bytes());
+        }
+        buf.extend_from_slice(&state.memsize.to_le_bytes());
+
+        buf
+    }
+
+    /// Restores a hasher from a state serialized by `save_state`.
+    ///
+    /// Returns `None` if the state is malformed.
+    pub fn load_state(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::STATE_SIZE {
+            return None;
+        }
+
+        let word = |off: usize| {
+            let mut buf = [0; 4];
+            buf.copy_from_slice(&bytes[off..off + 4]);
+            buf
+        };
+
+        let total_len_32 = u32::from_le_bytes(word(0));
+        let large_len = u32::from_le_bytes(word(4));
+        let (v1, v2, v3, v4) = (
+            u32::from_le_bytes(word(8)),
+            u32::from_le_bytes(word(12)),
+            u32::from_le_bytes(word(16)),
+            u32::from_le_bytes(word(20)),
+        );
+        let mut mem32 = [0; 4];
+        for (i, v) in mem32.iter_mut().enumerate() {
+            *v = u32::from_ne_bytes(word(24 + i * 4));
+        }
+        let memsize = u32::from_le_bytes(word(40));
+
+        if memsize >= 16 || large_len > 1 {
+            return None;
+        }
+
+        unsafe {
+            let h = new_state32();
+
+            *h.as_ptr() = ffi::XXH32_state_t {
+                total_len_32,
+                large_len,
+                v1,
+                v2,
+                v3,
+                v4,
+                mem32,
+                memsize,
+                reserved: 0,
+            };
+
+            Some(Hasher32(h))
         }
     }
 }
@@ -180,6 +324,10 @@ impl Hasher for Hasher32 {
 
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
         unsafe {
             ffi::XXH32_update(
                 self.0.as_ptr(),
@@ -197,18 +345,20 @@ impl FastHasher for Hasher32 {
     #[inline(always)]
     fn with_seed(seed: u32) -> Self {
         unsafe {
-            let h = ffi::XXH32_createState();
+            let h = new_state32();
 
-            ffi::XXH32_reset(h, seed);
+            ffi::XXH32_reset(h.as_ptr(), seed);
 
-            Hasher32(NonNull::new_unchecked(h))
+            Hasher32(h)
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for Hasher32 {}
 
 impl_build_hasher!(Hasher32, Hash32);
+impl_write!(Hasher32);
 
 /// An implementation of `std::hash::Hasher`.
 ///
@@ -250,11 +400,108 @@ impl Drop for Hasher64 {
 impl Clone for Hasher64 {
     fn clone(&self) -> Self {
         unsafe {
-            let state = ffi::XXH64_createState();
+            let state = new_state64();
+
+            ffi::XXH64_copyState(state.as_ptr(), self.0.as_ptr());
+
+            Hasher64(state)
+        }
+    }
+}
+
+impl Hasher64 {
+    /// The size of a saved state in bytes.
+    pub const STATE_SIZE: usize = 76;
+
+    /// Serializes the intermediate state of the hasher,
+    /// which could be persisted and resumed later with `load_state`.
+    ///
+    /// The state is encoded in a portable little endian layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::{xx::{self, Hasher64}, FastHasher};
+    ///
+    /// let mut h = Hasher64::new();
+    ///
+    /// h.write(b"hello");
+    ///
+    /// let state = h.save_state();
+    /// let mut h = Hasher64::load_state(&state).unwrap();
+    ///
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), xx::hash64(b"helloworld"));
+    /// ```
+    pub fn save_state(&self) -> Vec<u8> {
+        let state = unsafe { *self.0.as_ptr() };
+        let mut buf = Vec::with_capacity(Self::STATE_SIZE);
+
+        for v in &[state.total_len, state.v1, state.v2, state.v3, state.v4] {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        // the internal buffer is treated as a byte array
+        for v in &state.mem64 {
+            buf.extend_from_slice(&v.to_ne_bytes());
+        }
+        buf.extend_from_slice(&state.memsize.to_le_bytes());
+
+        buf
+    }
+
+    /// Restores a hasher from a state serialized by `save_state`.
+    ///
+    /// Returns `None` if the state is malformed.
+    pub fn load_state(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::STATE_SIZE {
+            return None;
+        }
 
-            ffi::XXH64_copyState(state, self.0.as_ptr());
+        let word = |off: usize| {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(&bytes[off..off + 8]);
+            buf
+        };
+
+        let total_len = u64::from_le_bytes(word(0));
+        let (v1, v2, v3, v4) = (
+            u64::from_le_bytes(word(8)),
+            u64::from_le_bytes(word(16)),
+            u64::from_le_bytes(word(24)),
+            u64::from_le_bytes(word(32)),
+        );
+        let mut mem64 = [0; 4];
+        for (i, v) in mem64.iter_mut().enumerate() {
+            *v = u64::from_ne_bytes(word(40 + i * 8));
+        }
+        let memsize = {
+            let mut buf = [0; 4];
+            buf.copy_from_slice(&bytes[72..76]);
+            u32::from_le_bytes(buf)
+        };
+
+        if memsize >= 32 {
+            return None;
+        }
 
-            Hasher64(NonNull::new_unchecked(state))
+        unsafe {
+            let h = new_state64();
+
+            *h.as_ptr() = ffi::XXH64_state_t {
+                total_len,
+                v1,
+                v2,
+                v3,
+                v4,
+                mem64,
+                memsize,
+                reserved32: 0,
+                reserved64: 0,
+            };
+
+            Some(Hasher64(h))
         }
     }
 }
@@ -267,6 +514,10 @@ impl Hasher for Hasher64 {
 
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
         unsafe {
             ffi::XXH64_update(
                 self.0.as_ptr(),
@@ -284,15 +535,76 @@ impl FastHasher for Hasher64 {
     #[inline(always)]
     fn with_seed(seed: u64) -> Self {
         unsafe {
-            let h = ffi::XXH64_createState();
+            let h = new_state64();
 
-            ffi::XXH64_reset(h, seed);
+            ffi::XXH64_reset(h.as_ptr(), seed);
 
-            Hasher64(NonNull::new_unchecked(h))
+            Hasher64(h)
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for Hasher64 {}
 
 impl_build_hasher!(Hasher64, Hash64);
+impl_write!(Hasher64);
+
+#[cfg(test)]
+mod tests {
+    use std::hash::Hasher;
+
+    use super::*;
+
+    #[test]
+    fn test_hash_all() {
+        let items: [&[u8]; 3] = [b"hello", b"", &[0x5a; 100]];
+        let hashes = hash_all(items.iter(), 123);
+
+        assert_eq!(hashes.len(), items.len());
+
+        for (item, &hash) in items.iter().zip(&hashes) {
+            assert_eq!(hash, hash64_with_seed(item, 123));
+        }
+
+        assert!(hash_all(Vec::<Vec<u8>>::new(), 123).is_empty());
+    }
+
+    #[test]
+    fn test_resume_from_state() {
+        let data = (0..10_000u32).map(|i| i as u8).collect::<Vec<_>>();
+
+        for &split in &[0, 1, 15, 16, 31, 32, 33, 4567, data.len()] {
+            let (head, tail) = data.split_at(split);
+
+            let mut h = Hasher32::with_seed(123);
+            h.write(head);
+            let state = h.save_state();
+            drop(h);
+
+            assert_eq!(state.len(), Hasher32::STATE_SIZE);
+
+            let mut h = Hasher32::load_state(&state).unwrap();
+            h.write(tail);
+            assert_eq!(h.finish(), u64::from(hash32_with_seed(&data, 123)));
+
+            let mut h = Hasher64::with_seed(123);
+            h.write(head);
+            let state = h.save_state();
+            drop(h);
+
+            assert_eq!(state.len(), Hasher64::STATE_SIZE);
+
+            let mut h = Hasher64::load_state(&state).unwrap();
+            h.write(tail);
+            assert_eq!(h.finish(), hash64_with_seed(&data, 123));
+        }
+    }
+
+    #[test]
+    fn test_load_malformed_state() {
+        assert!(Hasher32::load_state(&[]).is_none());
+        assert!(Hasher64::load_state(&[0; 75]).is_none());
+        assert!(Hasher64::load_state(&[0xff; Hasher64::STATE_SIZE]).is_none());
+    }
+}
@@ -1,9 +1,20 @@
 //! XXH3 is a new hash algorithm, featuring vastly improved speed performance for both small and large inputs.
-use std::hash::Hasher;
-use std::mem;
-use std::ptr::NonNull;
-
-use crate::{FastHash, FastHasher, HasherExt, StreamHasher};
+use alloc::alloc::{handle_alloc_error, Layout};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::hash::Hasher;
+use core::mem;
+use core::ptr::NonNull;
+
+#[cfg(feature = "std")]
+use crate::StreamHasher;
+use crate::{FastHash, FastHasher, HasherExt, ResetHasher};
+
+/// Allocates a native state, aborting like a Rust allocation if it fails.
+fn new_state() -> NonNull<ffi::XXH3_state_t> {
+    NonNull::new(unsafe { ffi::XXH3_createState() })
+        .unwrap_or_else(|| handle_alloc_error(Layout::new::<ffi::XXH3_state_t>()))
+}
 
 /// 64-bit hash functions for a byte array.
 ///
@@ -70,6 +81,95 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u128 {
     Hash128::hash_with_seed(v, seed)
 }
 
+/// The minimum size of a secret in bytes.
+pub const SECRET_SIZE_MIN: usize = 136;
+
+/// The size of a secret generated by `generate_secret` in bytes.
+pub const SECRET_DEFAULT_SIZE: usize = 192;
+
+/// Derives a secret from an arbitrary long custom seed, e.g. a passphrase or a random key.
+///
+/// The same custom seed always generates the same secret,
+/// and an empty custom seed generates the default secret.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::xxh3;
+///
+/// let secret = xxh3::generate_secret(b"my secret key");
+///
+/// assert_eq!(secret.len(), xxh3::SECRET_DEFAULT_SIZE);
+/// assert_eq!(
+///     xxh3::hash64_with_secret("hello world", &secret),
+///     xxh3::hash64_with_secret("hello world", &xxh3::generate_secret(b"my secret key")),
+/// );
+/// assert_ne!(xxh3::hash64_with_secret("hello world", &secret), xxh3::hash64("hello world"));
+/// ```
+pub fn generate_secret(custom_seed: &[u8]) -> Vec<u8> {
+    let mut secret = alloc::vec![0; SECRET_DEFAULT_SIZE];
+
+    unsafe {
+        ffi::XXH3_generateSecret(
+            secret.as_mut_ptr() as *mut _,
+            custom_seed.as_ptr() as *const _,
+            custom_seed.len(),
+        );
+    }
+
+    secret
+}
+
+/// 64-bit hash function for a byte array with a secret.
+///
+/// # Panics
+///
+/// Panics if the secret is shorter than `SECRET_SIZE_MIN` bytes.
+#[inline(always)]
+pub fn hash64_with_secret<T: AsRef<[u8]>>(v: T, secret: &[u8]) -> u64 {
+    let bytes = v.as_ref();
+
+    assert!(
+        secret.len() >= SECRET_SIZE_MIN,
+        "secret should be at least {} bytes",
+        SECRET_SIZE_MIN
+    );
+
+    unsafe {
+        ffi::XXH3_64bits_withSecret(
+            bytes.as_ptr() as *const _,
+            bytes.len(),
+            secret.as_ptr() as *const _,
+            secret.len(),
+        )
+    }
+}
+
+/// 128-bit hash function for a byte array with a secret.
+///
+/// # Panics
+///
+/// Panics if the secret is shorter than `SECRET_SIZE_MIN` bytes.
+#[inline(always)]
+pub fn hash128_with_secret<T: AsRef<[u8]>>(v: T, secret: &[u8]) -> u128 {
+    let bytes = v.as_ref();
+
+    assert!(
+        secret.len() >= SECRET_SIZE_MIN,
+        "secret should be at least {} bytes",
+        SECRET_SIZE_MIN
+    );
+
+    unsafe {
+        mem::transmute(ffi::XXH3_128bits_withSecret(
+            bytes.as_ptr() as *const _,
+            bytes.len(),
+            secret.as_ptr() as *const _,
+            secret.len(),
+        ))
+    }
+}
+
 /// An implementation of `std::hash::Hasher`.
 ///
 /// # Example
@@ -127,22 +227,81 @@ impl FastHash for Hash64 {
 /// h.write(b"world");
 /// assert_eq!(h.finish(), 18436838148490100038);
 /// ```
-pub struct Hasher64(NonNull<ffi::XXH3_state_t>);
+pub struct Hasher64 {
+    state: NonNull<ffi::XXH3_state_t>,
+    // the state only points to the secret, so the hasher owns a copy of it
+    secret: Option<Box<[u8]>>,
+}
+
+impl Hasher64 {
+    /// Creates a hasher with a secret, e.g. generated by `generate_secret`.
+    ///
+    /// The hasher hashes the same as `hash64_with_secret` with the secret.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret is shorter than `SECRET_SIZE_MIN` bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::xxh3::{self, Hasher64};
+    ///
+    /// let secret = xxh3::generate_secret(b"my secret key");
+    /// let mut h = Hasher64::with_secret(&secret);
+    ///
+    /// h.write(b"hello");
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), xxh3::hash64_with_secret(b"helloworld", &secret) as u64);
+    /// ```
+    pub fn with_secret(secret: &[u8]) -> Self {
+        assert!(
+            secret.len() >= SECRET_SIZE_MIN,
+            "secret should be at least {} bytes",
+            SECRET_SIZE_MIN
+        );
+
+        let secret: Box<[u8]> = secret.into();
+
+        unsafe {
+            let state = new_state();
+
+            ffi::XXH3_64bits_reset_withSecret(
+                state.as_ptr(),
+                secret.as_ptr() as *const _,
+                secret.len(),
+            );
+
+            Hasher64 {
+                state,
+                secret: Some(secret),
+            }
+        }
+    }
+}
 
 impl Default for Hasher64 {
     fn default() -> Self {
-        Hasher64(unsafe { NonNull::new_unchecked(ffi::XXH3_createState()) })
+        Self::new()
     }
 }
 
 impl Clone for Hasher64 {
     fn clone(&self) -> Self {
         unsafe {
-            let state = ffi::XXH3_createState();
+            let state = new_state();
+
+            ffi::XXH3_copyState(state.as_ptr(), self.state.as_ptr());
+
+            let secret = self.secret.clone();
 
-            ffi::XXH3_copyState(state, self.0.as_ptr());
+            if let Some(ref secret) = secret {
+                (*state.as_ptr()).extSecret = secret.as_ptr();
+            }
 
-            Hasher64(NonNull::new_unchecked(state))
+            Hasher64 { state, secret }
         }
     }
 }
@@ -150,7 +309,7 @@ impl Clone for Hasher64 {
 impl Drop for Hasher64 {
     fn drop(&mut self) {
         unsafe {
-            ffi::XXH3_freeState(self.0.as_ptr());
+            ffi::XXH3_freeState(self.state.as_ptr());
         }
     }
 }
@@ -158,13 +317,17 @@ impl Drop for Hasher64 {
 impl Hasher for Hasher64 {
     #[inline(always)]
     fn finish(&self) -> u64 {
-        unsafe { ffi::XXH3_64bits_digest(self.0.as_ptr()) }
+        unsafe { ffi::XXH3_64bits_digest(self.state.as_ptr()) }
     }
 
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
         unsafe {
-            ffi::XXH3_64bits_update(self.0.as_ptr(), bytes.as_ptr() as *const _, bytes.len());
+            ffi::XXH3_64bits_update(self.state.as_ptr(), bytes.as_ptr() as *const _, bytes.len());
         }
     }
 }
@@ -176,18 +339,45 @@ impl FastHasher for Hasher64 {
     #[inline(always)]
     fn with_seed(seed: u64) -> Self {
         unsafe {
-            let state = ffi::XXH3_createState();
+            let state = new_state();
 
-            ffi::XXH3_64bits_reset_withSeed(state, seed);
+            ffi::XXH3_64bits_reset_withSeed(state.as_ptr(), seed);
 
-            Hasher64(NonNull::new_unchecked(state))
+            Hasher64 {
+                state,
+                secret: None,
+            }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for Hasher64 {}
 
+impl ResetHasher for Hasher64 {
+    #[inline(always)]
+    fn reset(&mut self) {
+        unsafe {
+            let state = self.state.as_ptr();
+
+            match self.secret {
+                Some(ref secret) => {
+                    ffi::XXH3_64bits_reset_withSecret(
+                        state,
+                        secret.as_ptr() as *const _,
+                        secret.len(),
+                    );
+                }
+                None => {
+                    ffi::XXH3_64bits_reset_withSeed(state, (*state).seed);
+                }
+            }
+        }
+    }
+}
+
 impl_build_hasher!(Hasher64, Hash64);
+impl_write!(Hasher64);
 
 /// An implementation of `std::hash::Hasher`.
 ///
@@ -252,22 +442,81 @@ impl FastHash for Hash128 {
 /// h.write(b"world");
 /// assert_eq!(h.finish_ext(), 295047064626328774025194411167141021551);
 /// ```
-pub struct Hasher128(NonNull<ffi::XXH3_state_t>);
+pub struct Hasher128 {
+    state: NonNull<ffi::XXH3_state_t>,
+    // the state only points to the secret, so the hasher owns a copy of it
+    secret: Option<Box<[u8]>>,
+}
+
+impl Hasher128 {
+    /// Creates a hasher with a secret, e.g. generated by `generate_secret`.
+    ///
+    /// The hasher hashes the same as `hash128_with_secret` with the secret.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret is shorter than `SECRET_SIZE_MIN` bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// use fasthash::xxh3::{self, Hasher128};
+    ///
+    /// let secret = xxh3::generate_secret(b"my secret key");
+    /// let mut h = Hasher128::with_secret(&secret);
+    ///
+    /// h.write(b"hello");
+    /// h.write(b"world");
+    /// assert_eq!(h.finish(), xxh3::hash128_with_secret(b"helloworld", &secret) as u64);
+    /// ```
+    pub fn with_secret(secret: &[u8]) -> Self {
+        assert!(
+            secret.len() >= SECRET_SIZE_MIN,
+            "secret should be at least {} bytes",
+            SECRET_SIZE_MIN
+        );
+
+        let secret: Box<[u8]> = secret.into();
+
+        unsafe {
+            let state = new_state();
+
+            ffi::XXH3_128bits_reset_withSecret(
+                state.as_ptr(),
+                secret.as_ptr() as *const _,
+                secret.len(),
+            );
+
+            Hasher128 {
+                state,
+                secret: Some(secret),
+            }
+        }
+    }
+}
 
 impl Default for Hasher128 {
     fn default() -> Self {
-        Hasher128(unsafe { NonNull::new_unchecked(ffi::XXH3_createState()) })
+        Self::new()
     }
 }
 
 impl Clone for Hasher128 {
     fn clone(&self) -> Self {
         unsafe {
-            let state = ffi::XXH3_createState();
+            let state = new_state();
 
-            ffi::XXH3_copyState(state, self.0.as_ptr());
+            ffi::XXH3_copyState(state.as_ptr(), self.state.as_ptr());
 
-            Hasher128(NonNull::new_unchecked(state))
+            let secret = self.secret.clone();
+
+            if let Some(ref secret) = secret {
+                (*state.as_ptr()).extSecret = secret.as_ptr();
+            }
+
+            Hasher128 { state, secret }
         }
     }
 }
@@ -275,7 +524,7 @@ impl Clone for Hasher128 {
 impl Drop for Hasher128 {
     fn drop(&mut self) {
         unsafe {
-            ffi::XXH3_freeState(self.0.as_ptr());
+            ffi::XXH3_freeState(self.state.as_ptr());
         }
     }
 }
@@ -283,13 +532,17 @@ impl Drop for Hasher128 {
 impl Hasher for Hasher128 {
     #[inline(always)]
     fn finish(&self) -> u64 {
-        unsafe { ffi::XXH3_128bits_digest(self.0.as_ptr()).low64 }
+        unsafe { ffi::XXH3_128bits_digest(self.state.as_ptr()).low64 }
     }
 
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
         unsafe {
-            ffi::XXH3_128bits_update(self.0.as_ptr(), bytes.as_ptr() as *const _, bytes.len());
+            ffi::XXH3_128bits_update(self.state.as_ptr(), bytes.as_ptr() as *const _, bytes.len());
         }
     }
 }
@@ -297,7 +550,7 @@ impl Hasher for Hasher128 {
 impl HasherExt for Hasher128 {
     #[inline(always)]
     fn finish_ext(&self) -> u128 {
-        let h = unsafe { ffi::XXH3_128bits_digest(self.0.as_ptr()) };
+        let h = unsafe { ffi::XXH3_128bits_digest(self.state.as_ptr()) };
 
         u128::from(h.low64) + (u128::from(h.high64) << 64)
     }
@@ -310,15 +563,140 @@ impl FastHasher for Hasher128 {
     #[inline(always)]
     fn with_seed(seed: u64) -> Self {
         unsafe {
-            let state = ffi::XXH3_createState();
+            let state = new_state();
 
-            ffi::XXH3_128bits_reset_withSeed(state, seed);
+            ffi::XXH3_128bits_reset_withSeed(state.as_ptr(), seed);
 
-            Hasher128(NonNull::new_unchecked(state))
+            Hasher128 {
+                state,
+                secret: None,
+            }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for Hasher128 {}
 
+impl ResetHasher for Hasher128 {
+    #[inline(always)]
+    fn reset(&mut self) {
+        unsafe {
+            let state = self.state.as_ptr();
+
+            match self.secret {
+                Some(ref secret) => {
+                    ffi::XXH3_128bits_reset_withSecret(
+                        state,
+                        secret.as_ptr() as *const _,
+                        secret.len(),
+                    );
+                }
+                None => {
+                    ffi::XXH3_128bits_reset_withSeed(state, (*state).seed);
+                }
+            }
+        }
+    }
+}
+
 impl_build_hasher!(Hasher128, Hash128);
+impl_write!(Hasher128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret(b"custom seed");
+
+        assert_eq!(secret.len(), SECRET_DEFAULT_SIZE);
+        assert_eq!(secret, generate_secret(b"custom seed"));
+        assert_ne!(secret, generate_secret(b"another seed"));
+
+        for data in &[&b""[..], &b"hello world"[..], &[0x5a; 1000][..]] {
+            assert_eq!(
+                hash64_with_secret(data, &secret),
+                hash64_with_secret(data, &secret)
+            );
+            assert_eq!(
+                hash128_with_secret(data, &secret),
+                hash128_with_secret(data, &secret)
+            );
+            assert_ne!(hash64_with_secret(data, &secret), hash64(data));
+        }
+    }
+
+    #[test]
+    fn test_default_secret() {
+        let secret = generate_secret(b"");
+
+        for data in &[&b""[..], &b"hello world"[..], &[0x5a; 1000][..]] {
+            assert_eq!(hash64_with_secret(data, &secret), hash64(data));
+            assert_eq!(hash128_with_secret(data, &secret), hash128(data));
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let data = (0..5000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        let secret = generate_secret(b"custom seed");
+
+        for &len in &[0, 1, 16, 128, 240, 241, 1024, 5000] {
+            let data = &data[..len];
+
+            for &chunk in &[1, 7, 64, 1000] {
+                let mut h64 = Hasher64::with_seed(123);
+                let mut h128 = Hasher128::with_seed(123);
+                let mut s64 = Hasher64::with_secret(&secret);
+                let mut s128 = Hasher128::with_secret(&secret);
+
+                for bytes in data.chunks(chunk) {
+                    h64.write(bytes);
+                    h128.write(bytes);
+                    s64.write(bytes);
+                    s128.write(bytes);
+                }
+
+                assert_eq!(h64.finish(), hash64_with_seed(data, 123));
+                assert_eq!(h128.finish_ext(), hash128_with_seed(data, 123));
+                assert_eq!(s64.finish(), hash64_with_secret(data, &secret));
+                assert_eq!(s128.finish_ext(), hash128_with_secret(data, &secret));
+            }
+        }
+    }
+
+    #[test]
+    fn test_secret_reset_and_clone() {
+        let secret = generate_secret(b"custom seed");
+        let data = [0x5a_u8; 1000];
+
+        let mut h = Hasher64::with_secret(&secret);
+        h.write(b"hello");
+        h.reset();
+        h.write(&data);
+        assert_eq!(h.finish(), hash64_with_secret(&data[..], &secret));
+
+        let mut h = Hasher128::with_secret(&secret);
+        h.write(b"hello");
+        h.reset();
+        h.write(&data);
+        assert_eq!(h.finish_ext(), hash128_with_secret(&data[..], &secret));
+
+        let mut h = Hasher64::with_secret(&secret);
+        h.write(&data[..500]);
+        let mut cloned = h.clone();
+        drop(h);
+        cloned.write(&data[500..]);
+        assert_eq!(cloned.finish(), hash64_with_secret(&data[..], &secret));
+        cloned.reset();
+        assert_eq!(cloned.finish(), hash64_with_secret(b"", &secret));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_short_secret() {
+        hash64_with_secret(b"hello", &[0; SECRET_SIZE_MIN - 1]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Checks that the streaming hashers, and the buffered hashers past their
+//! buffer limit, hash huge inputs in bounded memory.
+#![cfg(any(feature = "xx", feature = "spooky"))]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hash::Hasher;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+#[allow(unused_imports)]
+use fasthash::*;
+
+struct PeakAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// the peak is process wide, so the tests measure it one at a time
+static MEASURE: Mutex<()> = Mutex::new(());
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK.fetch_max(allocated, Ordering::SeqCst);
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+const TOTAL: usize = 256 << 20;
+
+/// Writes `TOTAL` bytes to the hasher, returns its hash and the peak memory.
+fn measure<H: Hasher>(mut h: H) -> (u64, usize) {
+    let _guard = MEASURE.lock().unwrap_or_else(|err| err.into_inner());
+    let chunk = vec![0xa5_u8; 64 << 10];
+    let base = ALLOCATED.load(Ordering::SeqCst);
+
+    PEAK.store(base, Ordering::SeqCst);
+
+    for _ in 0..TOTAL / chunk.len() {
+        h.write(&chunk);
+    }
+
+    let hash = h.finish();
+
+    (hash, PEAK.load(Ordering::SeqCst) - base)
+}
+
+#[cfg(feature = "xx")]
+#[test]
+fn test_xxhash64_bounded_memory() {
+    let (hash, peak) = measure(xx::Hasher64::new());
+
+    assert!(peak < 64 << 10);
+    assert_ne!(hash, xx::hash64(b""));
+}
+
+#[cfg(feature = "spooky")]
+#[test]
+fn test_spooky64_spills_past_buffer_limit() {
+    const LIMIT: usize = 1 << 20;
+
+    let (hash, peak) = measure(spooky::Hasher64::new().with_buffer_limit(LIMIT));
+
+    // the buffer grows up to the limit, then the bytes go to the native state
+    assert!(peak < (64 << 10) + 2 * LIMIT);
+
+    let (expected, _) = measure(spooky::Hasher128::with_seed((0, 0)));
+
+    assert_eq!(hash, expected);
+}
@@ -0,0 +1,237 @@
+//! Pins the hash value of the empty input for every algorithm.
+//!
+//! An empty slice is passed to the native functions as a dangling, but never null,
+//! pointer with a zero length, which must not be dereferenced by the C code.
+//! The streaming hashers of the native state skip an empty `write` altogether.
+#![cfg(feature = "std")]
+
+#[allow(unused_imports)]
+use std::hash::{BuildHasher, Hasher};
+
+#[allow(unused_imports)]
+use fasthash::*;
+
+#[test]
+fn test_empty_write_is_noop() {
+    for &algorithm in any::AnyAlgorithm::ALL {
+        let seed = Seed::gen();
+
+        let h = algorithm.hasher_with_seed(seed);
+        let mut h2 = algorithm.hasher_with_seed(seed);
+
+        h2.write(&[]);
+        h2.write(b"");
+
+        assert_eq!(h.finish(), h2.finish(), "{}", algorithm.name());
+    }
+}
+
+#[test]
+fn test_empty_is_deterministic() {
+    for &algorithm in any::AnyAlgorithm::ALL {
+        let seed = Seed::gen();
+
+        assert_eq!(
+            algorithm.hasher_with_seed(seed).finish(),
+            algorithm.hasher_with_seed(seed).finish(),
+            "{}",
+            algorithm.name()
+        );
+    }
+}
+
+// every hash function of the registry, including the ones `AnyAlgorithm` can't select
+macro_rules! for_each_hash {
+    ($test:ident) => {
+        $test! {
+            #[cfg(feature = "ahash")]
+            ahash::Hash64,
+            #[cfg(feature = "city")]
+            city::Hash32,
+            #[cfg(feature = "city")]
+            city::Hash64,
+            #[cfg(feature = "city")]
+            city::Hash128,
+            #[cfg(feature = "city")]
+            city::crc::Hash128,
+            #[cfg(all(feature = "clhash", target_arch = "x86_64"))]
+            clhash::Hash64,
+            #[cfg(feature = "farm")]
+            farm::Hash32,
+            #[cfg(feature = "farm")]
+            farm::Hash64,
+            #[cfg(feature = "farm")]
+            farm::Hash128,
+            #[cfg(feature = "highway")]
+            highway::Hash64,
+            #[cfg(feature = "highway")]
+            highway::Hash128,
+            #[cfg(feature = "lookup3")]
+            lookup3::Hash32,
+            #[cfg(all(feature = "metro", feature = "aes"))]
+            meow::Hash128,
+            #[cfg(feature = "metro")]
+            metro::Hash64_1,
+            #[cfg(feature = "metro")]
+            metro::Hash64_2,
+            #[cfg(feature = "metro")]
+            metro::Hash128_1,
+            #[cfg(feature = "metro")]
+            metro::Hash128_2,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            metro::crc::Hash64_1,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            metro::crc::Hash64_2,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            metro::crc::Hash128_1,
+            #[cfg(all(feature = "metro", any(feature = "sse42", target_feature = "sse4.2")))]
+            metro::crc::Hash128_2,
+            #[cfg(feature = "mum")]
+            mum::Hash64,
+            #[cfg(feature = "murmur")]
+            murmur::Hash32,
+            #[cfg(feature = "murmur")]
+            murmur::Hash32Aligned,
+            #[cfg(feature = "murmur")]
+            murmur2::Hash32,
+            #[cfg(feature = "murmur")]
+            murmur2::Hash32A,
+            #[cfg(feature = "murmur")]
+            murmur2::Hash32Neutral,
+            #[cfg(feature = "murmur")]
+            murmur2::Hash32Aligned,
+            #[cfg(feature = "murmur")]
+            murmur2::Hash64_x64,
+            #[cfg(feature = "murmur")]
+            murmur2::Hash64_x86,
+            #[cfg(feature = "murmur")]
+            murmur3::Hash32,
+            #[cfg(feature = "murmur")]
+            murmur3::Hash128_x86,
+            #[cfg(feature = "murmur")]
+            murmur3::Hash128_x64,
+            #[cfg(feature = "nmhash")]
+            nmhash::Hash32,
+            #[cfg(feature = "nmhash")]
+            nmhash::Hash32X,
+            #[cfg(feature = "pengy")]
+            pengy::Hash64,
+            #[cfg(feature = "seahash")]
+            sea::Hash64,
+            #[cfg(feature = "siphash")]
+            siphash::SipHash13,
+            #[cfg(feature = "siphash")]
+            siphash::SipHash24,
+            #[cfg(feature = "spooky")]
+            spooky::Hash32,
+            #[cfg(feature = "spooky")]
+            spooky::Hash64,
+            #[cfg(feature = "spooky")]
+            spooky::Hash128,
+            #[cfg(feature = "t1ha")]
+            t1ha0::Hash64,
+            #[cfg(feature = "t1ha")]
+            t1ha0::Hash32,
+            #[cfg(feature = "t1ha")]
+            t1ha0::Hash64_32Le,
+            #[cfg(feature = "t1ha")]
+            t1ha0::Hash64_32Be,
+            #[cfg(all(feature = "t1ha", target_endian = "little"))]
+            t1ha1::Hash64Le,
+            #[cfg(all(feature = "t1ha", target_endian = "little"))]
+            t1ha1::Hash64Be,
+            #[cfg(all(feature = "t1ha", target_endian = "big"))]
+            t1ha1::Hash64Le,
+            #[cfg(all(feature = "t1ha", target_endian = "big"))]
+            t1ha1::Hash64Be,
+            #[cfg(feature = "t1ha")]
+            t1ha2::Hash64AtOnce,
+            #[cfg(feature = "t1ha")]
+            t1ha2::Hash128AtOnce,
+            #[cfg(feature = "umash")]
+            umash::Hash64,
+            #[cfg(feature = "umash")]
+            umash::Hash128,
+            #[cfg(feature = "wy")]
+            wy::Hash64,
+            #[cfg(feature = "xx")]
+            xx::Hash32,
+            #[cfg(feature = "xx")]
+            xx::Hash64,
+            #[cfg(feature = "xx")]
+            xxh3::Hash64,
+            #[cfg(feature = "xx")]
+            xxh3::Hash128,
+        }
+    };
+}
+
+macro_rules! test_empty_with_hashes {
+    ($( #[cfg($cfg:meta)] $hash:path, )*) => {
+        $(
+            #[cfg($cfg)]
+            {
+                let name = stringify!($hash);
+                let empty: &[u8] = &[];
+
+                assert_eq!(<$hash>::hash(empty), <$hash>::hash(b""), "{}", name);
+                assert_eq!(<$hash>::hash(Vec::<u8>::new()), <$hash>::hash(b""), "{}", name);
+
+                let seed = Default::default();
+                assert_eq!(
+                    <$hash>::hash_with_seed(empty, seed),
+                    <$hash>::hash_with_seed(b"", seed),
+                    "{}",
+                    name
+                );
+
+                let h = $hash.build_hasher();
+                let mut h2 = $hash.build_hasher();
+
+                h2.write(&[]);
+                h2.write(b"");
+
+                assert_eq!(h.finish(), h2.finish(), "{}", name);
+            }
+        )*
+    };
+}
+
+#[test]
+fn test_empty_with_hashes() {
+    for_each_hash!(test_empty_with_hashes);
+}
+
+#[cfg(feature = "city")]
+#[test]
+fn test_empty_city() {
+    assert_eq!(city::hash64(b""), 11160318154034397263);
+    assert_eq!(city::Hash64::hash(Vec::<u8>::new()), city::hash64(""));
+}
+
+#[cfg(feature = "lookup3")]
+#[test]
+fn test_empty_lookup3() {
+    // an empty input returns the initial state `0xdeadbeef + seed`
+    assert_eq!(lookup3::hash32(b""), 0xdeadbeef);
+    assert_eq!(lookup3::hash32_with_seed(b"", 123), 0xdeadbeef + 123);
+}
+
+#[cfg(feature = "murmur")]
+#[test]
+fn test_empty_murmur() {
+    assert_eq!(murmur::hash32(b""), 0);
+    assert_eq!(murmur2::hash32(b""), 0);
+    assert_eq!(murmur3::hash32(b""), 0);
+    assert_eq!(murmur3::hash128(b""), 0);
+    assert_eq!(murmur3::Hash128_x86::hash(b""), 0);
+}
+
+#[cfg(feature = "xx")]
+#[test]
+fn test_empty_xx() {
+    assert_eq!(xx::hash32(b""), 46947589);
+    assert_eq!(xx::hash64(b""), 17241709254077376921);
+    assert_eq!(xxh3::hash64(b""), 0x2d06800538d394c2);
+    assert_eq!(xxh3::hash128(b""), 0x99aa06d3014798d86001c324468d497f);
+}
@@ -0,0 +1,55 @@
+//! Checks the hashers only need `core`.
+//!
+//! Run it without the `std` feature to build the crate as `no_std`:
+//!
+//! ```sh
+//! cargo test --no-default-features --features xx,murmur,city --test no_std
+//! ```
+#![no_std]
+
+#[allow(unused_imports)]
+use core::hash::Hasher;
+
+#[allow(unused_imports)]
+use fasthash::*;
+
+#[cfg(feature = "xx")]
+#[test]
+fn test_stream_hasher() {
+    let buf = [0x5a_u8; 256];
+    let mut h = xx::Hasher64::with_seed(123);
+
+    for chunk in buf.chunks(100) {
+        h.write(chunk);
+    }
+
+    assert_eq!(h.finish(), xx::hash64_with_seed(&buf[..], 123));
+}
+
+#[cfg(feature = "murmur")]
+#[test]
+fn test_buffered_hasher() {
+    let mut buf = [0_u8; 64];
+    let mut h = murmur3::Hasher32::new();
+
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    h.write(&buf[..32]);
+    h.write(&buf[32..]);
+
+    assert_eq!(h.finish(), u64::from(murmur3::hash32(&buf[..])));
+}
+
+#[cfg(feature = "city")]
+#[test]
+fn test_one_shot() {
+    let buf = *b"hello world";
+
+    assert_eq!(city::hash64(&buf[..]), city::Hash64::hash(b"hello world"));
+    assert_ne!(
+        city::hash64(&buf[..]),
+        city::hash64_with_seed(&buf[..], 123)
+    );
+}
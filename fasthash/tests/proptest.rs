@@ -0,0 +1,70 @@
+//! Property tests asserting that feeding arbitrary chunks of an input
+//! produces the same hash as the one-shot hash of the whole input.
+use std::hash::Hasher;
+
+use proptest::prelude::*;
+
+use fasthash::{FastHash, FastHasher};
+
+fn split_at_points(data: &[u8], mut points: Vec<usize>) -> Vec<&[u8]> {
+    points.iter_mut().for_each(|p| *p %= data.len() + 1);
+    points.sort_unstable();
+
+    let mut chunks = vec![];
+    let mut last = 0;
+
+    for p in points {
+        chunks.push(&data[last..p]);
+        last = p;
+    }
+    chunks.push(&data[last..]);
+
+    chunks
+}
+
+fn hash_chunks<H: FastHasher>(chunks: &[&[u8]]) -> u64 {
+    let mut h = H::new();
+
+    for chunk in chunks {
+        h.write(chunk);
+    }
+
+    h.finish()
+}
+
+fn input() -> impl Strategy<Value = (Vec<u8>, Vec<usize>)> {
+    (
+        prop::collection::vec(any::<u8>(), 0..4096),
+        prop::collection::vec(any::<usize>(), 0..8),
+    )
+}
+
+macro_rules! concat_equals_stream {
+    ($( $(#[$meta:meta])* $name:ident: $hasher:ty => $hash:ty ),* $(,)*) => {$(
+        $(#[$meta])*
+        proptest! {
+            #[test]
+            fn $name((data, points) in input()) {
+                let chunks = split_at_points(&data, points);
+
+                prop_assert_eq!(
+                    hash_chunks::<$hasher>(&chunks),
+                    <$hash as FastHash>::hash(&data) as u64
+                );
+            }
+        }
+    )*};
+}
+
+concat_equals_stream! {
+    #[cfg(feature = "city")]
+    city64: fasthash::city::Hasher64 => fasthash::city::Hash64,
+    #[cfg(feature = "xx")]
+    xx64: fasthash::xx::Hasher64 => fasthash::xx::Hash64,
+    #[cfg(feature = "murmur")]
+    murmur3_32: fasthash::murmur3::Hasher32 => fasthash::murmur3::Hash32,
+    #[cfg(feature = "seahash")]
+    sea64: fasthash::sea::Hasher64 => fasthash::sea::Hash64,
+    #[cfg(feature = "t1ha")]
+    t1ha0_64: fasthash::t1ha0::Hasher64 => fasthash::t1ha0::Hash64,
+}
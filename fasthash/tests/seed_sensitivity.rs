@@ -0,0 +1,44 @@
+//! Checks the seed of every algorithm actually perturbs its hash values.
+//!
+//! A binding which ignores the seed, or passes it in the wrong argument,
+//! hashes the keys the same under any seed.
+use std::hash::Hasher;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use fasthash::*;
+
+const KEYS: usize = 1000;
+
+fn fixed_seed(n: u64) -> Seed {
+    Seed::from_words(StdRng::seed_from_u64(n).gen())
+}
+
+#[test]
+fn test_seed_changes_hash() {
+    let seeds = [fixed_seed(0), fixed_seed(1)];
+
+    for &algorithm in any::AnyAlgorithm::ALL {
+        let unchanged = (0..KEYS)
+            .filter(|i| {
+                let key = format!("key-{}", i);
+                let mut hashes = seeds.iter().map(|&seed| {
+                    let mut h = algorithm.hasher_with_seed(seed);
+                    h.write(key.as_bytes());
+                    h.finish()
+                });
+
+                hashes.next() == hashes.next()
+            })
+            .count();
+
+        // a 32-bit hash may collide by chance, but hardly ever for more than one key
+        assert!(
+            unchanged <= 1,
+            "{} hashes {} of {} keys the same under different seeds",
+            algorithm.name(),
+            unchanged,
+            KEYS
+        );
+    }
+}